@@ -0,0 +1,420 @@
+// Copyright Rouven Bauer
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Derive macros for the [`neo4j`](https://docs.rs/neo4j) crate.
+//!
+//! Don't depend on this crate directly.
+//! Instead, enable the `derive` feature of the `neo4j` crate, which re-exports the macros next to
+//! the traits they implement:
+//!  * `neo4j::driver::FromRecord`
+//!  * `neo4j::value::FromNode` (implements `neo4j::value::FromValueReceive`)
+//!  * `neo4j::value::ToParameters`
+//!
+//! All macros only support structs with named fields.
+//! Fields can be configured with the `#[neo4j(...)]` attribute:
+//!  * `rename = "..."`: use the given name for the column, property, or parameter instead of the
+//!    field's name.
+//!  * `default`: use [`Default::default()`] if the column or property is missing
+//!    (not supported by `ToParameters`).
+//!  * `labels`: fill the field (a `Vec<String>`) with the node's labels
+//!    (only supported by `FromNode`).
+//!
+//! The struct itself can be configured with the `#[neo4j(...)]` attribute as well:
+//!  * `crate = "..."`: the path to the `neo4j` crate (default: `::neo4j`).
+//!    Needed when the `neo4j` crate is renamed in `Cargo.toml` or re-exported by another crate.
+//!
+//! ```
+//! # extern crate neo4j as renamed_neo4j;
+//! use renamed_neo4j::value::ToParameters;
+//!
+//! #[derive(Clone, ToParameters)]
+//! #[neo4j(crate = "renamed_neo4j")]
+//! struct NewPerson {
+//!     name: String,
+//! }
+//!
+//! let parameters = NewPerson {
+//!     name: String::from("Alice"),
+//! }
+//! .to_parameters();
+//! assert_eq!(parameters.len(), 1);
+//! ```
+
+#![doc(test(attr(deny(dead_code))))]
+#![doc(test(attr(deny(unused))))]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Error, Fields, Ident, LitStr, Path, Result, parse_macro_input};
+
+/// Implement `neo4j::driver::FromRecord` by mapping the record's columns to the struct's fields.
+///
+/// Columns are matched by name, not by position.
+/// Additional columns are ignored.
+/// Missing columns are an error unless the field is marked with `#[neo4j(default)]`.
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+///
+/// use neo4j::driver::FromRecord;
+///
+/// #[derive(Debug, PartialEq, FromRecord)]
+/// struct Row {
+///     name: String,
+///     #[neo4j(rename = "yearsOld")]
+///     age: u8,
+///     #[neo4j(default)]
+///     nick: Option<String>,
+/// }
+///
+/// # let driver = doc_test_utils::get_driver();
+/// let result = driver
+///     .execute_query("RETURN 'Alice' AS name, 42 AS yearsOld")
+///     .with_database(Arc::new(String::from("neo4j")))
+///     .run()
+///     .unwrap();
+/// assert_eq!(
+///     result.into_rows::<Row>().unwrap(),
+///     vec![Row {
+///         name: String::from("Alice"),
+///         age: 42,
+///         nick: None
+///     }]
+/// );
+/// ```
+#[proc_macro_derive(FromRecord, attributes(neo4j))]
+pub fn derive_from_record(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_from_record(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Implement `neo4j::value::FromValueReceive` by mapping a node's properties to the struct's
+/// fields.
+///
+/// Missing properties are treated like `null` (e.g., `Option` fields become `None`) unless the
+/// field is marked with `#[neo4j(default)]`, in which case [`Default::default()`] is used.
+/// Additional properties are ignored.
+/// A field marked with `#[neo4j(labels)]` receives the node's labels.
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+///
+/// use neo4j::value::FromNode;
+///
+/// #[derive(Debug, PartialEq, FromNode)]
+/// struct Person {
+///     name: String,
+///     #[neo4j(default)]
+///     friends: Vec<String>,
+///     #[neo4j(labels)]
+///     labels: Vec<String>,
+/// }
+///
+/// # let driver = doc_test_utils::get_driver();
+/// let record = driver
+///     .execute_query("CREATE (n:Person {name: 'Alice'}) RETURN n")
+///     .with_database(Arc::new(String::from("neo4j")))
+///     .run()
+///     .unwrap()
+///     .into_single()
+///     .unwrap();
+/// assert_eq!(
+///     record.get::<Person>("n").unwrap(),
+///     Person {
+///         name: String::from("Alice"),
+///         friends: vec![],
+///         labels: vec![String::from("Person")],
+///     }
+/// );
+/// ```
+#[proc_macro_derive(FromNode, attributes(neo4j))]
+pub fn derive_from_node(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_from_node(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Implement `neo4j::value::ToParameters` by turning each field into a query parameter.
+///
+/// Each field is cloned and converted via `Into<neo4j::ValueSend>`.
+///
+/// # Example
+/// ```
+/// use neo4j::value::ToParameters;
+/// use neo4j::ValueSend;
+///
+/// #[derive(Clone, ToParameters)]
+/// struct NewPerson {
+///     name: String,
+///     #[neo4j(rename = "yearsOld")]
+///     age: i64,
+/// }
+///
+/// let parameters = NewPerson {
+///     name: String::from("Alice"),
+///     age: 42,
+/// }
+/// .to_parameters();
+/// assert_eq!(parameters["name"], ValueSend::from("Alice"));
+/// assert_eq!(parameters["yearsOld"], ValueSend::Integer(42));
+/// ```
+#[proc_macro_derive(ToParameters, attributes(neo4j))]
+pub fn derive_to_parameters(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_to_parameters(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn parse_crate_path(input: &DeriveInput) -> Result<Path> {
+    let mut path = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("neo4j"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                let lit: LitStr = meta.value()?.parse()?;
+                path = Some(lit.parse()?);
+            } else {
+                return Err(meta.error("unknown neo4j attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(path.unwrap_or_else(|| syn::parse_quote!(::neo4j)))
+}
+
+#[derive(Default)]
+struct FieldAttrs {
+    rename: Option<LitStr>,
+    default: bool,
+    labels: bool,
+}
+
+struct Field<'a> {
+    ident: &'a Ident,
+    key: LitStr,
+    attrs: FieldAttrs,
+}
+
+fn parse_field_attrs(field: &syn::Field) -> Result<FieldAttrs> {
+    let mut attrs = FieldAttrs::default();
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("neo4j"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                attrs.rename = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("default") {
+                attrs.default = true;
+            } else if meta.path.is_ident("labels") {
+                attrs.labels = true;
+            } else {
+                return Err(meta.error("unknown neo4j attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    if attrs.labels && (attrs.rename.is_some() || attrs.default) {
+        return Err(Error::new(
+            field.span(),
+            "`labels` cannot be combined with other neo4j attributes",
+        ));
+    }
+    Ok(attrs)
+}
+
+fn fields<'a>(input: &'a DeriveInput, derive: &str) -> Result<Vec<Field<'a>>> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.ident.span(),
+            format!("{derive} can only be derived for structs"),
+        ));
+    };
+    let Fields::Named(named) = &data.fields else {
+        return Err(Error::new(
+            data.fields.span(),
+            format!("{derive} can only be derived for structs with named fields"),
+        ));
+    };
+    named
+        .named
+        .iter()
+        .map(|field| {
+            let ident = field.ident.as_ref().expect("named fields have idents");
+            let attrs = parse_field_attrs(field)?;
+            let key = match &attrs.rename {
+                Some(rename) => rename.clone(),
+                None => LitStr::new(ident.to_string().trim_start_matches("r#"), ident.span()),
+            };
+            Ok(Field { ident, key, attrs })
+        })
+        .collect()
+}
+
+fn reject_labels(fields: &[Field], derive: &str) -> Result<()> {
+    match fields.iter().find(|field| field.attrs.labels) {
+        Some(field) => Err(Error::new(
+            field.ident.span(),
+            format!("`labels` is not supported by {derive}"),
+        )),
+        None => Ok(()),
+    }
+}
+
+fn expand_from_record(input: &DeriveInput) -> Result<TokenStream2> {
+    let fields = fields(input, "FromRecord")?;
+    reject_labels(&fields, "FromRecord")?;
+    let krate = parse_crate_path(input)?;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let field_values = fields.iter().map(|field| {
+        let Field { ident, key, attrs } = field;
+        let missing = if attrs.default {
+            quote!(::std::result::Result::Ok(::std::default::Default::default()))
+        } else {
+            quote!(::std::result::Result::Err(
+                #krate::value::FromValueReceiveError::new("no such key")
+            ))
+        };
+        quote! {
+            #ident: match record.take_value(#key) {
+                ::std::option::Option::Some(value) => {
+                    #krate::value::FromValueReceive::from_value_receive(value)
+                }
+                ::std::option::Option::None => #missing,
+            }
+            .map_err(|e| {
+                e.prepend_path(#krate::value::FromValueReceivePathElement::Key(
+                    ::std::string::String::from(#key),
+                ))
+            })?
+        }
+    });
+    Ok(quote! {
+        impl #impl_generics #krate::driver::FromRecord for #ident #ty_generics #where_clause {
+            fn from_record(
+                mut record: #krate::driver::Record,
+            ) -> ::std::result::Result<Self, #krate::value::FromValueReceiveError> {
+                ::std::result::Result::Ok(Self {
+                    #(#field_values,)*
+                })
+            }
+        }
+    })
+}
+
+fn expand_from_node(input: &DeriveInput) -> Result<TokenStream2> {
+    let fields = fields(input, "FromNode")?;
+    let krate = parse_crate_path(input)?;
+    if let Some(field) = fields.iter().filter(|field| field.attrs.labels).nth(1) {
+        return Err(Error::new(
+            field.ident.span(),
+            "only one field can be marked with `labels`",
+        ));
+    }
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let field_values = fields.iter().map(|field| {
+        let Field { ident, key, attrs } = field;
+        if attrs.labels {
+            return quote!(#ident: ::std::mem::take(&mut node.labels));
+        }
+        let missing = if attrs.default {
+            quote!(::std::result::Result::Ok(::std::default::Default::default()))
+        } else {
+            quote! {
+                #krate::value::FromValueReceive::from_value_receive(#krate::ValueReceive::Null)
+                    .map_err(|_| #krate::value::FromValueReceiveError::new("no such property"))
+            }
+        };
+        quote! {
+            #ident: match node.properties.remove(#key) {
+                ::std::option::Option::Some(value) => {
+                    #krate::value::FromValueReceive::from_value_receive(value)
+                }
+                ::std::option::Option::None => #missing,
+            }
+            .map_err(|e| {
+                e.prepend_path(#krate::value::FromValueReceivePathElement::Key(
+                    ::std::string::String::from(#key),
+                ))
+            })?
+        }
+    });
+    Ok(quote! {
+        impl #impl_generics #krate::value::FromValueReceive for #ident #ty_generics #where_clause {
+            fn from_value_receive(
+                value: #krate::ValueReceive,
+            ) -> ::std::result::Result<Self, #krate::value::FromValueReceiveError> {
+                let mut node = match value {
+                    #krate::ValueReceive::Node(node) => node,
+                    value => {
+                        return ::std::result::Result::Err(
+                            #krate::value::FromValueReceiveError::invalid_type("node", &value),
+                        );
+                    }
+                };
+                ::std::result::Result::Ok(Self {
+                    #(#field_values,)*
+                })
+            }
+        }
+    })
+}
+
+fn expand_to_parameters(input: &DeriveInput) -> Result<TokenStream2> {
+    let fields = fields(input, "ToParameters")?;
+    reject_labels(&fields, "ToParameters")?;
+    let krate = parse_crate_path(input)?;
+    if let Some(field) = fields.iter().find(|field| field.attrs.default) {
+        return Err(Error::new(
+            field.ident.span(),
+            "`default` is not supported by ToParameters",
+        ));
+    }
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let capacity = fields.len();
+    let inserts = fields.iter().map(|Field { ident, key, .. }| {
+        quote! {
+            parameters.insert(
+                ::std::string::String::from(#key),
+                #krate::ValueSend::from(::std::clone::Clone::clone(&self.#ident)),
+            );
+        }
+    });
+    Ok(quote! {
+        impl #impl_generics #krate::value::ToParameters for #ident #ty_generics #where_clause {
+            fn to_parameters(
+                &self,
+            ) -> ::std::collections::HashMap<::std::string::String, #krate::ValueSend> {
+                let mut parameters = ::std::collections::HashMap::with_capacity(#capacity);
+                #(#inserts)*
+                parameters
+            }
+        }
+    })
+}
@@ -17,7 +17,9 @@ pub(crate) mod resolution;
 use std::fmt::{Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::io::Result as IoResult;
+use std::io::{Error as IoError, ErrorKind as IoErrorKind};
 use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::vec::IntoIter;
@@ -27,7 +29,7 @@ use resolution::{AddressResolver, CustomResolution, DnsResolution};
 
 // imports for docs
 #[allow(unused)]
-use crate::driver::DriverConfig;
+use crate::driver::{ConnectionConfig, DriverConfig};
 
 pub(crate) const DEFAULT_PORT: u16 = 7687;
 const COLON_BYTES: usize = ':'.len_utf8();
@@ -66,24 +68,34 @@ const COLON_BYTES: usize = ':'.len_utf8();
 /// let address = Address::from("[::1]:4321");
 /// assert_eq!(address.host(), "[::1]");
 /// assert_eq!(address.port(), 4321);
+///
+/// // or point to a Unix domain socket
+/// let address = Address::unix_socket("/var/run/neo4j/bolt.sock");
+/// assert_eq!(
+///     address.unix_socket_path(),
+///     Some(std::path::Path::new("/var/run/neo4j/bolt.sock"))
+/// );
 /// ```
 #[derive(Debug, Clone)]
 pub struct Address {
     host: String,
     port: u16,
     key: String,
+    unix_socket_path: Option<PathBuf>,
     pub(crate) is_custom_resolved: bool,
     pub(crate) is_dns_resolved: bool,
 }
 
 /// Note that equality of addresses is defined as equality of its [`Address::unresolved_host()`]
-/// and [`Address::port()`] only.
+/// and [`Address::port()`] (and [`Address::unix_socket_path()`]) only.
 /// Therefore, resolved to different IP addresses coming from the same host are considered equal
 /// if their port is equal as well.
 impl PartialEq for Address {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
-        self.key == other.key && self.port == other.port
+        self.key == other.key
+            && self.port == other.port
+            && self.unix_socket_path == other.unix_socket_path
     }
 }
 
@@ -129,6 +141,36 @@ impl Address {
         )
     }
 
+    /// Create an address pointing to a Unix domain socket.
+    ///
+    /// Such addresses are never passed through the custom address resolver (see
+    /// [`DriverConfig::with_resolver`]) nor DNS resolved.
+    /// [`Address::host()`] and [`Address::unresolved_host()`] return the (lossy) string
+    /// representation of the path and [`Address::port()`] returns `0`.
+    ///
+    /// Unix domain sockets are only supported for direct connections (`bolt+unix://` scheme, see
+    /// [`ConnectionConfig`]) and only on Unix platforms.
+    /// Elsewhere, connecting to such an address fails.
+    pub fn unix_socket(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let host = path.to_string_lossy().into_owned();
+        Self {
+            key: host.clone(),
+            host,
+            port: 0,
+            unix_socket_path: Some(path),
+            is_custom_resolved: true,
+            is_dns_resolved: true,
+        }
+    }
+
+    /// Return the path of the Unix domain socket if this address points to one.
+    ///
+    /// See [`Address::unix_socket()`].
+    pub fn unix_socket_path(&self) -> Option<&Path> {
+        self.unix_socket_path.as_deref()
+    }
+
     fn normalize_ip(host: &str) -> (bool, String) {
         IpAddr::from_str(host)
             .map(|addr| (true, addr.to_string()))
@@ -211,7 +253,9 @@ impl Address {
 
 impl Display for Address {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        if self.host.find(':').is_some() {
+        if self.unix_socket_path.is_some() {
+            write!(f, "unix:{}", self.host)
+        } else if self.host.find(':').is_some() {
             write!(f, "[{}]:{}", self.host, self.port)
         } else {
             write!(f, "{}:{}", self.host, self.port)
@@ -226,6 +270,7 @@ impl From<(String, u16)> for Address {
             host,
             port,
             key,
+            unix_socket_path: None,
             is_custom_resolved: false,
             is_dns_resolved: is_resolved,
         }
@@ -239,6 +284,7 @@ impl From<(&str, u16)> for Address {
             host: String::from(host),
             port,
             key,
+            unix_socket_path: None,
             is_custom_resolved: false,
             is_dns_resolved: is_resolved,
         }
@@ -286,6 +332,7 @@ impl From<&str> for Address {
             host,
             port,
             key,
+            unix_socket_path: None,
             is_custom_resolved: false,
             is_dns_resolved: is_resolved,
         }
@@ -302,6 +349,12 @@ impl ToSocketAddrs for Address {
     type Iter = IntoIter<SocketAddr>;
 
     fn to_socket_addrs(&self) -> std::io::Result<Self::Iter> {
+        if self.unix_socket_path.is_some() {
+            return Err(IoError::new(
+                IoErrorKind::InvalidInput,
+                format!("{self} is a Unix domain socket, not a network address"),
+            ));
+        }
         (self.host.as_str(), self.port).to_socket_addrs()
     }
 }
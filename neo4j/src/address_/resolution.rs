@@ -143,6 +143,7 @@ impl DnsResolution {
                         host: resolved.ip().to_string(),
                         port: resolved.port(),
                         key: address.host.clone(),
+                        unix_socket_path: None,
                         is_custom_resolved: address.is_custom_resolved,
                         is_dns_resolved: true,
                     })
@@ -12,10 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod bulk_write;
 pub(crate) mod config;
 pub(crate) mod eager_result;
 mod home_db_cache;
 pub(crate) mod io;
+mod query_executor;
 pub(crate) mod record;
 pub mod record_stream;
 pub(crate) mod session;
@@ -30,30 +32,43 @@ use std::result::Result as StdResult;
 use std::sync::Arc;
 use std::time::Duration;
 
-#[cfg(feature = "_internal_testkit_backend")]
+use log::warn;
+use rustls::ClientConfig;
+
 use crate::address_::Address;
 use crate::bookmarks::{BookmarkManager, bookmark_managers};
 use crate::error_::Result;
 use crate::value::ValueSend;
+#[cfg(feature = "serde_1")]
+pub use bulk_write::BulkWriteSerializeError;
+pub use bulk_write::{BulkWriteBuilder, BulkWriteProgress, BulkWriteSummary, FailedBatch};
 use config::auth::AuthToken;
+use config::client_certificate::{ClientCertificateProvider, ClientCertificateResolver};
 pub use config::{
-    ConfigureFetchSizeError, ConnectionConfig, ConnectionConfigParseError, DriverConfig,
-    InvalidRoutingContextError, KeepAliveConfig, TlsConfigError,
+    ConfigureFetchSizeError, ConfigureRoutingError, ConnectionConfig, ConnectionConfigParseError,
+    DriverConfig, InvalidRoutingContextError, KeepAliveConfig, TlsConfigError,
 };
 pub use eager_result::{EagerResult, ScalarError};
-use home_db_cache::HomeDbCache;
-#[cfg(feature = "_internal_testkit_backend")]
-pub use io::ConnectionPoolMetrics;
+use home_db_cache::{HomeDbCache, HomeDbCacheKey};
 use io::bolt::message_parameters::TelemetryAPI;
-use io::{AcquireConfig, Pool, PoolConfig, PooledBolt, SessionAuth, UpdateRtArgs, UpdateRtDb};
+use io::{
+    AcquireConfig, Pool, PoolConfig, PoolRef, PooledBolt, SessionAuth, UpdateRtArgs, UpdateRtDb,
+};
+pub use io::{
+    CircuitBreakerConfig, CircuitState, ConfigureCircuitBreakerError, ConnectionPoolMetrics,
+    ConnectionPoolObserver, HousekeepingConfig, RoutingTableSnapshot,
+};
+#[cfg(feature = "derive")]
+pub use neo4j_derive::FromRecord;
 use notification::NotificationFilter;
-pub use record::Record;
+pub use query_executor::{Query, QueryExecutor, TransactionExecutor};
+pub use record::{FromRecord, Record};
 use record_stream::RecordStream;
 use session::config::InternalSessionConfig;
-use session::retry::RetryPolicy;
+use session::retry::{ExponentialBackoff, RetryPolicy};
 use session::{
-    DefaultMeta, DefaultMetaKey, DefaultParam, DefaultParamKey, DefaultReceiver, Session,
-    SessionConfig, default_receiver,
+    DefaultMeta, DefaultMetaKey, DefaultParam, DefaultParamKey, DefaultReceiver, OwnedSession,
+    Session, SessionConfig, default_receiver,
 };
 use summary::ServerInfo;
 use transaction::TransactionTimeout;
@@ -67,6 +82,15 @@ use session::TransactionBuilder;
 pub mod auth {
     pub use super::config::auth::*;
 }
+pub mod client_certificate {
+    pub use super::config::client_certificate::*;
+}
+pub mod connector {
+    pub use super::config::connector::*;
+}
+pub mod load_balancing {
+    pub use super::config::load_balancing::*;
+}
 pub mod notification {
     pub use super::config::notification::*;
 }
@@ -79,7 +103,7 @@ pub mod notification {
 #[derive(Debug)]
 pub struct Driver {
     config: ReducedDriverConfig,
-    pool: Pool,
+    pool: Arc<Pool>,
     home_db_cache: Arc<HomeDbCache>,
     capability_check_config: SessionConfig,
     execute_query_bookmark_manager: Arc<dyn BookmarkManager>,
@@ -105,17 +129,27 @@ impl Driver {
         }
         let pool_config = PoolConfig {
             routing_context: connection_config.routing_context,
-            tls_config: connection_config.tls_config.map(Arc::new),
+            tls_config: Self::tls_config(
+                connection_config.tls_config,
+                connection_config.client_certificate,
+            ),
             user_agent: config.user_agent,
             auth: config.auth,
             max_connection_lifetime: config.max_connection_lifetime,
+            idle_time_before_connection_test: config.idle_time_before_connection_test,
             max_connection_pool_size: config.max_connection_pool_size,
+            min_idle_connections: config.min_idle_connections,
             connection_timeout: config.connection_timeout,
             keep_alive: config.keep_alive,
             connection_acquisition_timeout: config.connection_acquisition_timeout,
             resolver: config.resolver,
             notification_filters: Arc::new(config.notification_filter),
             telemetry: config.telemetry,
+            observer: config.connection_pool_observer,
+            connector: config.connector,
+            load_balancing_strategy: config.load_balancing_strategy,
+            circuit_breaker: config.circuit_breaker,
+            housekeeping: config.housekeeping,
         };
         Driver {
             config: ReducedDriverConfig {
@@ -130,6 +164,24 @@ impl Driver {
         }
     }
 
+    fn tls_config(
+        tls_config: Option<ClientConfig>,
+        client_certificate: Option<Arc<dyn ClientCertificateProvider>>,
+    ) -> Option<Arc<ClientConfig>> {
+        match (tls_config, client_certificate) {
+            (Some(mut tls_config), Some(provider)) => {
+                tls_config.client_auth_cert_resolver =
+                    Arc::new(ClientCertificateResolver::new(provider));
+                Some(Arc::new(tls_config))
+            }
+            (None, Some(_)) => {
+                warn!("client certificate configured without encryption: ignoring certificate");
+                None
+            }
+            (tls_config, None) => tls_config.map(Arc::new),
+        }
+    }
+
     /// Spawn a new [`Session`] with the given [`SessionConfig`].
     ///
     /// This is the main entry point for advanced usage of the driver, when
@@ -174,9 +226,53 @@ impl Driver {
         };
         Session::new(
             config,
-            &self.pool,
+            PoolRef::Borrowed(&self.pool),
             Arc::clone(&self.home_db_cache),
-            &self.config,
+            self.config,
+        )
+    }
+
+    /// Spawn a new [`OwnedSession`] with the given [`SessionConfig`].
+    ///
+    /// Same as [`Driver::session()`], except that the returned session doesn't borrow the driver.
+    /// Instead, it shares the driver's connection pool, keeping it alive.
+    /// This allows storing the session next to an `Arc<Driver>`, returning it from functions,
+    /// or handing it to other threads.
+    /// Use [`OwnedSession::begin_transaction()`] to start transactions that don't borrow the
+    /// session either.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::thread;
+    ///
+    /// use neo4j::driver::Driver;
+    /// use neo4j::session::{OwnedSession, SessionConfig};
+    ///
+    /// fn make_session(driver: &Driver) -> OwnedSession {
+    ///     driver.owned_session(SessionConfig::new().with_database(Arc::new(String::from("neo4j"))))
+    /// }
+    ///
+    /// # let driver = doc_test_utils::get_driver();
+    /// let mut session = make_session(&driver);
+    /// let worker = thread::spawn(move || {
+    ///     let transaction = session.begin_transaction()?;
+    ///     transaction.query("RETURN 1 AS n").run()?.consume()?;
+    ///     transaction.commit()
+    /// });
+    /// worker.join().unwrap().unwrap();
+    /// ```
+    pub fn owned_session(&self, config: SessionConfig) -> OwnedSession {
+        let config = InternalSessionConfig {
+            config,
+            idle_time_before_connection_test: self.config.idle_time_before_connection_test,
+            eager_begin: true,
+        };
+        Session::new(
+            config,
+            PoolRef::Shared(Arc::clone(&self.pool)),
+            Arc::clone(&self.home_db_cache),
+            self.config,
         )
     }
 
@@ -207,9 +303,9 @@ impl Driver {
         };
         Session::new(
             config,
-            &self.pool,
+            PoolRef::Borrowed(&self.pool),
             Arc::clone(&self.home_db_cache),
-            &self.config,
+            self.config,
         )
     }
 
@@ -308,6 +404,50 @@ impl Driver {
         Arc::clone(&self.execute_query_bookmark_manager)
     }
 
+    /// Write many rows in batches, each batch in its own transaction.
+    ///
+    /// Use the returned [`BulkWriteBuilder`] to configure the batches and run them.
+    ///
+    /// The rows passed to [`BulkWriteBuilder::run()`] are split into batches.
+    /// Each batch is passed to `query` as a list parameter called `rows`
+    /// (e.g., `UNWIND $rows AS row CREATE (n:Node) SET n = row`).
+    /// Every batch is run in a managed transaction and retried with the configured
+    /// [`RetryPolicy`].
+    /// Batches are [causally chained](crate#causal-consistency) with
+    /// [`Driver::execute_query()`] calls using the default bookmark manager.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use neo4j::retry::ExponentialBackoff;
+    /// use neo4j::{value_map, ValueSend};
+    ///
+    /// # doc_test_utils::db_exclusive(|| {
+    /// # let driver = doc_test_utils::get_driver();
+    /// let rows = (0..2500).map(|id| ValueSend::Map(value_map!({ "id": id })));
+    /// let summary = driver
+    ///     .bulk_write("UNWIND $rows AS row CREATE (n:Node {id: row.id})")
+    ///     .with_database(Arc::new(String::from("neo4j")))
+    ///     .with_batch_size(1000)
+    ///     .with_parallelism(2)
+    ///     .with_retry_policy(ExponentialBackoff::new())
+    ///     .with_progress_callback(|progress| println!("{} rows written", progress.rows_written))
+    ///     .run(rows);
+    ///
+    /// assert!(summary.failed_batches.is_empty());
+    /// assert_eq!(summary.rows_written, 2500);
+    /// assert_eq!(summary.counters.nodes_created, 2500);
+    /// # doc_test_utils::wipe_db(&driver);
+    /// # });
+    /// ```
+    pub fn bulk_write<Q: AsRef<str>>(
+        &self,
+        query: Q,
+    ) -> BulkWriteBuilder<'_, Q, ExponentialBackoff, fn(&BulkWriteProgress)> {
+        BulkWriteBuilder::new(self, query)
+    }
+
     /// Make sure the driver can connect to the DBMS.
     ///
     /// This is equivalent to calling [`Driver::get_server_info()`], but ignoring the returned
@@ -316,6 +456,40 @@ impl Driver {
         self.acquire_connectivity_checked().map(drop)
     }
 
+    /// Open connections up front so that the first queries don't have to.
+    ///
+    /// Fills each connection pool up to the minimum configured with
+    /// [`DriverConfig::with_min_idle_connections()`].
+    /// When routing, these are the pools of all readers and writers in the routing tables the
+    /// driver knows.
+    /// If it doesn't know any yet, the routing table of the home database is fetched first.
+    ///
+    /// Does nothing if no minimum is configured.
+    ///
+    /// # Errors
+    /// A [`Neo4jError`] if fetching the routing table fails or if any pool could not be filled.
+    /// The driver still tries to fill all other pools before returning the first error.
+    pub fn warm_up(&self) -> Result<()> {
+        if self.pool.config.min_idle_connections == 0 {
+            return Ok(());
+        }
+        let cache_home_db = |db: Option<Arc<String>>| {
+            if let Some(db) = db {
+                self.home_db_cache
+                    .update(HomeDbCacheKey::new(None, None), db);
+            }
+        };
+        self.pool.warm_up(UpdateRtArgs {
+            db: None,
+            bookmarks: None,
+            imp_user: None,
+            deadline: self.pool.config.connection_acquisition_deadline(),
+            session_auth: SessionAuth::None,
+            idle_time_before_connection_test: None,
+            db_resolution_cb: Some(&cache_home_db),
+        })
+    }
+
     /// Get information about the DBMS the driver is connected to.
     ///
     /// When connecting to a cluster, this method makes no guarantees about which cluster member
@@ -346,9 +520,9 @@ impl Driver {
         };
         let mut session = Session::new(
             config,
-            &self.pool,
+            PoolRef::Borrowed(&self.pool),
             Arc::clone(&self.home_db_cache),
-            &self.config,
+            self.config,
         );
         session
             .acquire_connection(RoutingControl::Read)
@@ -388,7 +562,13 @@ impl Driver {
         self.pool.is_encrypted()
     }
 
-    #[cfg(feature = "_internal_testkit_backend")]
+    /// Get the current metrics of the connection pool for the given address.
+    ///
+    /// Returns [`None`] if the driver currently has no pool for that address.
+    /// When routing, pools are created and removed as servers join and leave the routing tables.
+    ///
+    /// See also [`Driver::get_all_connection_pool_metrics()`] and
+    /// [`DriverConfig::with_connection_pool_observer()`].
     #[inline]
     pub fn get_connection_pool_metrics(
         &self,
@@ -397,6 +577,100 @@ impl Driver {
         self.pool.get_metrics(address)
     }
 
+    /// Get the current metrics of all connection pools of the driver, keyed by server address.
+    ///
+    /// See also [`Driver::get_connection_pool_metrics()`].
+    #[inline]
+    pub fn get_all_connection_pool_metrics(&self) -> HashMap<Arc<Address>, ConnectionPoolMetrics> {
+        self.pool.get_all_metrics()
+    }
+
+    /// Get the current state of the circuit breaker for the given server address.
+    ///
+    /// Returns [`None`] if the circuit breaker is disabled
+    /// (see [`DriverConfig::with_circuit_breaker()`]), if routing is disabled
+    /// (see [`ConnectionConfig::with_routing()`]), or if the driver hasn't used the server yet.
+    ///
+    /// See also [`Driver::get_all_circuit_states()`].
+    #[inline]
+    pub fn get_circuit_state(&self, address: Arc<Address>) -> Option<CircuitState> {
+        self.pool.get_circuit_state(&address)
+    }
+
+    /// Get the current state of the circuit breakers of all servers the driver keeps track of.
+    ///
+    /// Servers that drop out of all routing tables are forgotten once their circuit is no longer
+    /// open.
+    ///
+    /// See also [`Driver::get_circuit_state()`].
+    #[inline]
+    pub fn get_all_circuit_states(&self) -> HashMap<Arc<Address>, CircuitState> {
+        self.pool.get_all_circuit_states()
+    }
+
+    /// Get a snapshot of the routing table the driver currently holds for the given database.
+    ///
+    /// If `database` is [`None`], the routing table of the home database is returned.
+    /// This only works after the driver has resolved the home database, e.g., by running work
+    /// against it or through [`Driver::refresh_routing_table()`].
+    ///
+    /// Returns [`None`] if the driver holds no routing table for the database or if routing is
+    /// disabled (see [`ConnectionConfig::with_routing()`]).
+    pub fn routing_table(&self, database: Option<Arc<String>>) -> Option<RoutingTableSnapshot> {
+        self.pool.routing_table(&self.routing_table_db(database))
+    }
+
+    /// Fetch a new routing table for the given database, even if the current one is still valid.
+    ///
+    /// If `database` is [`None`], the home database is resolved in the process.
+    ///
+    /// Returns a snapshot of the new routing table or `Ok(None)` if routing is disabled (see
+    /// [`ConnectionConfig::with_routing()`]).
+    ///
+    /// # Errors
+    /// A [`Neo4jError`] if none of the known routers could provide a routing table.
+    pub fn refresh_routing_table(
+        &self,
+        database: Option<Arc<String>>,
+    ) -> Result<Option<RoutingTableSnapshot>> {
+        let db = database.map(|db| UpdateRtDb { db, guess: false });
+        let cache_home_db = |db: Option<Arc<String>>| {
+            if let Some(db) = db {
+                self.home_db_cache
+                    .update(HomeDbCacheKey::new(None, None), db);
+            }
+        };
+        self.pool.refresh_routing_table(UpdateRtArgs {
+            db: db.as_ref(),
+            bookmarks: None,
+            imp_user: None,
+            deadline: self.pool.config.connection_acquisition_deadline(),
+            session_auth: SessionAuth::None,
+            idle_time_before_connection_test: None,
+            db_resolution_cb: match db {
+                None => Some(&cache_home_db),
+                Some(_) => None,
+            },
+        })
+    }
+
+    /// Mark the routing table for the given database as stale.
+    ///
+    /// The driver forgets the table's readers and writers and fetches a new routing table from
+    /// its routers the next time work is run against the database.
+    /// If `database` is [`None`], the home database's routing table is invalidated (provided the
+    /// driver has resolved the home database before).
+    ///
+    /// Does nothing if routing is disabled (see [`ConnectionConfig::with_routing()`]).
+    pub fn invalidate_routing_table(&self, database: Option<Arc<String>>) {
+        self.pool
+            .invalidate_routing_table(&self.routing_table_db(database))
+    }
+
+    fn routing_table_db(&self, database: Option<Arc<String>>) -> Option<Arc<String>> {
+        database.or_else(|| self.home_db_cache.get(&HomeDbCacheKey::new(None, None)))
+    }
+
     fn acquire_capability_check_connection(&self) -> Result<PooledBolt<'_>> {
         self.pool.acquire(AcquireConfig {
             mode: RoutingControl::Read,
@@ -950,6 +1224,14 @@ impl<
             notification_filter,
             mut receiver,
         } = self;
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "neo4j.execute_query",
+            db.system.name = "neo4j",
+            db.namespace = database.as_ref().map(|db| db.as_str()),
+            neo4j.mode = ?mode,
+        )
+        .entered();
         let mut session = driver.execute_query_session(
             database,
             impersonated_user,
@@ -996,6 +1278,14 @@ impl<
             notification_filter,
             mut receiver,
         } = self;
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "neo4j.execute_query",
+            db.system.name = "neo4j",
+            db.namespace = database.as_ref().map(|db| db.as_str()),
+            neo4j.mode = ?mode,
+        )
+        .entered();
         let mut session = driver.execute_query_session(
             database,
             impersonated_user,
@@ -1047,7 +1337,7 @@ impl<
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone)]
 pub(crate) struct ReducedDriverConfig {
     pub(crate) fetch_size: i64,
     pub(crate) idle_time_before_connection_test: Option<Duration>,
@@ -1067,3 +1357,533 @@ impl RoutingControl {
         }
     }
 }
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use std::collections::VecDeque;
+    use std::io;
+    use std::net::TcpStream;
+    use std::slice;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Instant as StdInstant;
+
+    use parking_lot::Mutex;
+
+    use super::*;
+    use crate::driver::connector::{Connector, Transport};
+    use crate::driver::load_balancing::ZoneAffinity;
+    use crate::testing::test_support::{self, direct_driver, hello_script};
+    use crate::testing::{ClientMessage, ServerMessage, StubScript, StubServer};
+    use crate::value_map;
+
+    fn query_script() -> StubScript {
+        test_support::query_script("RETURN 1 AS n")
+    }
+
+    fn run_read_query(driver: &Driver) -> Result<usize> {
+        driver
+            .execute_query("RETURN 1 AS n")
+            .with_database(neo4j().unwrap())
+            .with_routing_control(RoutingControl::Read)
+            .run()
+            .map(|result| result.records.len())
+    }
+
+    fn route_script(rt: ServerMessage) -> StubScript {
+        hello_script().client(ClientMessage::route()).server(rt)
+    }
+
+    fn routing_driver(router: &StubServer) -> Driver {
+        Driver::new(ConnectionConfig::new(router.address()), DriverConfig::new())
+    }
+
+    fn addresses(addresses: &[Arc<Address>]) -> Vec<String> {
+        addresses
+            .iter()
+            .map(|address| address.to_string())
+            .collect()
+    }
+
+    fn unused_address() -> Address {
+        Address::from(("unused.example.com", 7687))
+    }
+
+    fn neo4j() -> Option<Arc<String>> {
+        Some(Arc::new(String::from("neo4j")))
+    }
+
+    #[test]
+    fn test_routing_table_snapshot() {
+        // never contacted
+        let address = unused_address();
+        let router = StubServer::start(route_script(ServerMessage::routing_table(
+            300,
+            slice::from_ref(&address),
+            slice::from_ref(&address),
+            slice::from_ref(&address),
+        )))
+        .unwrap();
+        let driver = routing_driver(&router);
+
+        assert_eq!(driver.routing_table(neo4j()), None);
+        let refreshed = driver.refresh_routing_table(neo4j()).unwrap().unwrap();
+        let snapshot = driver.routing_table(neo4j()).unwrap();
+
+        assert_eq!(snapshot, refreshed);
+        assert_eq!(snapshot.database, neo4j());
+        assert_eq!(addresses(&snapshot.routers), [address.to_string()]);
+        assert_eq!(addresses(&snapshot.readers), [address.to_string()]);
+        assert_eq!(addresses(&snapshot.writers), [address.to_string()]);
+        assert_eq!(snapshot.ttl, Duration::from_secs(300));
+        assert_eq!(
+            snapshot.expires_at(),
+            Some(snapshot.last_refresh + Duration::from_secs(300))
+        );
+
+        drop(driver);
+        router.finish().unwrap();
+    }
+
+    #[test]
+    fn test_refresh_routing_table_fetches_from_known_routers() {
+        let second_router = StubServer::start(route_script(ServerMessage::routing_table(
+            600,
+            &[unused_address()],
+            &[unused_address()],
+            &[],
+        )))
+        .unwrap();
+        let address = second_router.address();
+        let router = StubServer::start(route_script(ServerMessage::routing_table(
+            300,
+            slice::from_ref(&address),
+            slice::from_ref(&address),
+            slice::from_ref(&address),
+        )))
+        .unwrap();
+        let driver = routing_driver(&router);
+
+        let first = driver.refresh_routing_table(neo4j()).unwrap().unwrap();
+        assert_eq!(first.ttl, Duration::from_secs(300));
+        // still fresh, but refreshed regardless
+        let second = driver.refresh_routing_table(neo4j()).unwrap().unwrap();
+        assert_eq!(second.ttl, Duration::from_secs(600));
+        assert!(second.last_refresh >= first.last_refresh);
+        assert_eq!(driver.routing_table(neo4j()), Some(second));
+
+        drop(driver);
+        router.finish().unwrap();
+        second_router.finish().unwrap();
+    }
+
+    #[test]
+    fn test_refresh_routing_table_resolves_home_db() {
+        let router = StubServer::start(route_script(ServerMessage::success(value_map!({"rt": {
+            "ttl": 300,
+            "db": "neo4j",
+            "servers": [
+                    {"role": "ROUTE", "addresses": ["unused.example.com:7687"]},
+                    {"role": "READ", "addresses": ["unused.example.com:7687"]},
+                ],
+        }}))))
+        .unwrap();
+        let driver = routing_driver(&router);
+
+        assert_eq!(driver.routing_table(None), None);
+        let snapshot = driver.refresh_routing_table(None).unwrap().unwrap();
+        assert_eq!(snapshot.database, neo4j());
+        assert_eq!(driver.routing_table(None), Some(snapshot.clone()));
+        assert_eq!(driver.routing_table(neo4j()), Some(snapshot));
+
+        drop(driver);
+        router.finish().unwrap();
+    }
+
+    #[test]
+    fn test_invalidate_routing_table() {
+        let reader = StubServer::start(query_script()).unwrap();
+        let second_router = StubServer::start(route_script(ServerMessage::routing_table(
+            300,
+            &[reader.address()],
+            &[reader.address()],
+            &[],
+        )))
+        .unwrap();
+        let address = second_router.address();
+        let router = StubServer::start(route_script(ServerMessage::routing_table(
+            300,
+            slice::from_ref(&address),
+            slice::from_ref(&address),
+            slice::from_ref(&address),
+        )))
+        .unwrap();
+        let driver = routing_driver(&router);
+        driver.refresh_routing_table(neo4j()).unwrap();
+
+        driver.invalidate_routing_table(neo4j());
+
+        let snapshot = driver.routing_table(neo4j()).unwrap();
+        assert_eq!(addresses(&snapshot.routers), [address.to_string()]);
+        assert!(snapshot.readers.is_empty());
+        assert!(snapshot.writers.is_empty());
+
+        assert_eq!(run_read_query(&driver).unwrap(), 1);
+        let snapshot = driver.routing_table(neo4j()).unwrap();
+        assert_eq!(addresses(&snapshot.readers), [reader.address().to_string()]);
+
+        drop(driver);
+        router.finish().unwrap();
+        second_router.finish().unwrap();
+        reader.finish().unwrap();
+    }
+
+    #[test]
+    fn test_load_balancing_strategy() {
+        let reader = StubServer::start(query_script()).unwrap();
+        let router = StubServer::start(route_script(ServerMessage::routing_table(
+            300,
+            &[unused_address()],
+            &[unused_address(), reader.address()],
+            &[],
+        )))
+        .unwrap();
+        let reader_host = reader.address().host().to_string();
+        let strategy = ZoneAffinity::new(move |address| address.host() == reader_host);
+        let driver = Driver::new(
+            ConnectionConfig::new(router.address()),
+            DriverConfig::new().with_load_balancing_strategy(Arc::new(strategy)),
+        );
+
+        assert_eq!(run_read_query(&driver).unwrap(), 1);
+        // a failed connection attempt would've removed the unused address
+        let snapshot = driver.routing_table(neo4j()).unwrap();
+        assert_eq!(snapshot.readers.len(), 2);
+
+        drop(driver);
+        router.finish().unwrap();
+        reader.finish().unwrap();
+    }
+
+    #[test]
+    fn test_circuit_breaker_ejects_failing_server() {
+        // nothing listens on this port (anymore)
+        let dead = {
+            let listener = std::net::TcpListener::bind(("127.0.0.1", 0)).unwrap();
+            Address::from(("127.0.0.1", listener.local_addr().unwrap().port()))
+        };
+        let second_reader = StubServer::start(query_script()).unwrap();
+        let second_router = StubServer::start(route_script(ServerMessage::routing_table(
+            300,
+            &[unused_address()],
+            &[dead.clone(), second_reader.address()],
+            &[],
+        )))
+        .unwrap();
+        let first_reader = StubServer::start(query_script()).unwrap();
+        let router = StubServer::start(route_script(ServerMessage::routing_table(
+            300,
+            &[second_router.address()],
+            &[dead.clone(), first_reader.address()],
+            &[],
+        )))
+        .unwrap();
+        let driver = Driver::new(
+            ConnectionConfig::new(router.address()),
+            DriverConfig::new().with_circuit_breaker(
+                CircuitBreakerConfig::new()
+                    .with_consecutive_failure_threshold(1)
+                    .unwrap(),
+            ),
+        );
+
+        assert_eq!(driver.get_circuit_state(Arc::new(dead.clone())), None);
+        assert_eq!(run_read_query(&driver).unwrap(), 1);
+        assert_eq!(
+            driver.get_circuit_state(Arc::new(dead.clone())),
+            Some(CircuitState::Open)
+        );
+        assert_eq!(
+            driver.get_circuit_state(Arc::new(first_reader.address())),
+            Some(CircuitState::Closed)
+        );
+
+        // the refreshed routing table brings the server back, but it stays ejected
+        driver.refresh_routing_table(neo4j()).unwrap();
+        assert_eq!(run_read_query(&driver).unwrap(), 1);
+        let snapshot = driver.routing_table(neo4j()).unwrap();
+        assert_eq!(snapshot.readers.len(), 2);
+        assert_eq!(
+            driver.get_all_circuit_states().get(&dead),
+            Some(&CircuitState::Open)
+        );
+
+        drop(driver);
+        router.finish().unwrap();
+        first_reader.finish().unwrap();
+        second_router.finish().unwrap();
+        second_reader.finish().unwrap();
+    }
+
+    fn wait_for(mut condition: impl FnMut() -> bool) {
+        let deadline = StdInstant::now() + Duration::from_secs(5);
+        while !condition() {
+            assert!(
+                StdInstant::now() < deadline,
+                "timed out waiting for condition"
+            );
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    fn idle_connections(driver: &Driver, address: Address) -> usize {
+        driver
+            .get_connection_pool_metrics(Arc::new(address))
+            .map(|metrics| metrics.idle)
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_warm_up_fills_reader_and_writer_pools() {
+        let reader = StubServer::start(hello_script()).unwrap();
+        let writer = StubServer::start(hello_script()).unwrap();
+        let router = StubServer::start(route_script(ServerMessage::routing_table(
+            300,
+            &[unused_address()],
+            &[reader.address()],
+            &[writer.address()],
+        )))
+        .unwrap();
+        let driver = Driver::new(
+            ConnectionConfig::new(router.address()),
+            DriverConfig::new().with_min_idle_connections(1),
+        );
+
+        driver.warm_up().unwrap();
+        assert_eq!(idle_connections(&driver, reader.address()), 1);
+        assert_eq!(idle_connections(&driver, writer.address()), 1);
+        // already warm => no new connections
+        driver.warm_up().unwrap();
+        assert_eq!(idle_connections(&driver, reader.address()), 1);
+        assert_eq!(idle_connections(&driver, writer.address()), 1);
+
+        drop(driver);
+        router.finish().unwrap();
+        reader.finish().unwrap();
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn test_new_routing_table_fills_pools_in_background() {
+        let reader = StubServer::start(hello_script()).unwrap();
+        let router = StubServer::start(route_script(ServerMessage::routing_table(
+            300,
+            &[unused_address()],
+            &[reader.address()],
+            &[],
+        )))
+        .unwrap();
+        let driver = Driver::new(
+            ConnectionConfig::new(router.address()),
+            DriverConfig::new().with_min_idle_connections(1),
+        );
+
+        driver.refresh_routing_table(neo4j()).unwrap();
+        wait_for(|| idle_connections(&driver, reader.address()) == 1);
+
+        drop(driver);
+        router.finish().unwrap();
+        reader.finish().unwrap();
+    }
+
+    #[test]
+    fn test_warm_up_direct_driver() {
+        let server = StubServer::start(hello_script()).unwrap();
+        let driver = Driver::new(
+            ConnectionConfig::new(server.address())
+                .with_routing(false)
+                .unwrap(),
+            DriverConfig::new().with_min_idle_connections(1),
+        );
+
+        driver.warm_up().unwrap();
+        assert_eq!(idle_connections(&driver, server.address()), 1);
+
+        drop(driver);
+        server.finish().unwrap();
+    }
+
+    /// Connects to the given servers one after the other, regardless of the requested address.
+    #[derive(Debug)]
+    struct ServerSequenceConnector(Mutex<VecDeque<Address>>);
+
+    impl Connector for ServerSequenceConnector {
+        fn connect(
+            &self,
+            _address: &Address,
+            _timeout: Option<Duration>,
+        ) -> io::Result<Box<dyn Transport>> {
+            let address = self.0.lock().pop_front().expect("no server left");
+            Ok(Box::new(TcpStream::connect((
+                address.host(),
+                address.port(),
+            ))?))
+        }
+    }
+
+    #[test]
+    fn test_closed_connection_is_replaced_to_keep_min_idle() {
+        let broken =
+            StubServer::start(hello_script().client(ClientMessage::begin()).close()).unwrap();
+        let replacement = StubServer::start(hello_script()).unwrap();
+        let connector =
+            ServerSequenceConnector(Mutex::new([broken.address(), replacement.address()].into()));
+        let driver = Driver::new(
+            ConnectionConfig::new(broken.address())
+                .with_routing(false)
+                .unwrap(),
+            DriverConfig::new()
+                .with_min_idle_connections(1)
+                .with_connector(Arc::new(connector)),
+        );
+
+        let mut session = driver.session(SessionConfig::new());
+        session.begin_transaction().unwrap_err();
+        wait_for(|| idle_connections(&driver, broken.address()) == 1);
+
+        drop(session);
+        drop(driver);
+        broken.finish().unwrap();
+        replacement.finish().unwrap();
+    }
+
+    #[derive(Debug, Default)]
+    struct ClosedConnectionsObserver {
+        closed: AtomicUsize,
+    }
+
+    impl ConnectionPoolObserver for ClosedConnectionsObserver {
+        fn on_connection_closed(&self, _address: &Address) {
+            self.closed.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_dropping_driver_closes_idle_connections() {
+        let server = StubServer::start(query_script().client(ClientMessage::goodbye())).unwrap();
+        let observer = Arc::new(ClosedConnectionsObserver::default());
+        let driver = Driver::new(
+            ConnectionConfig::new(server.address())
+                .with_routing(false)
+                .unwrap(),
+            DriverConfig::new().with_connection_pool_observer(observer.clone()),
+        );
+
+        assert_eq!(run_read_query(&driver).unwrap(), 1);
+        assert_eq!(observer.closed.load(Ordering::Relaxed), 0);
+
+        drop(driver);
+        assert_eq!(observer.closed.load(Ordering::Relaxed), 1);
+        server.finish().unwrap();
+    }
+
+    fn housekeeping() -> HousekeepingConfig {
+        HousekeepingConfig::new().with_interval(Duration::from_millis(10))
+    }
+
+    #[test]
+    fn test_housekeeping_closes_expired_connections() {
+        let server = StubServer::start(
+            query_script()
+                .client(ClientMessage::goodbye())
+                .with_timeout(Duration::from_secs(5)),
+        )
+        .unwrap();
+        let driver = Driver::new(
+            ConnectionConfig::new(server.address())
+                .with_routing(false)
+                .unwrap(),
+            DriverConfig::new()
+                .with_max_connection_lifetime(Duration::from_millis(50))
+                .with_housekeeping(housekeeping()),
+        );
+
+        let address = server.address();
+
+        assert_eq!(run_read_query(&driver).unwrap(), 1);
+        // finishing before dropping the driver => GOODBYE was sent by the housekeeping thread
+        server.finish().unwrap();
+        wait_for(|| idle_connections(&driver, address.clone()) == 0);
+    }
+
+    #[test]
+    fn test_housekeeping_liveness_checks_idle_connections() {
+        let server = StubServer::start(
+            query_script()
+                .client(ClientMessage::reset())
+                .server(ServerMessage::success(value_map!()))
+                .close()
+                .with_timeout(Duration::from_secs(5)),
+        )
+        .unwrap();
+        let driver = Driver::new(
+            ConnectionConfig::new(server.address())
+                .with_routing(false)
+                .unwrap(),
+            DriverConfig::new()
+                .with_idle_time_before_connection_test(Duration::ZERO)
+                .with_housekeeping(housekeeping()),
+        );
+
+        let address = server.address();
+
+        assert_eq!(run_read_query(&driver).unwrap(), 1);
+        server.finish().unwrap();
+        // the next check fails as the server closed the connection
+        wait_for(|| idle_connections(&driver, address.clone()) == 0);
+    }
+
+    #[test]
+    fn test_housekeeping_prunes_routing_tables_and_pools() {
+        let reader = StubServer::start(
+            query_script()
+                .client(ClientMessage::goodbye())
+                .with_timeout(Duration::from_secs(5)),
+        )
+        .unwrap();
+        let router = StubServer::start(route_script(ServerMessage::routing_table(
+            0,
+            &[unused_address()],
+            &[reader.address()],
+            &[],
+        )))
+        .unwrap();
+        let driver = Driver::new(
+            ConnectionConfig::new(router.address()),
+            DriverConfig::new()
+                .with_housekeeping(housekeeping().with_routing_table_purge_delay(Duration::ZERO)),
+        );
+
+        assert_eq!(run_read_query(&driver).unwrap(), 1);
+        wait_for(|| driver.routing_table(neo4j()).is_none());
+        wait_for(|| driver.get_all_connection_pool_metrics().is_empty());
+        reader.finish().unwrap();
+
+        drop(driver);
+        router.finish().unwrap();
+    }
+
+    #[test]
+    fn test_direct_driver_has_no_routing_table() {
+        let server = StubServer::start(hello_script()).unwrap();
+        let driver = direct_driver(&server);
+
+        assert_eq!(driver.routing_table(neo4j()), None);
+        assert_eq!(driver.refresh_routing_table(neo4j()).unwrap(), None);
+        driver.invalidate_routing_table(neo4j());
+        driver.verify_connectivity().unwrap();
+
+        drop(driver);
+        server.finish().unwrap();
+    }
+}
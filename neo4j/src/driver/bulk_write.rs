@@ -0,0 +1,588 @@
+// Copyright Rouven Bauer
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt::{Debug, Formatter};
+use std::ops::Range;
+use std::sync::{Arc, mpsc};
+use std::thread;
+
+use parking_lot::Mutex;
+#[cfg(feature = "serde_1")]
+use thiserror::Error;
+
+use super::session::SessionConfig;
+use super::summary::Counters;
+use super::{Driver, RoutingControl};
+use crate::retry::{ExponentialBackoff, RetryPolicy};
+use crate::value::ValueSend;
+
+// imports for docs
+#[allow(unused)]
+use crate::retry::RetryError;
+
+const DEFAULT_BATCH_SIZE: usize = 1000;
+
+type DefaultProgressCallback = fn(&BulkWriteProgress);
+
+fn default_progress_callback(_: &BulkWriteProgress) {}
+
+/// Builder for [`Driver::bulk_write()`].
+pub struct BulkWriteBuilder<'driver, Q, RP, FP> {
+    driver: &'driver Driver,
+    query: Q,
+    database: Option<Arc<String>>,
+    batch_size: usize,
+    parallelism: usize,
+    retry_policy: RP,
+    progress_callback: FP,
+}
+
+impl<'driver, Q: AsRef<str>>
+    BulkWriteBuilder<'driver, Q, ExponentialBackoff, DefaultProgressCallback>
+{
+    pub(super) fn new(driver: &'driver Driver, query: Q) -> Self {
+        Self {
+            driver,
+            query,
+            database: None,
+            batch_size: DEFAULT_BATCH_SIZE,
+            parallelism: 1,
+            retry_policy: ExponentialBackoff::new(),
+            progress_callback: default_progress_callback,
+        }
+    }
+}
+
+impl<'driver, Q: AsRef<str>, RP: RetryPolicy + Sync, FP: FnMut(&BulkWriteProgress) + Send>
+    BulkWriteBuilder<'driver, Q, RP, FP>
+where
+    RP::Error: Send,
+{
+    /// Run the batches against the given database.
+    ///
+    /// Always specify the database when possible, to allow the driver to work more efficiently.
+    #[inline]
+    pub fn with_database(mut self, database: Arc<String>) -> Self {
+        self.database = Some(database);
+        self
+    }
+
+    /// Run the batches against the user's home database.
+    ///
+    /// This is the *default*.
+    #[inline]
+    pub fn with_default_database(mut self) -> Self {
+        self.database = None;
+        self
+    }
+
+    /// Configure how many rows are sent per batch (i.e., per transaction).
+    ///
+    /// Defaults to `1000`.
+    ///
+    /// # Panics
+    /// Panics if `batch_size` is `0`.
+    #[inline]
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "batch_size must be greater than 0");
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Configure how many batches may be written concurrently.
+    ///
+    /// Each concurrent batch occupies one connection from the pool.
+    /// Defaults to `1` (i.e., batches are written one after the other).
+    ///
+    /// # Panics
+    /// Panics if `parallelism` is `0`.
+    #[inline]
+    pub fn with_parallelism(mut self, parallelism: usize) -> Self {
+        assert!(parallelism > 0, "parallelism must be greater than 0");
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Configure the retry policy each batch is run with.
+    ///
+    /// Defaults to [`ExponentialBackoff::new()`].
+    #[inline]
+    pub fn with_retry_policy<RP_: RetryPolicy + Sync>(
+        self,
+        retry_policy: RP_,
+    ) -> BulkWriteBuilder<'driver, Q, RP_, FP>
+    where
+        RP_::Error: Send,
+    {
+        let Self {
+            driver,
+            query,
+            database,
+            batch_size,
+            parallelism,
+            retry_policy: _,
+            progress_callback,
+        } = self;
+        BulkWriteBuilder {
+            driver,
+            query,
+            database,
+            batch_size,
+            parallelism,
+            retry_policy,
+            progress_callback,
+        }
+    }
+
+    /// Register a callback that is called after each batch (successful or not).
+    ///
+    /// The callback is never called concurrently, but might be called from different threads
+    /// if [parallelism](`BulkWriteBuilder::with_parallelism()`) is greater than `1`.
+    #[inline]
+    pub fn with_progress_callback<FP_: FnMut(&BulkWriteProgress) + Send>(
+        self,
+        progress_callback: FP_,
+    ) -> BulkWriteBuilder<'driver, Q, RP, FP_> {
+        let Self {
+            driver,
+            query,
+            database,
+            batch_size,
+            parallelism,
+            retry_policy,
+            progress_callback: _,
+        } = self;
+        BulkWriteBuilder {
+            driver,
+            query,
+            database,
+            batch_size,
+            parallelism,
+            retry_policy,
+            progress_callback,
+        }
+    }
+
+    /// Write all `rows` and return once every batch has either been committed or failed.
+    ///
+    /// Failing batches don't stop the remaining batches from being written.
+    /// They are reported in [`BulkWriteSummary::failed_batches`] instead.
+    pub fn run(self, rows: impl IntoIterator<Item = ValueSend>) -> BulkWriteSummary<RP::Error> {
+        let (summary, row_error) = self.run_fallible(rows.into_iter().map(Ok::<_, Infallible>));
+        if let Some((_, err)) = row_error {
+            match err {}
+        }
+        summary
+    }
+
+    /// Serialize `rows` batch by batch and write them.
+    ///
+    /// Each row is serialized (see [`crate::value::to_value()`]) right before the batch it
+    /// belongs to is handed to a worker.
+    /// Apart from that, this is the same as [`BulkWriteBuilder::run()`].
+    ///
+    /// Requires the `serde_1` feature.
+    ///
+    /// # Errors
+    /// A [`BulkWriteSerializeError`] if a row fails to serialize.
+    /// No further rows are serialized or written then.
+    /// All rows before the failing one are still written, and the outcome of that is reported in
+    /// [`BulkWriteSerializeError::summary`].
+    #[cfg(feature = "serde_1")]
+    #[allow(clippy::result_large_err)]
+    pub fn run_serialized<T: serde::Serialize>(
+        self,
+        rows: impl IntoIterator<Item = T>,
+    ) -> Result<BulkWriteSummary<RP::Error>, BulkWriteSerializeError<RP::Error>> {
+        let rows = rows.into_iter().map(|row| crate::value::to_value(&row));
+        match self.run_fallible(rows) {
+            (summary, None) => Ok(summary),
+            (summary, Some((row, error))) => Err(BulkWriteSerializeError {
+                row,
+                error,
+                summary,
+            }),
+        }
+    }
+
+    /// Write `rows` until the first `Err`, which is returned alongside its index.
+    fn run_fallible<E>(
+        self,
+        rows: impl IntoIterator<Item = Result<ValueSend, E>>,
+    ) -> (BulkWriteSummary<RP::Error>, Option<(usize, E)>) {
+        let Self {
+            driver,
+            query,
+            database,
+            batch_size,
+            parallelism,
+            retry_policy,
+            progress_callback,
+        } = self;
+        let worker = BatchWorker {
+            driver,
+            query: query.as_ref(),
+            database,
+            retry_policy,
+            state: Mutex::new(BulkWriteState {
+                progress: Default::default(),
+                counters: Default::default(),
+                failed_batches: Default::default(),
+                progress_callback,
+            }),
+        };
+        let (sender, receiver) = mpsc::sync_channel::<Batch>(parallelism);
+        let receiver = Arc::new(Mutex::new(receiver));
+        let mut row_error = None;
+        thread::scope(|scope| {
+            for _ in 0..parallelism {
+                let receiver = Arc::clone(&receiver);
+                let worker = &worker;
+                scope.spawn(move || {
+                    loop {
+                        let Ok(batch) = receiver.lock().recv() else {
+                            break;
+                        };
+                        worker.write(batch);
+                    }
+                });
+            }
+            // Only the workers keep the receiver alive.
+            // Should they all die (e.g., because the progress callback panicked), sending fails
+            // instead of blocking forever.
+            drop(receiver);
+            let mut rows = rows.into_iter();
+            let mut start = 0;
+            while row_error.is_none() {
+                let mut batch = Vec::new();
+                for row in rows.by_ref().take(batch_size) {
+                    match row {
+                        Ok(row) => batch.push(row),
+                        Err(err) => {
+                            row_error = Some((start + batch.len(), err));
+                            break;
+                        }
+                    }
+                }
+                if batch.is_empty() {
+                    break;
+                }
+                let end = start + batch.len();
+                if sender.send((start..end, batch)).is_err() {
+                    break;
+                }
+                start = end;
+            }
+            // let the workers finish once all batches have been picked up
+            drop(sender);
+        });
+        let BulkWriteState {
+            progress,
+            counters,
+            mut failed_batches,
+            progress_callback: _,
+        } = worker.state.into_inner();
+        failed_batches.sort_by_key(|batch| batch.rows.start);
+        let summary = BulkWriteSummary {
+            counters,
+            rows_written: progress.rows_written,
+            failed_batches,
+        };
+        (summary, row_error)
+    }
+}
+
+impl<Q: AsRef<str>, RP: Debug, FP> Debug for BulkWriteBuilder<'_, Q, RP, FP> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BulkWriteBuilder")
+            .field("driver", &"...")
+            .field("query", &self.query.as_ref())
+            .field("database", &self.database)
+            .field("batch_size", &self.batch_size)
+            .field("parallelism", &self.parallelism)
+            .field("retry_policy", &self.retry_policy)
+            .field("progress_callback", &"...")
+            .finish()
+    }
+}
+
+type Batch = (Range<usize>, Vec<ValueSend>);
+
+struct BatchWorker<'driver, 'query, RP: RetryPolicy, FP> {
+    driver: &'driver Driver,
+    query: &'query str,
+    database: Option<Arc<String>>,
+    retry_policy: RP,
+    state: Mutex<BulkWriteState<RP::Error, FP>>,
+}
+
+struct BulkWriteState<E, FP> {
+    progress: BulkWriteProgress,
+    counters: Counters,
+    failed_batches: Vec<FailedBatch<E>>,
+    progress_callback: FP,
+}
+
+impl<RP: RetryPolicy, FP: FnMut(&BulkWriteProgress)> BatchWorker<'_, '_, RP, FP> {
+    fn write(&self, (rows, batch): Batch) {
+        let parameters = HashMap::from([("rows", ValueSend::List(batch))]);
+        let config = SessionConfig::new()
+            .with_bookmark_manager(self.driver.execute_query_bookmark_manager());
+        let config = match &self.database {
+            Some(database) => config.with_database(Arc::clone(database)),
+            None => config,
+        };
+        let mut session = self.driver.session(config);
+        let res = self.retry_policy.execute(|| {
+            session
+                .transaction()
+                .with_routing_control(RoutingControl::Write)
+                .run(|tx| {
+                    let summary = tx
+                        .query(self.query)
+                        .with_parameters(&parameters)
+                        .run()?
+                        .consume()?;
+                    tx.commit()?;
+                    Ok(summary.map(|summary| summary.counters))
+                })
+        });
+
+        let mut state = self.state.lock();
+        let BulkWriteState {
+            progress,
+            counters,
+            failed_batches,
+            progress_callback,
+        } = &mut *state;
+        match res {
+            Ok(batch_counters) => {
+                if let Some(batch_counters) = batch_counters {
+                    counters.add(&batch_counters);
+                }
+                progress.batches_written += 1;
+                progress.rows_written += rows.len();
+            }
+            Err(error) => {
+                progress.batches_failed += 1;
+                progress.rows_failed += rows.len();
+                failed_batches.push(FailedBatch { rows, error });
+            }
+        }
+        progress_callback(progress);
+    }
+}
+
+/// Progress of a [`Driver::bulk_write()`] as reported to the
+/// [progress callback](`BulkWriteBuilder::with_progress_callback()`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BulkWriteProgress {
+    /// Number of batches committed so far.
+    pub batches_written: usize,
+    /// Number of rows in committed batches so far.
+    pub rows_written: usize,
+    /// Number of batches that failed so far.
+    pub batches_failed: usize,
+    /// Number of rows in failed batches so far.
+    pub rows_failed: usize,
+}
+
+/// The outcome of a [`Driver::bulk_write()`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct BulkWriteSummary<E> {
+    /// The counters of all committed batches added up.
+    pub counters: Counters,
+    /// Number of rows in committed batches.
+    pub rows_written: usize,
+    /// The batches that could not be committed, ordered by their rows.
+    pub failed_batches: Vec<FailedBatch<E>>,
+}
+
+/// Returned by [`BulkWriteBuilder::run_serialized()`] when a row failed to serialize.
+///
+/// Requires the `serde_1` feature.
+#[cfg(feature = "serde_1")]
+#[derive(Debug, Error)]
+#[error("row {row} failed to serialize: {error}")]
+#[non_exhaustive]
+pub struct BulkWriteSerializeError<E> {
+    /// The index of the row that failed to serialize.
+    pub row: usize,
+    /// Why the row failed to serialize.
+    #[source]
+    pub error: crate::value::SerializeError,
+    /// The outcome of writing the rows before the failing one.
+    pub summary: BulkWriteSummary<E>,
+}
+
+/// A batch of a [`Driver::bulk_write()`] that could not be committed.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct FailedBatch<E> {
+    /// The indices of the rows in the batch
+    /// (counting the rows passed to [`BulkWriteBuilder::run()`] from `0`).
+    pub rows: Range<usize>,
+    /// The error returned by the retry policy (e.g., [`RetryError`] for [`ExponentialBackoff`]).
+    pub error: E,
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use std::panic::{self, AssertUnwindSafe};
+    #[cfg(feature = "serde_1")]
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::error_::Neo4jError;
+    use crate::testing::test_support::{direct_driver, hello_script};
+    use crate::testing::{ClientMessage, ServerMessage, StubScript, StubServer};
+    use crate::value_map;
+
+    const QUERY: &str = "UNWIND $rows AS row CREATE (n:Node {id: row})";
+
+    fn batch_script(script: StubScript, rows: &[i64]) -> StubScript {
+        let rows = ValueSend::List(rows.iter().copied().map(ValueSend::Integer).collect());
+        script
+            .client(ClientMessage::begin())
+            .server(ServerMessage::success(value_map!()))
+            .client(ClientMessage::run(QUERY).with_field(1, value_map!({ "rows": rows })))
+            .client(ClientMessage::pull())
+    }
+
+    fn commit(script: StubScript, nodes_created: i64) -> StubScript {
+        script
+            .server(ServerMessage::success(value_map!({"fields": []})))
+            .server(ServerMessage::success(
+                value_map!({"type": "w", "stats": {"nodes-created": nodes_created}}),
+            ))
+            .client(ClientMessage::commit())
+            .server(ServerMessage::success(value_map!()))
+    }
+
+    #[test]
+    fn test_batches() {
+        let script = commit(batch_script(hello_script(), &[0, 1]), 2);
+        let script = commit(batch_script(script, &[2]), 1);
+        let server = StubServer::start(script).unwrap();
+        let driver = direct_driver(&server);
+        let mut progress_reports = Vec::new();
+
+        let summary = driver
+            .bulk_write(QUERY)
+            .with_database(Arc::new(String::from("neo4j")))
+            .with_batch_size(2)
+            .with_progress_callback(|progress| progress_reports.push(progress.clone()))
+            .run((0..3).map(ValueSend::Integer));
+
+        assert!(summary.failed_batches.is_empty());
+        assert_eq!(summary.rows_written, 3);
+        assert_eq!(summary.counters.nodes_created, 3);
+        assert!(summary.counters.contains_updates);
+        assert_eq!(
+            progress_reports
+                .iter()
+                .map(|progress| (progress.batches_written, progress.rows_written))
+                .collect::<Vec<_>>(),
+            vec![(1, 2), (2, 3)]
+        );
+        drop(driver);
+        server.finish().unwrap();
+    }
+
+    #[test]
+    fn test_failed_batch() {
+        let script = batch_script(hello_script(), &[0, 1])
+            .server(ServerMessage::failure(value_map!({
+                "code": "Neo.ClientError.Statement.SyntaxError",
+                "message": "Invalid input",
+            })))
+            .server(ServerMessage::ignored())
+            .client(ClientMessage::reset())
+            .server(ServerMessage::success(value_map!()));
+        let script = commit(batch_script(script, &[2]), 1);
+        let server = StubServer::start(script).unwrap();
+        let driver = direct_driver(&server);
+
+        let summary = driver
+            .bulk_write(QUERY)
+            .with_database(Arc::new(String::from("neo4j")))
+            .with_batch_size(2)
+            .run((0..3).map(ValueSend::Integer));
+
+        assert_eq!(summary.rows_written, 1);
+        assert_eq!(summary.counters.nodes_created, 1);
+        assert_eq!(summary.failed_batches.len(), 1);
+        let failed_batch = &summary.failed_batches[0];
+        assert_eq!(failed_batch.rows, 0..2);
+        let RetryError::Neo4jError(Neo4jError::ServerError { error }) = &failed_batch.error else {
+            panic!("expected server error, got {:?}", failed_batch.error);
+        };
+        assert_eq!(error.code(), "Neo.ClientError.Statement.SyntaxError");
+        drop(driver);
+        server.finish().unwrap();
+    }
+
+    #[test]
+    fn test_panicking_progress_callback() {
+        let script = commit(batch_script(hello_script(), &[0]), 1);
+        let server = StubServer::start(script).unwrap();
+        let driver = direct_driver(&server);
+
+        let res = panic::catch_unwind(AssertUnwindSafe(|| {
+            driver
+                .bulk_write(QUERY)
+                .with_database(Arc::new(String::from("neo4j")))
+                .with_batch_size(1)
+                .with_progress_callback(|_| panic!("progress callback failed"))
+                // more batches than fit into the channel
+                .run((0..10).map(ValueSend::Integer))
+        }));
+
+        assert!(res.is_err());
+        drop(driver);
+        server.finish().unwrap();
+    }
+
+    #[cfg(feature = "serde_1")]
+    #[test]
+    fn test_run_serialized_stops_at_first_serialize_error() {
+        let script = commit(batch_script(hello_script(), &[0, 1]), 2);
+        let script = commit(batch_script(script, &[2]), 1);
+        let server = StubServer::start(script).unwrap();
+        let driver = direct_driver(&server);
+        let serialized = AtomicUsize::new(0);
+
+        let rows = [0, 1, 2, u64::MAX, 4, 5].into_iter().inspect(|_| {
+            serialized.fetch_add(1, Ordering::Relaxed);
+        });
+        let err = driver
+            .bulk_write(QUERY)
+            .with_database(Arc::new(String::from("neo4j")))
+            .with_batch_size(2)
+            .run_serialized(rows)
+            .unwrap_err();
+
+        assert_eq!(err.row, 3);
+        assert_eq!(err.summary.rows_written, 3);
+        assert_eq!(err.summary.counters.nodes_created, 3);
+        assert!(err.summary.failed_batches.is_empty());
+        assert_eq!(serialized.load(Ordering::Relaxed), 4);
+        drop(driver);
+        server.finish().unwrap();
+    }
+}
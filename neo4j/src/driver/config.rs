@@ -13,6 +13,9 @@
 // limitations under the License.
 
 pub(crate) mod auth;
+pub(crate) mod client_certificate;
+pub(crate) mod connector;
+pub(crate) mod load_balancing;
 pub(crate) mod notification;
 
 use std::collections::HashMap;
@@ -28,15 +31,23 @@ use rustls::ClientConfig;
 use thiserror::Error;
 use uriparse::{Query, URI};
 
+use super::io::{CircuitBreakerConfig, ConnectionPoolObserver, HousekeepingConfig};
 use crate::address_::Address;
 use crate::address_::DEFAULT_PORT;
 use crate::address_::resolution::AddressResolver;
 use crate::value::ValueSend;
 use auth::{AuthManager, AuthToken};
+use client_certificate::{
+    ClientCertificate, ClientCertificateProvider, client_certificate_providers,
+};
+use connector::Connector;
+use load_balancing::{LeastConnected, LoadBalancingStrategy};
 use notification::NotificationFilter;
 
 // imports for docs
 #[allow(unused)]
+use super::Driver;
+#[allow(unused)]
 use super::ExecuteQueryBuilder;
 #[allow(unused)]
 use super::session::{AutoCommitBuilder, SessionConfig, TransactionBuilder};
@@ -57,6 +68,7 @@ pub struct DriverConfig {
     pub(crate) max_connection_lifetime: Option<Duration>,
     pub(crate) idle_time_before_connection_test: Option<Duration>,
     pub(crate) max_connection_pool_size: usize,
+    pub(crate) min_idle_connections: usize,
     pub(crate) fetch_size: i64,
     pub(crate) connection_timeout: Option<Duration>,
     pub(crate) connection_acquisition_timeout: Option<Duration>,
@@ -64,6 +76,11 @@ pub struct DriverConfig {
     pub(crate) notification_filter: NotificationFilter,
     pub(crate) keep_alive: Option<KeepAliveConfig>,
     pub(crate) telemetry: bool,
+    pub(crate) connection_pool_observer: Option<Arc<dyn ConnectionPoolObserver>>,
+    pub(crate) connector: Option<Arc<dyn Connector>>,
+    pub(crate) load_balancing_strategy: Arc<dyn LoadBalancingStrategy>,
+    pub(crate) circuit_breaker: Option<CircuitBreakerConfig>,
+    pub(crate) housekeeping: Option<HousekeepingConfig>,
 }
 
 #[derive(Debug)]
@@ -117,6 +134,12 @@ pub enum KeepAliveConfig {
 /// # let _ = conf;
 /// ```
 ///
+/// On Unix platforms, the driver can also connect (without encryption and routing) to a DBMS
+/// listening on a Unix domain socket (see [`Address::unix_socket()`]):
+/// ```text
+/// bolt+unix:///path/to/socket
+/// ```
+///
 /// ## Programmatically
 /// To get better type safety and avoid parsing errors at runtime, this crate also provides a
 /// builder API.
@@ -146,11 +169,18 @@ pub enum KeepAliveConfig {
 ///    [platform support](https://docs.rs/rustls/latest/rustls/index.html#platform-support)
 ///  * Make sure a crypto provider is installed (e.g., by calling
 ///    [`rustls::crypto::CryptoProvider::install_default()`] when starting the process).
+///
+/// ### Mutual TLS
+/// To authenticate the driver towards the server with a client certificate (mTLS), use
+/// [`ConnectionConfig::with_client_certificate()`] or, if the certificate is subject to rotation,
+/// [`ConnectionConfig::with_client_certificate_provider()`] in addition to configuring
+/// encryption.
 #[derive(Debug)]
 pub struct ConnectionConfig {
     pub(crate) address: Address,
     pub(crate) routing_context: Option<HashMap<String, ValueSend>>,
     pub(crate) tls_config: Option<ClientConfig>,
+    pub(crate) client_certificate: Option<Arc<dyn ClientCertificateProvider>>,
 }
 
 impl Default for DriverConfig {
@@ -161,6 +191,7 @@ impl Default for DriverConfig {
             max_connection_lifetime: Some(DEFAULT_MAX_CONNECTION_LIFETIME),
             idle_time_before_connection_test: None,
             max_connection_pool_size: 100,
+            min_idle_connections: 0,
             fetch_size: DEFAULT_FETCH_SIZE,
             connection_timeout: Some(DEFAULT_CONNECTION_TIMEOUT),
             connection_acquisition_timeout: Some(DEFAULT_CONNECTION_ACQUISITION_TIMEOUT),
@@ -168,6 +199,11 @@ impl Default for DriverConfig {
             notification_filter: Default::default(),
             keep_alive: None,
             telemetry: true,
+            connection_pool_observer: None,
+            connector: None,
+            load_balancing_strategy: Arc::new(LeastConnected),
+            circuit_breaker: None,
+            housekeeping: None,
         }
     }
 }
@@ -251,6 +287,8 @@ impl DriverConfig {
     ///
     /// When a connection is attempted to be picked up from the connection pool, it will be closed
     /// if it has been created longer than this duration ago.
+    /// With [`DriverConfig::with_housekeeping()`], idle connections are also closed in the
+    /// background.
     #[inline]
     pub fn with_max_connection_lifetime(mut self, max_connection_lifetime: Duration) -> Self {
         self.max_connection_lifetime = Some(max_connection_lifetime);
@@ -291,6 +329,9 @@ impl DriverConfig {
     /// Set the timeout to [`Duration::ZERO`] to make the driver always perform the liveness check
     /// when picking up a connection from the pool.
     ///
+    /// With [`DriverConfig::with_housekeeping()`], idle connections are also tested in the
+    /// background.
+    ///
     /// Usually, this parameter does not need tweaking.
     ///
     /// # Example
@@ -332,6 +373,31 @@ impl DriverConfig {
         self
     }
 
+    /// Configure the minimum number of idle connections the driver should keep per connection
+    /// pool.
+    ///
+    /// When routing, this applies to the pools of all readers and writers in the routing tables.
+    /// Whenever the driver fetches a new routing table or closes a connection (e.g., because it
+    /// broke or reached its maximum lifetime), it opens the missing connections in the background.
+    /// With [`DriverConfig::with_housekeeping()`], pools are also refilled periodically.
+    /// Use [`Driver::warm_up()`] to fill the pools up front, e.g., before serving traffic.
+    /// Pools never grow beyond [`DriverConfig::with_max_connection_pool_size()`].
+    ///
+    /// The *default* is `0`, i.e., connections are only opened when needed.
+    ///
+    /// # Example
+    /// ```
+    /// use neo4j::driver::DriverConfig;
+    ///
+    /// let config = DriverConfig::new().with_min_idle_connections(5);
+    /// # drop(config);
+    /// ```
+    #[inline]
+    pub fn with_min_idle_connections(mut self, min_idle_connections: usize) -> Self {
+        self.min_idle_connections = min_idle_connections;
+        self
+    }
+
     /// Change the fetch size to fetch `fetch_size` records at once.
     ///
     /// See also [`SessionConfig::with_fetch_size()`] which is the same setting but per session.
@@ -579,6 +645,122 @@ impl DriverConfig {
         self.telemetry = telemetry;
         self
     }
+
+    /// Register a [`ConnectionPoolObserver`] to be notified about connection pool events.
+    ///
+    /// This can, for example, be used to export pool metrics to a monitoring system.
+    /// See also [`Driver::get_connection_pool_metrics()`] for polling the pool's state instead.
+    ///
+    /// See [`ConnectionPoolObserver`] for an example.
+    #[inline]
+    pub fn with_connection_pool_observer(
+        mut self,
+        observer: Arc<dyn ConnectionPoolObserver>,
+    ) -> Self {
+        self.connection_pool_observer = Some(observer);
+        self
+    }
+
+    /// Don't notify any [`ConnectionPoolObserver`] about connection pool events.
+    ///
+    /// This is the *default*.
+    #[inline]
+    pub fn without_connection_pool_observer(mut self) -> Self {
+        self.connection_pool_observer = None;
+        self
+    }
+
+    /// Use a custom [`Connector`] to establish the connections to the DBMS.
+    ///
+    /// This allows running Bolt over transports other than TCP.
+    /// See [`Connector`] for caveats and an example.
+    #[inline]
+    pub fn with_connector(mut self, connector: Arc<dyn Connector>) -> Self {
+        self.connector = Some(connector);
+        self
+    }
+
+    /// Connect to the DBMS via TCP.
+    ///
+    /// This is the *default*.
+    ///
+    /// See also [`DriverConfig::with_connector()`].
+    #[inline]
+    pub fn without_connector(mut self) -> Self {
+        self.connector = None;
+        self
+    }
+
+    /// Choose which of several servers the driver prefers when routing work.
+    ///
+    /// See [`LoadBalancingStrategy`] for details and the built-in strategies.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use neo4j::driver::load_balancing::LatencyWeighted;
+    /// use neo4j::driver::DriverConfig;
+    ///
+    /// let config = DriverConfig::new().with_load_balancing_strategy(Arc::new(LatencyWeighted));
+    /// # drop(config);
+    /// ```
+    #[inline]
+    pub fn with_load_balancing_strategy(
+        mut self,
+        strategy: Arc<dyn LoadBalancingStrategy>,
+    ) -> Self {
+        self.load_balancing_strategy = strategy;
+        self
+    }
+
+    /// Prefer the server with the fewest connections in use
+    /// (see [`load_balancing::LeastConnected`]).
+    ///
+    /// This is the *default*.
+    #[inline]
+    pub fn with_default_load_balancing_strategy(mut self) -> Self {
+        self.load_balancing_strategy = Arc::new(LeastConnected);
+        self
+    }
+
+    /// Eject servers that fail repeatedly until they've recovered.
+    ///
+    /// Only applies when routing.
+    /// See [`CircuitBreakerConfig`] for details and an example.
+    #[inline]
+    pub fn with_circuit_breaker(mut self, circuit_breaker: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
+    /// Don't eject failing servers beyond removing them from the current routing table.
+    ///
+    /// This is the *default*.
+    #[inline]
+    pub fn without_circuit_breaker(mut self) -> Self {
+        self.circuit_breaker = None;
+        self
+    }
+
+    /// Run a background thread that periodically closes expired connections, liveness-checks
+    /// idle ones, and prunes unused connection pools.
+    ///
+    /// See [`HousekeepingConfig`] for details and an example.
+    #[inline]
+    pub fn with_housekeeping(mut self, housekeeping: HousekeepingConfig) -> Self {
+        self.housekeeping = Some(housekeeping);
+        self
+    }
+
+    /// Only check connections when they're about to be used.
+    ///
+    /// This is the *default*.
+    #[inline]
+    pub fn without_housekeeping(mut self) -> Self {
+        self.housekeeping = None;
+        self
+    }
 }
 
 impl ConnectionConfig {
@@ -586,18 +768,35 @@ impl ConnectionConfig {
     ///
     /// Besides the required address, no TLS encryption will be used and routing with an empty
     /// routing context is the default.
+    /// Only if `address` points to a Unix domain socket (see [`Address::unix_socket()`]), routing
+    /// is disabled as it's not supported for such addresses.
     pub fn new(address: Address) -> Self {
+        let routing_context = address.unix_socket_path().is_none().then(HashMap::new);
         Self {
             address,
-            routing_context: Some(HashMap::new()),
+            routing_context,
             tls_config: None,
+            client_certificate: None,
         }
     }
 
     /// Change the address the driver should connect to.
-    pub fn with_address(mut self, address: Address) -> Self {
+    ///
+    /// # Errors
+    /// A [`ConfigureRoutingError`] is returned if routing is enabled and `address` points to a
+    /// Unix domain socket (see [`Address::unix_socket()`]).
+    /// Disable routing first ([`ConnectionConfig::with_routing(false)`](Self::with_routing)) to
+    /// connect to such an address.
+    #[allow(clippy::result_large_err)]
+    pub fn with_address(
+        mut self,
+        address: Address,
+    ) -> StdResult<Self, ConfigureRoutingError<Self>> {
+        if self.routing_context.is_some() && address.unix_socket_path().is_some() {
+            return Err(ConfigureRoutingError { builder: self });
+        }
         self.address = address;
-        self
+        Ok(self)
     }
 
     /// Choose whether the driver should perform routing [`true`] or not [`false`].
@@ -613,13 +812,21 @@ impl ConnectionConfig {
     /// When enabling it (`with_routing(true)`), an empty routing context will be configured.
     /// If you want to enable routing with a routing context, calling
     /// [`ConnectionConfig::with_routing_context()`] is sufficient.
-    pub fn with_routing(mut self, routing: bool) -> Self {
+    ///
+    /// # Errors
+    /// A [`ConfigureRoutingError`] is returned when enabling routing while the configured address
+    /// points to a Unix domain socket (see [`Address::unix_socket()`]).
+    /// Routing is not supported for such addresses.
+    #[allow(clippy::result_large_err)]
+    pub fn with_routing(mut self, routing: bool) -> StdResult<Self, ConfigureRoutingError<Self>> {
         if !routing {
             self.routing_context = None
+        } else if self.address.unix_socket_path().is_some() {
+            return Err(ConfigureRoutingError { builder: self });
         } else if self.routing_context.is_none() {
             self.routing_context = Some(HashMap::new());
         }
-        self
+        Ok(self)
     }
 
     /// Enable routing with a specific routing context.
@@ -629,12 +836,19 @@ impl ConnectionConfig {
     ///
     /// # Errors
     /// An [`InvalidRoutingContextError`] is returned if the routing context contains the *reserved*
-    /// key `"address"`.
+    /// key `"address"` or if the configured address points to a Unix domain socket (see
+    /// [`Address::unix_socket()`]).
     #[allow(clippy::result_large_err)]
     pub fn with_routing_context(
         mut self,
         routing_context: HashMap<String, String>,
     ) -> StdResult<Self, InvalidRoutingContextError<Self>> {
+        if self.address.unix_socket_path().is_some() {
+            return Err(InvalidRoutingContextError {
+                builder: self,
+                it: "cannot be used with a Unix domain socket address",
+            });
+        }
         if routing_context.contains_key("address") {
             return Err(InvalidRoutingContextError {
                 builder: self,
@@ -706,12 +920,119 @@ impl ConnectionConfig {
         self
     }
 
+    /// Enforce TLS encryption, only accepting server certificates with one of the given
+    /// fingerprints (certificate pinning).
+    ///
+    /// A fingerprint is the SHA-256 digest of the DER encoded end-entity certificate as, for
+    /// example, printed by `openssl x509 -noout -fingerprint -sha256 -in server.pem`.
+    /// Besides the fingerprint, neither the certificate chain, nor the host name, nor the
+    /// certificate's validity period are checked.
+    ///
+    /// Returns an error if the installed crypto provider does not support SHA-256.
+    ///
+    /// To use TLS, see the notes about [TLS requirements](Self#tls).
+    ///
+    /// # Example
+    /// ```
+    /// use neo4j::driver::ConnectionConfig;
+    ///
+    /// let fingerprint = [0xAB; 32];
+    /// let conf = ConnectionConfig::new(("localhost", 7687).into())
+    ///     .with_encryption_trust_pinned_certificates([fingerprint])
+    ///     .unwrap();
+    /// # let _ = conf;
+    /// ```
+    #[allow(clippy::result_large_err)]
+    pub fn with_encryption_trust_pinned_certificates(
+        mut self,
+        fingerprints: impl IntoIterator<Item = [u8; 32]>,
+    ) -> StdResult<Self, TlsConfigError> {
+        let fingerprints = fingerprints.into_iter().collect();
+        self.tls_config = Some(match tls_helper::pinned_tls_config(fingerprints) {
+            Ok(config) => config,
+            Err(message) => {
+                return Err(TlsConfigError {
+                    message,
+                    config: self,
+                });
+            }
+        });
+        Ok(self)
+    }
+
+    /// Enforce TLS encryption using a custom [`rustls::ClientConfig`].
+    ///
+    /// This is an escape hatch for advanced use-cases not covered by the other
+    /// `with_encryption_...` methods (e.g., restricting cipher suites, choosing a crypto
+    /// provider, or using a custom server certificate verifier).
+    /// The driver does not validate the configuration in any way.
+    /// If a client certificate is configured (see
+    /// [`ConnectionConfig::with_client_certificate()`]), it will replace the client certificate
+    /// resolver of the given config.
+    ///
+    /// Requires the `rustls_0_23` feature.
+    ///
+    /// To use TLS, see the notes about [TLS requirements](Self#tls).
+    #[cfg(feature = "rustls_0_23")]
+    pub fn with_tls_config(mut self, tls_config: Arc<ClientConfig>) -> Self {
+        self.tls_config = Some(Arc::unwrap_or_clone(tls_config));
+        self
+    }
+
     /// Disable TLS encryption.
     pub fn with_encryption_disabled(mut self) -> Self {
         self.tls_config = None;
         self
     }
 
+    /// Present the given client certificate to the server for mutual TLS (mTLS).
+    ///
+    /// This only has an effect if encryption is enabled (e.g., through
+    /// [`ConnectionConfig::with_encryption_trust_default_cas()`]).
+    ///
+    /// # Example
+    /// ```no_run
+    /// use neo4j::driver::client_certificate::ClientCertificate;
+    /// use neo4j::driver::ConnectionConfig;
+    ///
+    /// let certificate = ClientCertificate::from_pem_files("client.pem", "client.key").unwrap();
+    /// let conf = ConnectionConfig::new(("localhost", 7687).into())
+    ///     .with_encryption_trust_default_cas()
+    ///     .unwrap()
+    ///     .with_client_certificate(certificate);
+    /// # let _ = conf;
+    /// ```
+    pub fn with_client_certificate(self, certificate: ClientCertificate) -> Self {
+        self.with_client_certificate_provider(Arc::new(client_certificate_providers::new_static(
+            certificate,
+        )))
+    }
+
+    /// Use a [`ClientCertificateProvider`] to determine the client certificate presented to the
+    /// server for mutual TLS (mTLS).
+    /// This allows to rotate the certificate without restarting the driver.
+    ///
+    /// This only has an effect if encryption is enabled (e.g., through
+    /// [`ConnectionConfig::with_encryption_trust_default_cas()`]).
+    ///
+    /// See also [`client_certificate_providers`] and
+    /// [`RotatingClientCertificateProvider`](client_certificate::RotatingClientCertificateProvider).
+    pub fn with_client_certificate_provider(
+        mut self,
+        provider: Arc<dyn ClientCertificateProvider>,
+    ) -> Self {
+        self.client_certificate = Some(provider);
+        self
+    }
+
+    /// Don't present any client certificate to the server.
+    ///
+    /// This is the *default*.
+    pub fn without_client_certificate(mut self) -> Self {
+        self.client_certificate = None;
+        self
+    }
+
     fn parse_uri(uri: &str) -> StdResult<ConnectionConfig, ConnectionConfigParseError> {
         let uri = URI::try_from(uri).map_err(URIError)?;
 
@@ -722,10 +1043,11 @@ impl ConnectionConfig {
             "bolt" => (false, None),
             "bolt+s" => (false, Some(tls_helper::secure_tls_config()?)),
             "bolt+ssc" => (false, Some(tls_helper::self_signed_tls_config())),
+            "bolt+unix" => return Self::parse_unix_uri(&uri),
             scheme => {
                 return Err(ConnectionConfigParseError(format!(
                     "unknown scheme in URI {scheme} expected `neo4j`, `neo4j`, `neo4j+s`, \
-                     `neo4j+ssc`, `bolt`, `bolt+s`, or `bolt+ssc`"
+                     `neo4j+ssc`, `bolt`, `bolt+s`, `bolt+ssc`, or `bolt+unix`"
                 )));
             }
         };
@@ -789,6 +1111,43 @@ impl ConnectionConfig {
             address: (host, port).into(),
             routing_context,
             tls_config,
+            client_certificate: None,
+        })
+    }
+
+    fn parse_unix_uri(uri: &URI) -> StdResult<ConnectionConfig, ConnectionConfigParseError> {
+        if let Some(authority) = uri.authority()
+            && !authority.to_string().is_empty()
+        {
+            return Err(ConnectionConfigParseError(format!(
+                "bolt+unix URI cannot contain a host, found: {authority} \
+                 (expected bolt+unix:///path/to/socket)"
+            )));
+        }
+        let mut path = uri.path().to_owned();
+        path.normalize(false);
+        let path = path.to_string();
+        if !path.starts_with('/') || path == "/" {
+            return Err(ConnectionConfigParseError(format!(
+                "bolt+unix URI must contain an absolute socket path, found: {path}"
+            )));
+        }
+        if let Some(query) = uri.query() {
+            return Err(ConnectionConfigParseError(format!(
+                "URI with bolt+unix scheme cannot contain a query (routing context), found: {query}"
+            )));
+        }
+        if let Some(fragment) = uri.fragment() {
+            return Err(ConnectionConfigParseError(format!(
+                "URI cannot contain a fragment, found: {fragment}"
+            )));
+        }
+
+        Ok(ConnectionConfig {
+            address: Address::unix_socket(path),
+            routing_context: None,
+            tls_config: None,
+            client_certificate: None,
         })
     }
 
@@ -838,7 +1197,8 @@ impl FromStr for ConnectionConfig {
 ///
 /// See also [`ConnectionConfig::with_encryption_trust_default_cas()`],
 /// [`ConnectionConfig::with_encryption_trust_custom_cas()`],
-/// [`ConnectionConfig::with_encryption_trust_any_certificate()`].
+/// [`ConnectionConfig::with_encryption_trust_any_certificate()`],
+/// [`ConnectionConfig::with_encryption_trust_pinned_certificates()`].
 #[derive(Debug, Error)]
 #[non_exhaustive]
 #[error("{message}")]
@@ -884,6 +1244,17 @@ pub struct ConfigureFetchSizeError<Builder> {
     pub builder: Builder,
 }
 
+/// Used when enabling routing for an address that doesn't support it.
+///
+/// Routing is not supported for Unix domain sockets (see [`Address::unix_socket()`]).
+///
+/// See also [`ConnectionConfig::with_routing()`], [`ConnectionConfig::with_address()`].
+#[derive(Debug, Error)]
+#[error("routing is not supported for Unix domain socket addresses")]
+pub struct ConfigureRoutingError<Builder> {
+    pub builder: Builder,
+}
+
 /// Used when configuring a routing context that is invalid.
 ///
 /// See also [`ConnectionConfig::with_routing_context()`].
@@ -914,7 +1285,7 @@ mod mockable {
         use rustls::RootCertStore;
         use rustls_platform_verifier::BuilderVerifierExt;
 
-        use super::NonVerifyingVerifier;
+        use super::{NonVerifyingVerifier, PinnedFingerprintVerifier};
 
         pub fn secure_tls_config() -> StdResult<ClientConfig, String> {
             // Fails if a provider is already installed. That's fine, too.
@@ -964,9 +1335,25 @@ mod mockable {
                 .set_certificate_verifier(Arc::new(NonVerifyingVerifier::new()));
             config
         }
+
+        pub fn pinned_tls_config(fingerprints: Vec<[u8; 32]>) -> StdResult<ClientConfig, String> {
+            // Fails if a provider is already installed. That's fine, too.
+            let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+
+            let verifier = PinnedFingerprintVerifier::new(fingerprints)?;
+            let root_store = RootCertStore::empty();
+            let mut config = ClientConfig::builder()
+                .with_root_certificates(root_store)
+                .with_no_client_auth();
+            config
+                .dangerous()
+                .set_certificate_verifier(Arc::new(verifier));
+            Ok(config)
+        }
     }
 
     mod dangerous {
+        use std::fmt::{Debug, Formatter};
         use std::result::Result as StdResult;
         use std::sync::Arc;
 
@@ -975,9 +1362,29 @@ mod mockable {
         use rustls::client::danger::{
             HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
         };
-        use rustls::{DigitallySignedStruct, RootCertStore, SignatureScheme};
+        use rustls::crypto::CryptoProvider;
+        use rustls::crypto::hash::{Hash, HashAlgorithm};
+        use rustls::{DigitallySignedStruct, RootCertStore, SignatureScheme, SupportedCipherSuite};
         use rustls_pki_types::{CertificateDer, Der, ServerName, TrustAnchor, UnixTime};
 
+        /// Create a verifier to forward the signature verification methods to.
+        fn signature_verifier() -> WebPkiServerVerifier {
+            let default_verifier = WebPkiServerVerifier::builder(Arc::new(RootCertStore {
+                roots: vec![
+                    // any certificate will do as we only forward those methods to the default
+                    // verifier which do not care about the certificate
+                    TrustAnchor {
+                        subject: Der::from_slice(b""),
+                        subject_public_key_info: Der::from_slice(b""),
+                        name_constraints: None,
+                    },
+                ],
+            }))
+            .build()
+            .unwrap();
+            Arc::into_inner(default_verifier).unwrap()
+        }
+
         /// As the name suggests, this verifier happily accepts any certificate.
         /// This is not secure and should only be used for testing.
         #[derive(Debug)]
@@ -987,21 +1394,9 @@ mod mockable {
 
         impl NonVerifyingVerifier {
             pub fn new() -> Self {
-                let default_verifier = WebPkiServerVerifier::builder(Arc::new(RootCertStore {
-                    roots: vec![
-                        // any certificate will do as we only forward those methods to the default
-                        // verifier which do not care about the certificate
-                        TrustAnchor {
-                            subject: Der::from_slice(b""),
-                            subject_public_key_info: Der::from_slice(b""),
-                            name_constraints: None,
-                        },
-                    ],
-                }))
-                .build()
-                .unwrap();
-                let default_verifier = Arc::into_inner(default_verifier).unwrap();
-                Self { default_verifier }
+                Self {
+                    default_verifier: signature_verifier(),
+                }
             }
         }
 
@@ -1041,9 +1436,137 @@ mod mockable {
                 self.default_verifier.supported_verify_schemes()
             }
         }
+
+        /// Accepts only certificates whose SHA-256 fingerprint is among the pinned ones.
+        /// Neither the certificate chain, nor the server name, nor the validity period are
+        /// checked.
+        pub(super) struct PinnedFingerprintVerifier {
+            fingerprints: Vec<[u8; 32]>,
+            sha256: &'static dyn Hash,
+            default_verifier: WebPkiServerVerifier,
+        }
+
+        impl PinnedFingerprintVerifier {
+            pub fn new(fingerprints: Vec<[u8; 32]>) -> StdResult<Self, String> {
+                let provider = CryptoProvider::get_default()
+                    .ok_or_else(|| String::from("no crypto provider installed"))?;
+                let sha256 = provider
+                    .cipher_suites
+                    .iter()
+                    .filter_map(|suite| match suite {
+                        SupportedCipherSuite::Tls13(suite) => Some(suite.common.hash_provider),
+                        _ => None,
+                    })
+                    .find(|hash| hash.algorithm() == HashAlgorithm::SHA256)
+                    .ok_or_else(|| {
+                        String::from("installed crypto provider does not support SHA-256")
+                    })?;
+                Ok(Self {
+                    fingerprints,
+                    sha256,
+                    default_verifier: signature_verifier(),
+                })
+            }
+        }
+
+        impl Debug for PinnedFingerprintVerifier {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct("PinnedFingerprintVerifier")
+                    .field("fingerprints", &self.fingerprints)
+                    .finish_non_exhaustive()
+            }
+        }
+
+        impl ServerCertVerifier for PinnedFingerprintVerifier {
+            fn verify_server_cert(
+                &self,
+                end_entity: &CertificateDer<'_>,
+                _intermediates: &[CertificateDer<'_>],
+                _server_name: &ServerName<'_>,
+                _ocsp_response: &[u8],
+                _now: UnixTime,
+            ) -> StdResult<ServerCertVerified, RustlsError> {
+                let fingerprint = self.sha256.hash(end_entity);
+                match self
+                    .fingerprints
+                    .iter()
+                    .any(|pinned| pinned.as_slice() == fingerprint.as_ref())
+                {
+                    true => Ok(ServerCertVerified::assertion()),
+                    false => Err(RustlsError::General(String::from(
+                        "server certificate does not match any pinned fingerprint",
+                    ))),
+                }
+            }
+
+            fn verify_tls12_signature(
+                &self,
+                message: &[u8],
+                cert: &CertificateDer<'_>,
+                dss: &DigitallySignedStruct,
+            ) -> StdResult<HandshakeSignatureValid, RustlsError> {
+                self.default_verifier
+                    .verify_tls12_signature(message, cert, dss)
+            }
+
+            fn verify_tls13_signature(
+                &self,
+                message: &[u8],
+                cert: &CertificateDer<'_>,
+                dss: &DigitallySignedStruct,
+            ) -> StdResult<HandshakeSignatureValid, RustlsError> {
+                self.default_verifier
+                    .verify_tls13_signature(message, cert, dss)
+            }
+
+            fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+                self.default_verifier.supported_verify_schemes()
+            }
+        }
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+
+            const FINGERPRINT: [u8; 32] = [
+                0x30, 0x50, 0x55, 0x40, 0x11, 0x0B, 0x7D, 0xC1, 0x86, 0xC8, 0x9B, 0x9A, 0x7B, 0xB6,
+                0xD6, 0xB1, 0x4D, 0x7E, 0x85, 0x28, 0xC9, 0x05, 0x77, 0xAD, 0xCB, 0x55, 0x9E, 0x4F,
+                0x57, 0x48, 0x22, 0x25,
+            ];
+
+            fn verify(fingerprints: Vec<[u8; 32]>) -> StdResult<ServerCertVerified, RustlsError> {
+                let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+                let cert = rustls_pemfile::certs(
+                    &mut &include_bytes!("../test_data/client_certificate/client.pem")[..],
+                )
+                .next()
+                .unwrap()
+                .unwrap();
+                PinnedFingerprintVerifier::new(fingerprints)
+                    .unwrap()
+                    .verify_server_cert(
+                        &cert,
+                        &[],
+                        &ServerName::try_from("example.com").unwrap(),
+                        &[],
+                        UnixTime::now(),
+                    )
+            }
+
+            #[test]
+            fn test_pinned_fingerprint_accepted() {
+                verify(vec![[0; 32], FINGERPRINT]).unwrap();
+            }
+
+            #[test]
+            fn test_other_fingerprint_rejected() {
+                verify(vec![[0; 32]]).unwrap_err();
+                verify(vec![]).unwrap_err();
+            }
+        }
     }
 
-    use dangerous::NonVerifyingVerifier;
+    use dangerous::{NonVerifyingVerifier, PinnedFingerprintVerifier};
 }
 
 #[cfg(test)]
@@ -1144,6 +1667,38 @@ mod tests {
         connection_config.tls_config.unwrap();
     }
 
+    #[rstest]
+    fn test_pinned_certificates_tls() {
+        let _m = get_tls_helper_lock();
+        let ctx = tls_helper::pinned_tls_config_context();
+        ctx.expect()
+            .withf(|fingerprints| *fingerprints == [[1; 32], [2; 32]])
+            .returning(|_| Ok(get_test_client_config()));
+
+        let address = ("localhost", 7687).into();
+        let connection_config = ConnectionConfig::new(address)
+            .with_encryption_trust_pinned_certificates([[1; 32], [2; 32]])
+            .unwrap();
+
+        connection_config.tls_config.unwrap();
+    }
+
+    #[rstest]
+    fn test_client_certificate() {
+        let certificate = ClientCertificate::from_pem(
+            include_bytes!("../test_data/client_certificate/client.pem"),
+            include_bytes!("../test_data/client_certificate/client.key"),
+        )
+        .unwrap();
+        let address = ("localhost", 7687).into();
+        let connection_config = ConnectionConfig::new(address);
+        assert!(connection_config.client_certificate.is_none());
+        let connection_config = connection_config.with_client_certificate(certificate);
+        assert!(connection_config.client_certificate.is_some());
+        let connection_config = connection_config.without_client_certificate();
+        assert!(connection_config.client_certificate.is_none());
+    }
+
     #[rstest]
     fn test_custom_ca_tls() {
         let test_paths = Arc::new(
@@ -1241,6 +1796,64 @@ mod tests {
         assert_eq!(connection_config.address.port(), port);
     }
 
+    #[rstest]
+    #[case("bolt+unix:///tmp/neo4j.sock", "/tmp/neo4j.sock")]
+    #[case("bolt+unix:///var/run/neo4j/bolt", "/var/run/neo4j/bolt")]
+    fn test_parsing_unix_socket(#[case] uri: &str, #[case] path: &str) {
+        let connection_config = ConnectionConfig::try_from(uri).unwrap();
+        assert_eq!(
+            connection_config.address.unix_socket_path(),
+            Some(Path::new(path))
+        );
+        assert!(connection_config.routing_context.is_none());
+        assert!(connection_config.tls_config.is_none());
+    }
+
+    #[rstest]
+    #[case("bolt+unix://localhost/tmp/neo4j.sock")]
+    #[case("bolt+unix://localhost:7687/tmp/neo4j.sock")]
+    #[case("bolt+unix://")]
+    #[case("bolt+unix:///")]
+    #[case("bolt+unix:///tmp/neo4j.sock?foo=bar")]
+    #[case("bolt+unix:///tmp/neo4j.sock#foo")]
+    fn test_parsing_invalid_unix_socket(#[case] uri: &str) {
+        ConnectionConfig::try_from(uri).unwrap_err();
+    }
+
+    #[test]
+    fn test_unix_socket_disables_routing() {
+        let connection_config = ConnectionConfig::new(Address::unix_socket("/tmp/neo4j.sock"));
+        assert!(connection_config.routing_context.is_none());
+
+        let err = connection_config
+            .with_routing_context(HashMap::new())
+            .unwrap_err();
+        assert!(err.to_string().contains("Unix domain socket"));
+        assert!(err.builder.routing_context.is_none());
+
+        let err = err.builder.with_routing(true).unwrap_err();
+        assert!(err.builder.routing_context.is_none());
+        err.builder.with_routing(false).unwrap();
+    }
+
+    #[test]
+    fn test_routing_rejects_switching_to_unix_socket() {
+        let connection_config = ConnectionConfig::new(Address::from(("localhost", 7687)));
+
+        let err = connection_config
+            .with_address(Address::unix_socket("/tmp/neo4j.sock"))
+            .unwrap_err();
+        assert_eq!(err.builder.address, Address::from(("localhost", 7687)));
+
+        let connection_config = err
+            .builder
+            .with_routing(false)
+            .unwrap()
+            .with_address(Address::unix_socket("/tmp/neo4j.sock"))
+            .unwrap();
+        assert!(connection_config.address.unix_socket_path().is_some());
+    }
+
     #[rstest]
     #[case("", hash_map!())]
     #[case("?", hash_map!())]
@@ -0,0 +1,296 @@
+// Copyright Rouven Bauer
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{Debug, Formatter};
+use std::fs;
+use std::path::Path;
+use std::result::Result as StdResult;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use rustls::SignatureScheme;
+use rustls::client::ResolvesClientCert;
+use rustls::crypto::CryptoProvider;
+use rustls::sign::CertifiedKey;
+use thiserror::Error;
+
+// imports for docs
+#[allow(unused)]
+use super::ConnectionConfig;
+
+/// A client certificate (chain) together with its private key used for mutual TLS (mTLS).
+///
+/// See [`ConnectionConfig::with_client_certificate()`] and
+/// [`ConnectionConfig::with_client_certificate_provider()`].
+#[derive(Clone)]
+pub struct ClientCertificate {
+    key: Arc<CertifiedKey>,
+}
+
+impl ClientCertificate {
+    /// Load a client certificate from PEM encoded files.
+    ///
+    /// `cert_path` must point to a file containing the certificate chain, starting with the
+    /// client's certificate.
+    /// `key_path` must point to a file containing the unencrypted private key (PKCS#1, PKCS#8, or
+    /// SEC1).
+    /// Both may point to the same file.
+    ///
+    /// # Errors
+    /// A [`ClientCertificateError`] if the files cannot be read, do not contain a certificate or a
+    /// private key, or the private key does not match the certificate.
+    pub fn from_pem_files(
+        cert_path: impl AsRef<Path>,
+        key_path: impl AsRef<Path>,
+    ) -> StdResult<Self, ClientCertificateError> {
+        let (cert_path, key_path) = (cert_path.as_ref(), key_path.as_ref());
+        let cert_pem = fs::read(cert_path).map_err(|e| {
+            ClientCertificateError::new(format!(
+                "failed to read client certificate from {cert_path:?}: {e}"
+            ))
+        })?;
+        let key_pem = fs::read(key_path).map_err(|e| {
+            ClientCertificateError::new(format!("failed to read client key from {key_path:?}: {e}"))
+        })?;
+        Self::from_pem(&cert_pem, &key_pem)
+    }
+
+    /// Load a client certificate from PEM encoded bytes.
+    ///
+    /// See [`ClientCertificate::from_pem_files()`] for details.
+    pub fn from_pem(cert_pem: &[u8], key_pem: &[u8]) -> StdResult<Self, ClientCertificateError> {
+        let cert_chain = rustls_pemfile::certs(&mut &*cert_pem)
+            .collect::<StdResult<Vec<_>, _>>()
+            .map_err(|e| {
+                ClientCertificateError::new(format!("failed to parse client certificate: {e}"))
+            })?;
+        if cert_chain.is_empty() {
+            return Err(ClientCertificateError::new(
+                "no certificate found in client certificate PEM",
+            ));
+        }
+        let key = rustls_pemfile::private_key(&mut &*key_pem)
+            .map_err(|e| ClientCertificateError::new(format!("failed to parse client key: {e}")))?
+            .ok_or_else(|| ClientCertificateError::new("no private key found in client key PEM"))?;
+        let key = CertifiedKey::from_der(cert_chain, key, &crypto_provider()).map_err(|e| {
+            ClientCertificateError::new(format!("failed to load client certificate: {e}"))
+        })?;
+        Ok(Self { key: Arc::new(key) })
+    }
+}
+
+impl Debug for ClientCertificate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientCertificate").finish_non_exhaustive()
+    }
+}
+
+fn crypto_provider() -> Arc<CryptoProvider> {
+    // Fails if a provider is already installed. That's fine, too.
+    let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+    Arc::clone(
+        CryptoProvider::get_default().expect("crypto provider installed or failed to install"),
+    )
+}
+
+/// Used when loading a [`ClientCertificate`] failed.
+#[derive(Debug, Error)]
+#[error("{message}")]
+pub struct ClientCertificateError {
+    message: String,
+}
+
+impl ClientCertificateError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+/// The `ClientCertificateProvider` trait allows to change the [`ClientCertificate`] used for
+/// mutual TLS (mTLS) while the driver is running (e.g., when certificates are rotated).
+///
+/// **⚠️ WARNING**:
+/// Any provider implementation must not interact with the driver it is used with to avoid
+/// deadlocks.
+///
+/// Pre-defined provider implementations are available in
+/// [`client_certificate_providers#functions`] and [`RotatingClientCertificateProvider`].
+pub trait ClientCertificateProvider: Send + Sync + Debug {
+    /// Get the [`ClientCertificate`] to be used for new connections.
+    ///
+    /// The driver will call this method whenever it establishes a new TLS connection.
+    /// This is expected to happen frequently, so this method should be fast.
+    /// Cloning a [`ClientCertificate`] is cheap.
+    /// Hence, implementations are advised to hold on to the current certificate instead of
+    /// loading it on every call.
+    ///
+    /// Connections established with an older certificate are not affected.
+    fn get_certificate(&self) -> ClientCertificate;
+}
+
+/// Contains pre-defined [`ClientCertificateProvider`] implementations.
+pub mod client_certificate_providers {
+    use super::*;
+
+    /// Create a new [`ClientCertificateProvider`] that always uses the same
+    /// [`ClientCertificate`].
+    pub fn new_static(certificate: ClientCertificate) -> impl ClientCertificateProvider {
+        StaticClientCertificateProvider { certificate }
+    }
+
+    /// Create a new [`RotatingClientCertificateProvider`].
+    ///
+    /// See [`RotatingClientCertificateProvider::update_certificate()`].
+    pub fn new_rotating(certificate: ClientCertificate) -> RotatingClientCertificateProvider {
+        RotatingClientCertificateProvider {
+            certificate: Mutex::new(certificate),
+        }
+    }
+
+    #[derive(Debug)]
+    struct StaticClientCertificateProvider {
+        certificate: ClientCertificate,
+    }
+
+    impl ClientCertificateProvider for StaticClientCertificateProvider {
+        fn get_certificate(&self) -> ClientCertificate {
+            self.certificate.clone()
+        }
+    }
+}
+
+/// A [`ClientCertificateProvider`] whose certificate can be replaced at any time.
+///
+/// Created with [`client_certificate_providers::new_rotating()`].
+///
+/// # Example
+/// ```no_run
+/// use std::sync::Arc;
+///
+/// use neo4j::driver::client_certificate::{client_certificate_providers, ClientCertificate};
+/// use neo4j::driver::{ConnectionConfig, Driver, DriverConfig};
+///
+/// let certificate = ClientCertificate::from_pem_files("client.pem", "client.key").unwrap();
+/// let provider = Arc::new(client_certificate_providers::new_rotating(certificate));
+/// let connection_config = ConnectionConfig::new(("localhost", 7687).into())
+///     .with_encryption_trust_default_cas()
+///     .unwrap()
+///     .with_client_certificate_provider(provider.clone());
+/// let driver = Driver::new(connection_config, DriverConfig::new());
+///
+/// // later, when the certificate has been rotated
+/// let certificate = ClientCertificate::from_pem_files("client.pem", "client.key").unwrap();
+/// provider.update_certificate(certificate);
+/// # drop(driver);
+/// ```
+#[derive(Debug)]
+pub struct RotatingClientCertificateProvider {
+    certificate: Mutex<ClientCertificate>,
+}
+
+impl RotatingClientCertificateProvider {
+    /// Replace the certificate to be used for new connections.
+    pub fn update_certificate(&self, certificate: ClientCertificate) {
+        *self.certificate.lock() = certificate;
+    }
+}
+
+impl ClientCertificateProvider for RotatingClientCertificateProvider {
+    fn get_certificate(&self) -> ClientCertificate {
+        self.certificate.lock().clone()
+    }
+}
+
+/// Bridges a [`ClientCertificateProvider`] to rustls.
+#[derive(Debug)]
+pub(crate) struct ClientCertificateResolver {
+    provider: Arc<dyn ClientCertificateProvider>,
+}
+
+impl ClientCertificateResolver {
+    pub(crate) fn new(provider: Arc<dyn ClientCertificateProvider>) -> Self {
+        Self { provider }
+    }
+}
+
+impl ResolvesClientCert for ClientCertificateResolver {
+    fn resolve(
+        &self,
+        _root_hint_subjects: &[&[u8]],
+        _sigschemes: &[SignatureScheme],
+    ) -> Option<Arc<CertifiedKey>> {
+        Some(self.provider.get_certificate().key)
+    }
+
+    fn has_certs(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CERT: &[u8] = include_bytes!("../../test_data/client_certificate/client.pem");
+    const KEY: &[u8] = include_bytes!("../../test_data/client_certificate/client.key");
+    const OTHER_KEY: &[u8] = include_bytes!("../../test_data/client_certificate/other.key");
+
+    #[test]
+    fn test_from_pem() {
+        ClientCertificate::from_pem(CERT, KEY).unwrap();
+    }
+
+    #[test]
+    fn test_from_pem_missing_cert() {
+        let err = ClientCertificate::from_pem(KEY, KEY).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "no certificate found in client certificate PEM"
+        );
+    }
+
+    #[test]
+    fn test_from_pem_missing_key() {
+        let err = ClientCertificate::from_pem(CERT, CERT).unwrap_err();
+        assert_eq!(err.to_string(), "no private key found in client key PEM");
+    }
+
+    #[test]
+    fn test_from_pem_mismatching_key() {
+        let err = ClientCertificate::from_pem(CERT, OTHER_KEY).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("failed to load client certificate")
+        );
+    }
+
+    #[test]
+    fn test_resolver_follows_rotation() {
+        let first = ClientCertificate::from_pem(CERT, KEY).unwrap();
+        let second = ClientCertificate::from_pem(CERT, KEY).unwrap();
+        let provider = Arc::new(client_certificate_providers::new_rotating(first.clone()));
+        let resolver = ClientCertificateResolver::new(Arc::clone(&provider) as _);
+
+        let resolved = resolver.resolve(&[], &[]).unwrap();
+        assert!(Arc::ptr_eq(&resolved, &first.key));
+        let resolved = resolver.resolve(&[], &[]).unwrap();
+        assert!(Arc::ptr_eq(&resolved, &first.key));
+
+        provider.update_certificate(second.clone());
+        let resolved = resolver.resolve(&[], &[]).unwrap();
+        assert!(Arc::ptr_eq(&resolved, &second.key));
+    }
+}
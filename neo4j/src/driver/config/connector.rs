@@ -0,0 +1,253 @@
+// Copyright Rouven Bauer
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::io::{Error as IoError, ErrorKind as IoErrorKind, Read, Result as IoResult, Write};
+use std::net::TcpStream;
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+use std::time::Duration;
+
+use crate::address_::Address;
+
+// imports for docs
+#[allow(unused)]
+use super::{ConnectionConfig, DriverConfig};
+
+/// A bidirectional byte stream the driver can speak Bolt over.
+///
+/// This trait is implemented for [`TcpStream`] and (on Unix platforms)
+/// [`UnixStream`](std::os::unix::net::UnixStream).
+///
+/// The driver enforces deadlines on blocking reads and writes (e.g., while acquiring a
+/// connection, see [`DriverConfig::with_connection_acquisition_timeout()`]) by setting timeouts
+/// on the transport.
+/// Transports that don't override [`Transport::set_read_timeout()`] and
+/// [`Transport::set_write_timeout()`] can't honor them: any read or write subject to a deadline
+/// fails.
+///
+/// # Example
+/// ```
+/// use std::io::{Read, Result as IoResult, Write};
+/// use std::net::TcpStream;
+/// use std::time::Duration;
+///
+/// use neo4j::driver::connector::Transport;
+///
+/// #[derive(Debug)]
+/// struct LoggingTransport(TcpStream);
+///
+/// impl Read for LoggingTransport {
+///     fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+///         let n = self.0.read(buf)?;
+///         println!("read {n} bytes");
+///         Ok(n)
+///     }
+/// }
+///
+/// impl Write for LoggingTransport {
+///     fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+///         let n = self.0.write(buf)?;
+///         println!("wrote {n} bytes");
+///         Ok(n)
+///     }
+///
+///     fn flush(&mut self) -> IoResult<()> {
+///         self.0.flush()
+///     }
+/// }
+///
+/// impl Transport for LoggingTransport {
+///     fn set_read_timeout(&mut self, timeout: Option<Duration>) -> IoResult<()> {
+///         self.0.set_read_timeout(timeout)
+///     }
+///
+///     fn set_write_timeout(&mut self, timeout: Option<Duration>) -> IoResult<()> {
+///         self.0.set_write_timeout(timeout)
+///     }
+/// }
+/// # fn wrap(stream: TcpStream) -> Box<dyn Transport> {
+/// #     Box::new(LoggingTransport(stream))
+/// # }
+/// # let _ = wrap;
+/// ```
+pub trait Transport: Read + Write + Send + Debug {
+    /// Set the timeout for blocking reads ([`None`] meaning no timeout).
+    ///
+    /// Reads that don't complete in time must fail with an [`IoErrorKind::WouldBlock`] or
+    /// [`IoErrorKind::TimedOut`] error (like with [`TcpStream::set_read_timeout()`]).
+    ///
+    /// The default implementation returns an [`IoErrorKind::Unsupported`] error.
+    fn set_read_timeout(&mut self, timeout: Option<Duration>) -> IoResult<()> {
+        let _ = timeout;
+        Err(timeouts_unsupported())
+    }
+
+    /// Set the timeout for blocking writes ([`None`] meaning no timeout).
+    ///
+    /// Writes that don't complete in time must fail with an [`IoErrorKind::WouldBlock`] or
+    /// [`IoErrorKind::TimedOut`] error (like with [`TcpStream::set_write_timeout()`]).
+    ///
+    /// The default implementation returns an [`IoErrorKind::Unsupported`] error.
+    fn set_write_timeout(&mut self, timeout: Option<Duration>) -> IoResult<()> {
+        let _ = timeout;
+        Err(timeouts_unsupported())
+    }
+}
+
+fn timeouts_unsupported() -> IoError {
+    IoError::new(
+        IoErrorKind::Unsupported,
+        "transport doesn't support timeouts (see Transport::set_read_timeout and \
+        Transport::set_write_timeout)",
+    )
+}
+
+macro_rules! impl_transport {
+    ($stream:ty) => {
+        impl Transport for $stream {
+            #[inline]
+            fn set_read_timeout(&mut self, timeout: Option<Duration>) -> IoResult<()> {
+                <$stream>::set_read_timeout(self, timeout)
+            }
+
+            #[inline]
+            fn set_write_timeout(&mut self, timeout: Option<Duration>) -> IoResult<()> {
+                <$stream>::set_write_timeout(self, timeout)
+            }
+        }
+    };
+}
+
+impl_transport!(TcpStream);
+#[cfg(unix)]
+impl_transport!(UnixStream);
+
+/// Establishes the connections the driver runs Bolt over.
+///
+/// By default, the driver connects to the DBMS via TCP.
+/// A custom connector allows running Bolt over any other [`Transport`], e.g., Unix domain
+/// sockets, a proxy tunnel, an in-process stream, or a test double.
+///
+/// Register a connector with [`DriverConfig::with_connector()`].
+///
+/// When a connector is used
+///  * the driver does not DNS resolve addresses.
+///    Addresses are still passed through the custom resolver (see
+///    [`DriverConfig::with_resolver()`]), if configured.
+///  * it's also in charge of connecting to Unix domain socket addresses (see
+///    [`Address::unix_socket()`]).
+///  * TLS (see [`ConnectionConfig::with_encryption_trust_default_cas()`] and friends) is still
+///    applied on top of the returned transport.
+///  * TCP keep alive (see [`DriverConfig::with_keep_alive()`]) is not applied.
+///  * deadlines on individual reads and writes (e.g., for connection liveness checks or timeout
+///    hints sent by the server) are enforced through [`Transport::set_read_timeout()`] and
+///    [`Transport::set_write_timeout()`].
+///
+/// # Example
+/// ```
+/// use std::io::Result as IoResult;
+/// use std::net::TcpStream;
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// use neo4j::address::Address;
+/// use neo4j::driver::connector::{Connector, Transport};
+/// use neo4j::driver::DriverConfig;
+///
+/// #[derive(Debug)]
+/// struct NoDelayTcpConnector;
+///
+/// impl Connector for NoDelayTcpConnector {
+///     fn connect(
+///         &self,
+///         address: &Address,
+///         _timeout: Option<Duration>,
+///     ) -> IoResult<Box<dyn Transport>> {
+///         let stream = TcpStream::connect((address.host(), address.port()))?;
+///         stream.set_nodelay(true)?;
+///         Ok(Box::new(stream))
+///     }
+/// }
+///
+/// let config = DriverConfig::new().with_connector(Arc::new(NoDelayTcpConnector));
+/// # let _ = config;
+/// ```
+pub trait Connector: Send + Sync + Debug {
+    /// Open a new transport to the server at `address`.
+    ///
+    /// `timeout` is the time left for establishing the connection (see
+    /// [`DriverConfig::with_connection_timeout()`]).
+    /// It's up to the connector to respect it.
+    ///
+    /// Returned errors are treated like any other failure to connect to the server.
+    /// In particular, the driver might try another server or retry the work.
+    fn connect(&self, address: &Address, timeout: Option<Duration>)
+    -> IoResult<Box<dyn Transport>>;
+}
+
+/// Connect to an address created with [`Address::unix_socket()`].
+#[cfg(unix)]
+pub(crate) fn connect_unix_socket(address: &Address) -> IoResult<UnixStream> {
+    let Some(path) = address.unix_socket_path() else {
+        return Err(IoError::new(
+            IoErrorKind::InvalidInput,
+            format!("{address} is not a Unix domain socket address"),
+        ));
+    };
+    UnixStream::connect(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_connect_unix_socket_rejects_network_address() {
+        let address = Address::from(("localhost", 7687));
+        let err = connect_unix_socket(&address).unwrap_err();
+        assert_eq!(err.kind(), IoErrorKind::InvalidInput);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_connect_unix_socket() {
+        use std::os::unix::net::UnixListener;
+        use std::thread;
+
+        let path = std::env::temp_dir().join(format!(
+            "neo4j-rust-driver-test-{}.sock",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4];
+            stream.read_exact(&mut buf).unwrap();
+            stream.write_all(&buf).unwrap();
+        });
+
+        let address = Address::unix_socket(&path);
+        let mut transport = connect_unix_socket(&address).unwrap();
+        transport.write_all(&[1, 2, 3, 4]).unwrap();
+        let mut buf = [0u8; 4];
+        transport.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [1, 2, 3, 4]);
+
+        server.join().unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,357 @@
+// Copyright Rouven Bauer
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::address_::Address;
+use crate::driver::ConnectionPoolMetrics;
+
+// imports for docs
+#[allow(unused)]
+use super::DriverConfig;
+
+/// A server the driver could send work to, together with the state of its connection pool.
+///
+/// See [`LoadBalancingStrategy`].
+#[derive(Debug)]
+pub struct ServerCandidate {
+    address: Arc<Address>,
+    metrics: ConnectionPoolMetrics,
+}
+
+impl ServerCandidate {
+    pub(crate) fn new(address: Arc<Address>, metrics: ConnectionPoolMetrics) -> Self {
+        Self { address, metrics }
+    }
+
+    /// The address of the server as found in the routing table.
+    pub fn address(&self) -> &Arc<Address> {
+        &self.address
+    }
+
+    /// The current metrics of the driver's connection pool for this server.
+    ///
+    /// If the driver has no pool for the server yet, all metrics are zero (or [`None`]).
+    pub fn metrics(&self) -> &ConnectionPoolMetrics {
+        &self.metrics
+    }
+
+    pub(crate) fn into_address(self) -> Arc<Address> {
+        self.address
+    }
+
+    fn load(&self) -> usize {
+        self.metrics.in_use + self.metrics.creating
+    }
+}
+
+/// Decides which server the driver prefers when routing work.
+///
+/// When routing, the driver needs to pick one of the readers or writers of the routing table
+/// to acquire a connection from.
+/// It asks the strategy to sort the candidates by preference and tries them in that order
+/// until one yields a connection.
+/// Only called when there is more than one candidate.
+///
+/// Register a strategy with [`DriverConfig::with_load_balancing_strategy()`].
+/// The driver comes with these strategies:
+///  * [`LeastConnected`] (*default*)
+///  * [`RoundRobin`]
+///  * [`LatencyWeighted`]
+///  * [`ZoneAffinity`]
+///
+/// **⚠️ WARNING**:
+/// The strategy is called synchronously on every connection acquisition.
+/// Implementations must therefore be fast and must not block.
+/// Further, they must not interact with the driver they are used with to avoid deadlocks.
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+///
+/// use neo4j::driver::load_balancing::{LoadBalancingStrategy, ServerCandidate};
+/// use neo4j::driver::DriverConfig;
+///
+/// /// Prefer servers with the fewest connections waiting to be acquired.
+/// #[derive(Debug)]
+/// struct LeastPending;
+///
+/// impl LoadBalancingStrategy for LeastPending {
+///     fn order(&self, candidates: &mut [ServerCandidate]) {
+///         candidates.sort_by_key(|candidate| candidate.metrics().pending_acquisitions);
+///     }
+/// }
+///
+/// let config = DriverConfig::new().with_load_balancing_strategy(Arc::new(LeastPending));
+/// # drop(config);
+/// ```
+pub trait LoadBalancingStrategy: Send + Sync + Debug {
+    /// Sort `candidates` by preference: most preferred first.
+    ///
+    /// The candidates are passed in the order of the routing table.
+    fn order(&self, candidates: &mut [ServerCandidate]);
+}
+
+/// Prefer servers with the fewest connections in use (including those being established).
+///
+/// This is the *default* strategy.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LeastConnected;
+
+impl LoadBalancingStrategy for LeastConnected {
+    fn order(&self, candidates: &mut [ServerCandidate]) {
+        candidates.sort_by_key(ServerCandidate::load);
+    }
+}
+
+/// Cycle through the servers, preferring a different one for each acquisition.
+///
+/// The strategy is stateful.
+/// Sharing one instance between several drivers makes them share the rotation.
+#[derive(Debug, Default)]
+pub struct RoundRobin {
+    next: AtomicUsize,
+}
+
+impl RoundRobin {
+    /// Create a new round-robin strategy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl LoadBalancingStrategy for RoundRobin {
+    fn order(&self, candidates: &mut [ServerCandidate]) {
+        if candidates.is_empty() {
+            return;
+        }
+        let offset = self.next.fetch_add(1, Ordering::Relaxed) % candidates.len();
+        candidates.rotate_left(offset);
+    }
+}
+
+/// Prefer servers that respond fast and are not busy.
+///
+/// The cost of a server is its latency (see [`ConnectionPoolMetrics::round_trip_time`] plus
+/// [`ConnectionPoolMetrics::mean_acquisition_time()`]) multiplied by one more than the number of
+/// its connections in use.
+/// Servers with the lowest cost are preferred.
+/// Servers the driver has not measured yet count as having no latency, so they get tried (and
+/// measured) early.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LatencyWeighted;
+
+impl LatencyWeighted {
+    fn cost(candidate: &ServerCandidate) -> u128 {
+        let metrics = candidate.metrics();
+        let latency = metrics.round_trip_time.unwrap_or_default()
+            + metrics.mean_acquisition_time().unwrap_or_default();
+        latency.as_nanos() * (candidate.load() as u128 + 1)
+    }
+}
+
+impl LoadBalancingStrategy for LatencyWeighted {
+    fn order(&self, candidates: &mut [ServerCandidate]) {
+        candidates.sort_by_cached_key(Self::cost);
+    }
+}
+
+/// Prefer servers matching a predicate, e.g., servers in the same availability zone.
+///
+/// Matching servers are always preferred over non-matching ones.
+/// Within both groups, the servers are ordered by another strategy
+/// ([`LeastConnected`] by default, see [`ZoneAffinity::with_strategy()`]).
+/// Non-matching servers are only used if none of the matching ones yields a connection.
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+///
+/// use neo4j::driver::load_balancing::{RoundRobin, ZoneAffinity};
+/// use neo4j::driver::DriverConfig;
+///
+/// let strategy = ZoneAffinity::new(|address| address.host().ends_with(".eu-west-1.example.com"))
+///     .with_strategy(Arc::new(RoundRobin::new()));
+/// let config = DriverConfig::new().with_load_balancing_strategy(Arc::new(strategy));
+/// # drop(config);
+/// ```
+pub struct ZoneAffinity {
+    predicate: Box<dyn Fn(&Address) -> bool + Send + Sync>,
+    strategy: Arc<dyn LoadBalancingStrategy>,
+}
+
+impl ZoneAffinity {
+    /// Prefer servers for which `predicate` returns `true`.
+    pub fn new(predicate: impl Fn(&Address) -> bool + Send + Sync + 'static) -> Self {
+        Self {
+            predicate: Box::new(predicate),
+            strategy: Arc::new(LeastConnected),
+        }
+    }
+
+    /// Order the servers within the matching and within the non-matching group by `strategy`.
+    pub fn with_strategy(mut self, strategy: Arc<dyn LoadBalancingStrategy>) -> Self {
+        self.strategy = strategy;
+        self
+    }
+}
+
+impl Debug for ZoneAffinity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZoneAffinity")
+            .field("predicate", &"...")
+            .field("strategy", &self.strategy)
+            .finish()
+    }
+}
+
+impl LoadBalancingStrategy for ZoneAffinity {
+    fn order(&self, candidates: &mut [ServerCandidate]) {
+        self.strategy.order(candidates);
+        // stable sort => keeps the inner strategy's order within each group
+        candidates.sort_by_cached_key(|candidate| !(self.predicate)(candidate.address()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use rstest::*;
+
+    use super::*;
+
+    fn candidate(host: &str, in_use: usize, round_trip_time: Option<Duration>) -> ServerCandidate {
+        ServerCandidate::new(
+            Arc::new(Address::from((host, 7687))),
+            ConnectionPoolMetrics {
+                in_use,
+                round_trip_time,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn hosts(candidates: &[ServerCandidate]) -> Vec<&str> {
+        candidates.iter().map(|c| c.address().host()).collect()
+    }
+
+    #[test]
+    fn test_least_connected() {
+        let mut candidates = [
+            candidate("a", 2, None),
+            candidate("b", 0, None),
+            candidate("c", 1, None),
+            candidate("d", 0, None),
+        ];
+        LeastConnected.order(&mut candidates);
+        assert_eq!(hosts(&candidates), ["b", "d", "c", "a"]);
+    }
+
+    #[test]
+    fn test_least_connected_counts_connections_being_created() {
+        let mut busy = candidate("a", 0, None);
+        busy.metrics.creating = 2;
+        let mut candidates = [busy, candidate("b", 1, None)];
+        LeastConnected.order(&mut candidates);
+        assert_eq!(hosts(&candidates), ["b", "a"]);
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let strategy = RoundRobin::new();
+        let mut firsts = Vec::new();
+        for _ in 0..4 {
+            let mut candidates = [
+                candidate("a", 0, None),
+                candidate("b", 0, None),
+                candidate("c", 0, None),
+            ];
+            strategy.order(&mut candidates);
+            firsts.push(candidates[0].address().host().to_string());
+        }
+        assert_eq!(firsts, ["a", "b", "c", "a"]);
+    }
+
+    #[rstest]
+    #[case::by_latency(
+        [("a", 0, Some(30)), ("b", 0, Some(10)), ("c", 0, Some(20))],
+        ["b", "c", "a"],
+    )]
+    #[case::weighted_by_load(
+        [("a", 3, Some(10)), ("b", 0, Some(30)), ("c", 1, Some(20))],
+        ["b", "a", "c"],
+    )]
+    #[case::unmeasured_first(
+        [("a", 0, Some(10)), ("b", 5, None), ("c", 0, Some(20))],
+        ["b", "a", "c"],
+    )]
+    fn test_latency_weighted(
+        #[case] input: [(&str, usize, Option<u64>); 3],
+        #[case] expected: [&str; 3],
+    ) {
+        let mut candidates = input
+            .map(|(host, in_use, rtt)| candidate(host, in_use, rtt.map(Duration::from_millis)));
+        LatencyWeighted.order(&mut candidates);
+        assert_eq!(hosts(&candidates), expected);
+    }
+
+    #[test]
+    fn test_latency_weighted_includes_acquisition_time() {
+        let mut slow = candidate("a", 0, Some(Duration::from_millis(10)));
+        slow.metrics.acquisitions = 1;
+        slow.metrics.total_acquisition_time = Duration::from_millis(50);
+        let mut candidates = [slow, candidate("b", 0, Some(Duration::from_millis(20)))];
+        LatencyWeighted.order(&mut candidates);
+        assert_eq!(hosts(&candidates), ["b", "a"]);
+    }
+
+    #[test]
+    fn test_zone_affinity() {
+        let strategy = ZoneAffinity::new(|address| address.host().starts_with("local"));
+        let mut candidates = [
+            candidate("remote1", 0, None),
+            candidate("local1", 2, None),
+            candidate("remote2", 1, None),
+            candidate("local2", 1, None),
+        ];
+        strategy.order(&mut candidates);
+        assert_eq!(
+            hosts(&candidates),
+            ["local2", "local1", "remote1", "remote2"]
+        );
+    }
+
+    #[test]
+    fn test_zone_affinity_with_strategy() {
+        let strategy = ZoneAffinity::new(|address| address.host().starts_with("local"))
+            .with_strategy(Arc::new(RoundRobin::new()));
+        let order = |strategy: &ZoneAffinity| {
+            let mut candidates = [
+                candidate("local1", 0, None),
+                candidate("remote1", 0, None),
+                candidate("local2", 0, None),
+            ];
+            strategy.order(&mut candidates);
+            hosts(&candidates)
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(order(&strategy), ["local1", "local2", "remote1"]);
+        assert_eq!(order(&strategy), ["local2", "local1", "remote1"]);
+    }
+}
@@ -17,9 +17,15 @@ use std::sync::Arc;
 
 use thiserror::Error;
 
-use crate::driver::Record;
+use crate::driver::{FromRecord, Record};
 use crate::summary::Summary;
-use crate::value::ValueReceive;
+#[cfg(feature = "arrow_57")]
+use crate::value::arrow::{ArrowConversionError, ArrowConverter};
+#[cfg(feature = "serde_json_1")]
+use crate::value::jolt::{JoltError, JoltPathElement, write_ndjson_line};
+#[cfg(feature = "serde_1")]
+use crate::value::{DeserializeError, DeserializePathElement};
+use crate::value::{FromValueReceiveError, FromValueReceivePathElement, ValueReceive};
 
 #[derive(Debug)]
 pub struct EagerResult {
@@ -29,6 +35,33 @@ pub struct EagerResult {
 }
 
 impl EagerResult {
+    /// Assemble an `EagerResult`, e.g., as a test fixture.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use neo4j::driver::{EagerResult, Record};
+    /// use neo4j::summary::Summary;
+    /// use neo4j::ValueReceive;
+    ///
+    /// let key = Arc::new(String::from("n"));
+    /// let result = EagerResult::new(
+    ///     vec![Arc::clone(&key)],
+    ///     vec![Record::from_entries([(key, ValueReceive::Integer(1))])],
+    ///     Summary::builder().build(),
+    /// );
+    ///
+    /// assert_eq!(result.into_scalar().unwrap(), ValueReceive::Integer(1));
+    /// ```
+    pub fn new(keys: Vec<Arc<String>>, records: Vec<Record>, summary: Summary) -> Self {
+        Self {
+            keys,
+            records,
+            summary,
+        }
+    }
+
     /// Assuming the result contains exactly one record with exactly one value, return that value.
     ///
     /// Returns an [`ScalarError`] if the result contains not exactly one record or the record
@@ -86,6 +119,155 @@ impl EagerResult {
     pub fn into_values(self) -> impl Iterator<Item = impl Iterator<Item = ValueReceive>> {
         self.records.into_iter().map(Record::into_values)
     }
+
+    /// Convert all records into `T`, e.g., tuples of the records' values.
+    ///
+    /// See [`FromRecord`] for details.
+    /// On failure, the path of the returned [`FromValueReceiveError`] starts with the index of the
+    /// offending record.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// # let driver = doc_test_utils::get_driver();
+    /// let result = driver
+    ///     .execute_query("UNWIND [1, 2] AS x RETURN x, toString(x) AS s, null AS n")
+    ///     .with_database(Arc::new(String::from("neo4j")))
+    ///     .run()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     result.into_rows::<(i64, String, Option<bool>)>().unwrap(),
+    ///     vec![(1, String::from("1"), None), (2, String::from("2"), None)]
+    /// );
+    /// ```
+    pub fn into_rows<T: FromRecord>(self) -> StdResult<Vec<T>, FromValueReceiveError> {
+        self.records
+            .into_iter()
+            .enumerate()
+            .map(|(i, record)| {
+                T::from_record(record)
+                    .map_err(|e| e.prepend_path(FromValueReceivePathElement::Index(i)))
+            })
+            .collect()
+    }
+
+    /// Deserialize all records into any type implementing [`serde::Deserialize`].
+    ///
+    /// See [`Record::to()`] for details.
+    /// On failure, the path of the returned [`DeserializeError`] starts with the index of the
+    /// offending record.
+    ///
+    /// Requires the `serde_1` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize, PartialEq)]
+    /// struct Row {
+    ///     x: i64,
+    ///     y: i64,
+    /// }
+    ///
+    /// # let driver = doc_test_utils::get_driver();
+    /// let result = driver
+    ///     .execute_query("UNWIND [1, 2] AS x RETURN x, x + 1 AS y")
+    ///     .with_database(Arc::new(String::from("neo4j")))
+    ///     .run()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     result.records_as::<Row>().unwrap(),
+    ///     vec![Row { x: 1, y: 2 }, Row { x: 2, y: 3 }]
+    /// );
+    /// ```
+    #[cfg(feature = "serde_1")]
+    pub fn records_as<'de, T: serde::Deserialize<'de>>(
+        &'de self,
+    ) -> StdResult<Vec<T>, DeserializeError> {
+        self.records
+            .iter()
+            .enumerate()
+            .map(|(i, record)| {
+                record
+                    .to()
+                    .map_err(|e| e.prepend_path(DeserializePathElement::Index(i)))
+            })
+            .collect()
+    }
+
+    /// Convert all records into a single Arrow [`RecordBatch`](arrow_array_57::RecordBatch).
+    ///
+    /// The schema is inferred from the keys and the values.
+    /// See [`crate::value::arrow`] for how values are mapped.
+    /// To convert large results without holding them in memory, use
+    /// [`RecordStream::arrow_batches()`](crate::driver::record_stream::RecordStream::arrow_batches)
+    /// instead.
+    ///
+    /// Requires the `arrow_57` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// # let driver = doc_test_utils::get_driver();
+    /// let result = driver
+    ///     .execute_query("UNWIND range(1, 3) AS x RETURN x, {name: 'n' + x} AS map")
+    ///     .with_database(Arc::new(String::from("neo4j")))
+    ///     .run()
+    ///     .unwrap();
+    ///
+    /// let batch = result.to_arrow().unwrap();
+    /// assert_eq!(batch.num_rows(), 3);
+    /// assert_eq!(
+    ///     batch.schema().field(1).data_type().to_string(),
+    ///     r#"Struct("name": Utf8)"#
+    /// );
+    /// ```
+    #[cfg(feature = "arrow_57")]
+    pub fn to_arrow(&self) -> StdResult<arrow_array_57::RecordBatch, ArrowConversionError> {
+        ArrowConverter::new(self.keys.clone(), None).convert(&self.records)
+    }
+
+    /// Encode all records as newline-delimited JSON (NDJSON), one line per record.
+    ///
+    /// See [`Record::to_ndjson()`] for the format of each line.
+    /// On failure, the path of the returned [`JoltError`] starts with the index of the offending
+    /// record.
+    ///
+    /// Requires the `serde_json_1` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// # let driver = doc_test_utils::get_driver();
+    /// let result = driver
+    ///     .execute_query("UNWIND [1, 2] AS x RETURN x, date('2024-01-0' + x) AS d")
+    ///     .with_database(Arc::new(String::from("neo4j")))
+    ///     .run()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     result.to_ndjson().unwrap(),
+    ///     concat!(
+    ///         r#"{"x":{"Z":"1"},"d":{"T":"2024-01-01"}}"#,
+    ///         "\n",
+    ///         r#"{"x":{"Z":"2"},"d":{"T":"2024-01-02"}}"#,
+    ///         "\n",
+    ///     )
+    /// );
+    /// ```
+    #[cfg(feature = "serde_json_1")]
+    pub fn to_ndjson(&self) -> StdResult<String, JoltError> {
+        let mut ndjson = String::new();
+        for (i, record) in self.records.iter().enumerate() {
+            write_ndjson_line(record, &mut ndjson)
+                .map_err(|e| e.prepend_path(JoltPathElement::Index(i)))?;
+        }
+        Ok(ndjson)
+    }
 }
 
 /// Error returned by [`EagerResult::into_scalar()`] and [`EagerResult::into_single()`] if the
@@ -17,8 +17,10 @@ pub(crate) mod deadline;
 mod pool;
 mod varint;
 
-#[cfg(feature = "_internal_testkit_backend")]
-pub use pool::ConnectionPoolMetrics;
 pub(crate) use pool::{
-    AcquireConfig, Pool, PoolConfig, PooledBolt, SessionAuth, UpdateRtArgs, UpdateRtDb,
+    AcquireConfig, Pool, PoolConfig, PoolRef, PooledBolt, SessionAuth, UpdateRtArgs, UpdateRtDb,
+};
+pub use pool::{
+    CircuitBreakerConfig, CircuitState, ConfigureCircuitBreakerError, ConnectionPoolMetrics,
+    ConnectionPoolObserver, HousekeepingConfig, RoutingTableSnapshot,
 };
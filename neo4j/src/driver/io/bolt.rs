@@ -33,6 +33,8 @@ pub(crate) mod message_parameters;
 mod packstream;
 mod response;
 mod socket;
+#[cfg(feature = "testing")]
+mod stub_connection;
 
 use std::backtrace::Backtrace;
 use std::borrow::Borrow;
@@ -40,7 +42,7 @@ use std::collections::{HashMap, VecDeque};
 use std::fmt::{Debug, Formatter};
 use std::hash::{Hash, Hasher};
 use std::io::{Read, Write};
-use std::net::{Shutdown, TcpStream};
+use std::net::Shutdown;
 use std::ops::Deref;
 use std::result;
 use std::sync::Arc;
@@ -51,7 +53,7 @@ use atomic_refcell::AtomicRefCell;
 use enum_dispatch::enum_dispatch;
 use usize_cast::FromUsize;
 
-use super::deadline::DeadlineIO;
+use super::deadline::{DeadlineIO, SocketTimeouts};
 use crate::address_::Address;
 use crate::driver::auth::AuthToken;
 use crate::error_::{Neo4jError, Result, ServerError};
@@ -75,7 +77,9 @@ use bolt5x7::{Bolt5x7, Bolt5x7StructTranslator};
 use bolt5x8::{Bolt5x8, Bolt5x8StructTranslator};
 use bolt6x0::{Bolt6x0, Bolt6x0StructTranslator};
 use chunk::{Chunker, Dechunker};
-pub(crate) use handshake::{TcpConnector, open};
+#[cfg(unix)]
+pub(crate) use handshake::UnixConnector;
+pub(crate) use handshake::{CustomConnector, SocketProvider, TcpConnector, open};
 use message::BoltMessage;
 use message_parameters::{
     BeginParameters, CommitParameters, DiscardParameters, GoodbyeParameters, HelloParameters,
@@ -86,7 +90,9 @@ use packstream::PackStreamSerializer;
 pub(crate) use response::{
     BoltMeta, BoltRecordFields, BoltResponse, ResponseCallbacks, ResponseMessage,
 };
-pub(crate) use socket::{BufTcpStream, Socket};
+pub(crate) use socket::{BufTcpStream, RawSocket, Socket};
+#[cfg(feature = "testing")]
+pub(crate) use stub_connection::{StubConnection, is_supported_version};
 
 macro_rules! debug_buf_start {
     ($name:ident) => {
@@ -203,6 +209,26 @@ macro_rules! socket_debug {
 }
 pub(crate) use socket_debug;
 
+/// Create a span for a request message that is closed once the server replied to it.
+/// See [`ResponseCallbacks::with_span()`].
+#[cfg(feature = "tracing")]
+macro_rules! message_span {
+    ($data:expr, $name:literal $(, $($fields:tt)+)?) => {
+        tracing::info_span!(
+            $name,
+            $($($fields)+,)?
+            db.system.name = "neo4j",
+            server.address = %$data.address,
+            neo4j.bolt_id = $data.bolt_id().as_deref(),
+            error.type = tracing::field::Empty,
+        )
+    };
+}
+
+fn socket_timeouts(socket: &Option<RawSocket>) -> Option<&dyn SocketTimeouts> {
+    socket.as_ref().map(|socket| socket as _)
+}
+
 pub(crate) fn dbg_extra(port: Option<u16>, bolt_id: Option<&str>) -> String {
     format!(
         "[#{:04X} {:<10}] ",
@@ -228,9 +254,10 @@ impl<RW: Read + Write> Bolt<RW> {
     fn new(
         version: (u8, u8),
         stream: RW,
-        socket: Arc<Option<TcpStream>>,
+        socket: Arc<Option<RawSocket>>,
         local_port: Option<u16>,
         address: Arc<Address>,
+        handshake_rtt: Duration,
     ) -> Self {
         let protocol_version = ServerAwareBoltVersion::parse(version.0, version.1)
             .unwrap_or_else(|| panic!("implement protocol for version {version:?}"));
@@ -273,6 +300,7 @@ impl<RW: Read + Write> Bolt<RW> {
             socket,
             local_port,
             address,
+            handshake_rtt,
         );
         Self { data, protocol }
     }
@@ -350,6 +378,12 @@ impl<RW: Read + Write> Bolt<RW> {
         parameters: RunParameters<KP, KM>,
         callbacks: ResponseCallbacks,
     ) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        let callbacks = callbacks.with_span(message_span!(
+            self.data,
+            "neo4j.run",
+            db.namespace = parameters.db
+        ));
         self.protocol.run(&mut self.data, parameters, callbacks)
     }
 
@@ -358,6 +392,14 @@ impl<RW: Read + Write> Bolt<RW> {
         parameters: DiscardParameters,
         callbacks: ResponseCallbacks,
     ) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        let callbacks = callbacks.with_span(message_span!(
+            self.data,
+            "neo4j.discard",
+            neo4j.records = tracing::field::Empty,
+            neo4j.query_type = tracing::field::Empty,
+            neo4j.has_more = tracing::field::Empty
+        ));
         self.protocol.discard(&mut self.data, parameters, callbacks)
     }
 
@@ -366,6 +408,14 @@ impl<RW: Read + Write> Bolt<RW> {
         parameters: PullParameters,
         callbacks: ResponseCallbacks,
     ) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        let callbacks = callbacks.with_span(message_span!(
+            self.data,
+            "neo4j.pull",
+            neo4j.records = tracing::field::Empty,
+            neo4j.query_type = tracing::field::Empty,
+            neo4j.has_more = tracing::field::Empty
+        ));
         self.protocol.pull(&mut self.data, parameters, callbacks)
     }
 
@@ -374,10 +424,18 @@ impl<RW: Read + Write> Bolt<RW> {
         parameters: BeginParameters<K>,
         callbacks: ResponseCallbacks,
     ) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        let callbacks = callbacks.with_span(message_span!(
+            self.data,
+            "neo4j.begin",
+            db.namespace = parameters.db.as_ref().map(|db| db.as_str())
+        ));
         self.protocol.begin(&mut self.data, parameters, callbacks)
     }
 
     pub(crate) fn commit(&mut self, callbacks: ResponseCallbacks) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        let callbacks = callbacks.with_span(message_span!(self.data, "neo4j.commit"));
         self.protocol
             .commit(&mut self.data, CommitParameters::new(), callbacks)
     }
@@ -392,6 +450,12 @@ impl<RW: Read + Write> Bolt<RW> {
         parameters: RouteParameters,
         callbacks: ResponseCallbacks,
     ) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        let callbacks = callbacks.with_span(message_span!(
+            self.data,
+            "neo4j.route",
+            db.namespace = parameters.db
+        ));
         self.protocol.route(&mut self.data, parameters, callbacks)
     }
 
@@ -424,7 +488,7 @@ impl<RW: Read + Write> Bolt<RW> {
         let mut reader = DeadlineIO::new(
             &mut self.data.stream,
             deadline,
-            self.data.socket.deref().as_ref(),
+            socket_timeouts(&self.data.socket),
         );
         let mut dechunker = Dechunker::new(&mut reader);
         let message_result: Result<BoltMessage<ValueReceive>> =
@@ -471,9 +535,18 @@ impl<RW: Read + Write> Bolt<RW> {
     pub(crate) fn is_older_than(&self, duration: Duration) -> bool {
         self.data.is_older_than(duration)
     }
+    pub(crate) fn lifetime_jitter(&self) -> Duration {
+        self.data.lifetime_jitter
+    }
+    pub(crate) fn set_lifetime_jitter(&mut self, jitter: Duration) {
+        self.data.lifetime_jitter = jitter
+    }
     pub(crate) fn is_idle_for(&self, timeout: Duration) -> bool {
         self.data.is_idle_for(timeout)
     }
+    pub(crate) fn handshake_rtt(&self) -> Duration {
+        self.data.handshake_rtt
+    }
     pub(crate) fn set_telemetry_enabled(&mut self, enabled: bool) {
         self.data.set_telemetry_enabled(enabled)
     }
@@ -482,6 +555,11 @@ impl<RW: Read + Write> Bolt<RW> {
         self.data.ssr_enabled()
     }
 
+    #[cfg(feature = "tracing")]
+    pub(crate) fn bolt_id(&self) -> Option<String> {
+        self.data.bolt_id()
+    }
+
     #[inline(always)]
     pub(crate) fn debug_log(&self, msg: impl FnOnce() -> String) {
         bolt_debug!(self.data, "{}", msg());
@@ -536,7 +614,7 @@ pub(crate) struct BoltData<RW: Read + Write> {
     message_buff: VecDeque<Vec<Vec<u8>>>,
     responses: VecDeque<BoltResponse>,
     stream: RW,
-    socket: Arc<Option<TcpStream>>,
+    socket: Arc<Option<RawSocket>>,
     local_port: Option<u16>,
     version: (u8, u8),
     protocol_version: ServerAwareBoltVersion,
@@ -553,6 +631,8 @@ pub(crate) struct BoltData<RW: Read + Write> {
     auth_reset: AuthResetHandle,
     created_at: Instant,
     idle_since: Instant,
+    handshake_rtt: Duration,
+    lifetime_jitter: Duration,
 }
 
 impl<RW: Read + Write> BoltData<RW> {
@@ -560,9 +640,10 @@ impl<RW: Read + Write> BoltData<RW> {
         version: (u8, u8),
         protocol_version: ServerAwareBoltVersion,
         stream: RW,
-        socket: Arc<Option<TcpStream>>,
+        socket: Arc<Option<RawSocket>>,
         local_port: Option<u16>,
         address: Arc<Address>,
+        handshake_rtt: Duration,
     ) -> Self {
         let now = Instant::now();
         Self {
@@ -586,6 +667,8 @@ impl<RW: Read + Write> BoltData<RW> {
             auth_reset: Default::default(),
             created_at: now,
             idle_since: now,
+            handshake_rtt,
+            lifetime_jitter: Duration::ZERO,
         }
     }
 
@@ -593,6 +676,14 @@ impl<RW: Read + Write> BoltData<RW> {
         &self.address
     }
 
+    #[cfg(feature = "tracing")]
+    fn bolt_id(&self) -> Option<String> {
+        match self.meta.try_borrow().ok()?.get("connection_id") {
+            Some(ValueReceive::String(id)) => Some(id.clone()),
+            _ => None,
+        }
+    }
+
     pub(crate) fn auth(&self) -> Option<&Arc<AuthToken>> {
         self.auth.as_ref()
     }
@@ -670,7 +761,7 @@ impl<RW: Read + Write> BoltData<RW> {
         if let Some(message_buff) = self.message_buff.pop_front() {
             let chunker = Chunker::new(&message_buff);
             let mut writer =
-                DeadlineIO::new(&mut self.stream, deadline, self.socket.deref().as_ref());
+                DeadlineIO::new(&mut self.stream, deadline, socket_timeouts(&self.socket));
             for chunk in chunker {
                 let res = Neo4jError::wrap_write(writer.write_all(&chunk));
                 let res = writer.rewrite_error(res);
@@ -684,7 +775,7 @@ impl<RW: Read + Write> BoltData<RW> {
     }
 
     fn flush(&mut self, deadline: Option<Instant>) -> Result<()> {
-        let mut writer = DeadlineIO::new(&mut self.stream, deadline, self.socket.deref().as_ref());
+        let mut writer = DeadlineIO::new(&mut self.stream, deadline, socket_timeouts(&self.socket));
         let res = Neo4jError::wrap_write(writer.flush());
         let res = writer.rewrite_error(res);
         if let Err(err) = &res {
@@ -16,7 +16,6 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::io::{Read, Write};
 use std::mem;
-use std::net::TcpStream;
 use std::ops::Deref;
 use std::sync::Arc;
 use std::time::Duration;
@@ -25,13 +24,14 @@ use atomic_refcell::AtomicRefCell;
 use log::warn;
 use usize_cast::FromUsize;
 
+use super::super::super::deadline::SocketTimeouts;
 use super::super::message_parameters::HelloParameters;
 use super::super::packstream::{
     PackStreamSerializer, PackStreamSerializerDebugImpl, PackStreamSerializerImpl,
 };
 use super::super::response::{BoltResponse, ResponseCallbacks, ResponseMessage};
 use super::super::{
-    BoltData, BoltMeta, BoltStructTranslator, debug_buf, debug_buf_end, debug_buf_start,
+    BoltData, BoltMeta, BoltStructTranslator, RawSocket, debug_buf, debug_buf_end, debug_buf_start,
 };
 use super::common::{check_no_notification_filter, write_auth_entries, write_str_entry};
 use crate::error_::Result;
@@ -176,7 +176,7 @@ impl HelloHandler5x0 {
 
     pub(super) fn hello_response_handle_connection_hints(
         meta: &BoltMeta,
-        socket: Option<&TcpStream>,
+        socket: Option<&RawSocket>,
     ) {
         let hints = Self::extract_connection_hints(meta);
         Self::hello_response_handle_timeout_hint(&hints, socket);
@@ -197,7 +197,7 @@ impl HelloHandler5x0 {
 
     pub(super) fn hello_response_handle_timeout_hint(
         hints: &HashMap<String, ValueReceive>,
-        socket: Option<&TcpStream>,
+        socket: Option<&RawSocket>,
     ) {
         if let Some(timeout) = hints.get(RECV_TIMEOUT_KEY) {
             match timeout {
@@ -15,7 +15,6 @@
 use std::collections::HashMap;
 use std::io::{Read, Write};
 use std::mem;
-use std::net::TcpStream;
 use std::ops::Deref;
 use std::sync::Arc;
 
@@ -26,7 +25,9 @@ use super::super::packstream::{
     PackStreamSerializer, PackStreamSerializerDebugImpl, PackStreamSerializerImpl,
 };
 use super::super::response::{BoltMeta, BoltResponse, ResponseCallbacks, ResponseMessage};
-use super::super::{BoltData, BoltStructTranslator, debug_buf, debug_buf_end, debug_buf_start};
+use super::super::{
+    BoltData, BoltStructTranslator, RawSocket, debug_buf, debug_buf_end, debug_buf_start,
+};
 use super::common::{notification_filter_entries_count, write_notification_filter_entries};
 use super::hello_5x0::HelloHandler5x0;
 use super::hello_5x3::HelloHandler5x3;
@@ -154,7 +155,7 @@ impl HelloHandler5x4 {
 
     pub(super) fn hello_response_handle_connection_hints(
         meta: &BoltMeta,
-        socket: Option<&TcpStream>,
+        socket: Option<&RawSocket>,
         telemetry_enabled: &mut bool,
     ) {
         let hints = HelloHandler5x0::extract_connection_hints(meta);
@@ -15,7 +15,6 @@
 use std::collections::HashMap;
 use std::io::{Read, Write};
 use std::mem;
-use std::net::TcpStream;
 use std::ops::Deref;
 use std::sync::Arc;
 
@@ -23,7 +22,7 @@ use log::warn;
 
 use super::super::message_parameters::HelloParameters;
 use super::super::response::{BoltMeta, BoltResponse, ResponseCallbacks, ResponseMessage};
-use super::super::{BoltData, BoltStructTranslator};
+use super::super::{BoltData, BoltStructTranslator, RawSocket};
 use super::hello_5x0::HelloHandler5x0;
 use super::hello_5x4::HelloHandler5x4;
 use crate::error_::Result;
@@ -69,7 +68,7 @@ impl HelloHandler5x8 {
 
     pub(super) fn hello_response_handle_connection_hints(
         meta: &BoltMeta,
-        socket: Option<&TcpStream>,
+        socket: Option<&RawSocket>,
         telemetry_enabled: &mut bool,
         ssr_enabled: &mut bool,
     ) {
@@ -15,8 +15,10 @@
 use std::fmt::{Debug, Display};
 use std::io::{self, Read, Write};
 use std::net::{Shutdown, SocketAddr, TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant as StdInstant};
 
 use itertools::Itertools;
 use log::Level::Trace;
@@ -26,14 +28,17 @@ use socket2::{Socket as Socket2, TcpKeepalive};
 
 use super::super::deadline::DeadlineIO;
 use super::super::varint::{ReadVarIntError, read_var_int, write_var_int};
-pub(crate) use super::socket::{BufTcpStream, Socket};
+pub(crate) use super::socket::{BufTcpStream, RawSocket, SharedTransport, Socket};
 use super::{Bolt, dbg_extra, socket_debug};
 use crate::address_::Address;
 use crate::driver::config::KeepAliveConfig;
+use crate::driver::config::connector::Connector;
+#[cfg(unix)]
+use crate::driver::config::connector::connect_unix_socket;
 use crate::error_::{Neo4jError, Result};
 use crate::time::Instant;
 
-const BOLT_MAGIC_PREAMBLE: [u8; 4] = [0x60, 0x60, 0xB0, 0x17];
+pub(super) const BOLT_MAGIC_PREAMBLE: [u8; 4] = [0x60, 0x60, 0xB0, 0x17];
 // [bolt-version-bump] search tag when changing bolt version support
 const BOLT_VERSION_OFFER: [u8; 16] = [
     0, 0, 1, 255, // BOLT handshake manifest v1
@@ -58,12 +63,29 @@ impl AddressProvider for Address {
 }
 
 pub(crate) trait SocketProvider {
-    type RW: Read + Write + Debug;
+    type RW: Debug;
     type BuffRW: Read + Write + Debug;
 
     fn connect(&mut self, addr: &Arc<impl AddressProvider>) -> io::Result<Self::RW>;
     fn get_local_port(&mut self, sock: &Self::RW) -> u16;
     fn connect_timeout(&mut self, addr: &SocketAddr, timeout: Duration) -> io::Result<Self::RW>;
+    /// Try all socket addresses `addr` resolves to.
+    /// `next_timeout` is queried for the timeout to use after each failed attempt.
+    fn connect_each_timeout(
+        &mut self,
+        addr: &Arc<impl AddressProvider>,
+        timeout: Duration,
+        mut next_timeout: impl FnMut() -> Duration,
+    ) -> io::Result<Self::RW> {
+        let mut timeout = timeout;
+        each_addr(&**addr, |addr| match self.connect_timeout(addr?, timeout) {
+            Ok(connection) => Ok(connection),
+            Err(e) => {
+                timeout = next_timeout();
+                Err(e)
+            }
+        })
+    }
     fn set_tcp_keepalive(
         &mut self,
         socket: Self::RW,
@@ -77,7 +99,7 @@ pub(crate) trait SocketProvider {
         sock: &'rw Self::RW,
         deadline: Option<Instant>,
     ) -> DeadlineIO<'rw, S>;
-    fn new_socket(&mut self, _sock: Self::RW) -> Option<TcpStream> {
+    fn new_socket(&mut self, _sock: Self::RW) -> Option<RawSocket> {
         None
     }
 }
@@ -155,8 +177,167 @@ impl SocketProvider for TcpConnector {
     }
 
     #[inline]
-    fn new_socket(&mut self, sock: Self::RW) -> Option<TcpStream> {
-        Some(sock)
+    fn new_socket(&mut self, sock: Self::RW) -> Option<RawSocket> {
+        Some(RawSocket::Tcp(sock))
+    }
+}
+
+/// Runs Bolt over Unix domain sockets (see [`Address::unix_socket()`]).
+///
+/// Unix domain sockets have no connection timeout and no TCP keep alive.
+/// Deadlines are enforced just like for TCP sockets.
+#[cfg(unix)]
+#[derive(Debug)]
+pub(crate) struct UnixConnector;
+
+#[cfg(unix)]
+impl SocketProvider for UnixConnector {
+    type RW = UnixStream;
+    type BuffRW = BufTcpStream;
+
+    #[inline]
+    fn connect(&mut self, addr: &Arc<impl AddressProvider>) -> io::Result<Self::RW> {
+        connect_unix_socket(&Arc::clone(addr).into_address())
+    }
+
+    #[inline]
+    fn get_local_port(&mut self, _sock: &Self::RW) -> u16 {
+        0
+    }
+
+    #[inline]
+    fn connect_timeout(&mut self, addr: &SocketAddr, _timeout: Duration) -> io::Result<Self::RW> {
+        connect_unix_socket(&Address::from(*addr))
+    }
+
+    #[inline]
+    fn connect_each_timeout(
+        &mut self,
+        addr: &Arc<impl AddressProvider>,
+        _timeout: Duration,
+        _next_timeout: impl FnMut() -> Duration,
+    ) -> io::Result<Self::RW> {
+        self.connect(addr)
+    }
+
+    #[inline]
+    fn set_tcp_keepalive(
+        &mut self,
+        socket: Self::RW,
+        _keepalive: Option<KeepAliveConfig>,
+    ) -> io::Result<Self::RW> {
+        Ok(socket)
+    }
+
+    #[inline]
+    fn shutdown(&mut self, sock: &Self::RW, how: Shutdown) -> io::Result<()> {
+        sock.shutdown(how)
+    }
+
+    #[inline]
+    fn new_buffered(&mut self, sock: &Self::RW) -> Result<Self::BuffRW> {
+        let stream = Neo4jError::wrap_connect(sock.try_clone())?;
+        Ok(BufTcpStream::new_custom(SharedTransport::new(Box::new(
+            stream,
+        ))))
+    }
+
+    #[inline]
+    fn new_deadline_io<'rw, S: Read + Write>(
+        &'_ mut self,
+        stream: S,
+        sock: &'rw Self::RW,
+        deadline: Option<Instant>,
+    ) -> DeadlineIO<'rw, S> {
+        DeadlineIO::new(stream, deadline, Some(sock))
+    }
+
+    #[inline]
+    fn new_socket(&mut self, sock: Self::RW) -> Option<RawSocket> {
+        Some(RawSocket::Unix(sock))
+    }
+}
+
+/// Runs Bolt over the [`Transport`]s of a user-provided [`Connector`].
+///
+/// The raw socket is shared with the buffered stream to apply deadlines through the transport's
+/// timeout hooks.
+/// As there's no [`TcpStream`] backing the transport, shutting down is a no-op.
+#[derive(Debug)]
+pub(crate) struct CustomConnector<'a>(pub(crate) &'a dyn Connector);
+
+impl CustomConnector<'_> {
+    fn connect_address(
+        &self,
+        address: &Address,
+        timeout: Option<Duration>,
+    ) -> io::Result<SharedTransport> {
+        let transport = self.0.connect(address, timeout)?;
+        Ok(SharedTransport::new(transport))
+    }
+}
+
+impl SocketProvider for CustomConnector<'_> {
+    type RW = SharedTransport;
+    type BuffRW = BufTcpStream;
+
+    #[inline]
+    fn connect(&mut self, addr: &Arc<impl AddressProvider>) -> io::Result<Self::RW> {
+        self.connect_address(&Arc::clone(addr).into_address(), None)
+    }
+
+    #[inline]
+    fn get_local_port(&mut self, _sock: &Self::RW) -> u16 {
+        0
+    }
+
+    #[inline]
+    fn connect_timeout(&mut self, addr: &SocketAddr, timeout: Duration) -> io::Result<Self::RW> {
+        self.connect_address(&Address::from(*addr), Some(timeout))
+    }
+
+    #[inline]
+    fn connect_each_timeout(
+        &mut self,
+        addr: &Arc<impl AddressProvider>,
+        timeout: Duration,
+        _next_timeout: impl FnMut() -> Duration,
+    ) -> io::Result<Self::RW> {
+        self.connect_address(&Arc::clone(addr).into_address(), Some(timeout))
+    }
+
+    #[inline]
+    fn set_tcp_keepalive(
+        &mut self,
+        socket: Self::RW,
+        _keepalive: Option<KeepAliveConfig>,
+    ) -> io::Result<Self::RW> {
+        Ok(socket)
+    }
+
+    #[inline]
+    fn shutdown(&mut self, _sock: &Self::RW, _how: Shutdown) -> io::Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn new_buffered(&mut self, sock: &Self::RW) -> Result<Self::BuffRW> {
+        Ok(BufTcpStream::new_custom(sock.clone()))
+    }
+
+    #[inline]
+    fn new_deadline_io<'rw, S: Read + Write>(
+        &'_ mut self,
+        stream: S,
+        sock: &'rw Self::RW,
+        deadline: Option<Instant>,
+    ) -> DeadlineIO<'rw, S> {
+        DeadlineIO::new(stream, deadline, Some(sock))
+    }
+
+    #[inline]
+    fn new_socket(&mut self, sock: Self::RW) -> Option<RawSocket> {
+        Some(RawSocket::Custom(sock))
     }
 }
 
@@ -177,19 +358,9 @@ pub(crate) fn open<S: SocketProvider>(
     let timeout = combine_connection_timout(connect_timeout, deadline);
     let raw_socket = Neo4jError::wrap_connect(match timeout {
         None => socket_provider.connect(&address),
-        Some(timeout) => {
-            let mut timeout = timeout;
-            each_addr(&*address, |addr| {
-                match socket_provider.connect_timeout(addr?, timeout) {
-                    Ok(connection) => Ok(connection),
-                    Err(e) => {
-                        timeout = combine_connection_timout(connect_timeout, deadline)
-                            .expect("timeout cannot disappear");
-                        Err(e)
-                    }
-                }
-            })
-        }
+        Some(timeout) => socket_provider.connect_each_timeout(&address, timeout, || {
+            combine_connection_timout(connect_timeout, deadline).expect("timeout cannot disappear")
+        }),
     })?;
     let raw_socket = socket_provider
         .set_tcp_keepalive(raw_socket, keep_alive)
@@ -202,15 +373,43 @@ pub(crate) fn open<S: SocketProvider>(
     let mut socket = Socket::new(buffered_socket, address.unresolved_host(), tls_config)?;
 
     let mut deadline_io = socket_provider.new_deadline_io(&mut socket, &raw_socket, deadline);
+    let res = handshake(
+        &mut socket_provider,
+        &mut deadline_io,
+        &raw_socket,
+        local_port,
+    );
+    // failing to apply the deadline (e.g., a custom transport not supporting timeouts) is a
+    // configuration error, not a connectivity issue
+    let (version, handshake_rtt) = deadline_io.rewrite_error(res)?;
+
+    Ok(Bolt::new(
+        version,
+        socket,
+        Arc::new(socket_provider.new_socket(raw_socket)),
+        Some(local_port),
+        address.into_address(),
+        handshake_rtt,
+    ))
+}
 
+/// Negotiate the Bolt version.
+///
+/// Returns the version and the round trip time it took.
+fn handshake<S: SocketProvider>(
+    socket_provider: &mut S,
+    deadline_io: &mut DeadlineIO<impl Read + Write>,
+    raw_socket: &S::RW,
+    local_port: u16,
+) -> Result<((u8, u8), Duration)> {
     socket_debug!(
         local_port,
         "C: <HANDSHAKE> {:#010X?}",
         u32::from_be_bytes(BOLT_MAGIC_PREAMBLE)
     );
     wrap_socket_write(
-        &mut socket_provider,
-        &raw_socket,
+        socket_provider,
+        raw_socket,
         local_port,
         deadline_io.write_all(&BOLT_MAGIC_PREAMBLE),
     )?;
@@ -228,35 +427,29 @@ pub(crate) fn open<S: SocketProvider>(
             .join(" ")
     );
     wrap_socket_write(
-        &mut socket_provider,
-        &raw_socket,
+        socket_provider,
+        raw_socket,
         local_port,
         deadline_io.write_all(&BOLT_VERSION_OFFER),
     )?;
-    wrap_socket_write(
-        &mut socket_provider,
-        &raw_socket,
-        local_port,
-        deadline_io.flush(),
-    )?;
+    wrap_socket_write(socket_provider, raw_socket, local_port, deadline_io.flush())?;
 
+    // Everything is flushed (incl. the TLS handshake, if any), so awaiting the server's answer
+    // takes about one network round trip.
+    let rtt_start = StdInstant::now();
     let mut negotiated_version = [0u8; 4];
     wrap_socket_read(
-        &mut socket_provider,
-        &raw_socket,
+        socket_provider,
+        raw_socket,
         local_port,
         deadline_io.read_exact(&mut negotiated_version),
     )?;
+    let handshake_rtt = rtt_start.elapsed();
 
     let version = match negotiated_version {
         [_, _, 1, 255] => {
             // BOLT handshake manifest v1
-            handshake_manifest_v1(
-                &mut socket_provider,
-                &mut deadline_io,
-                &raw_socket,
-                local_port,
-            )?
+            handshake_manifest_v1(socket_provider, &mut *deadline_io, raw_socket, local_port)?
         }
         _ => {
             socket_debug!(
@@ -265,21 +458,14 @@ pub(crate) fn open<S: SocketProvider>(
                 u32::from_be_bytes(negotiated_version)
             );
             wrap_socket_killing(
-                &mut socket_provider,
-                &raw_socket,
+                socket_provider,
+                raw_socket,
                 local_port,
                 decode_version_offer(&negotiated_version),
             )?
         }
     };
-
-    Ok(Bolt::new(
-        version,
-        socket,
-        Arc::new(socket_provider.new_socket(raw_socket)),
-        Some(local_port),
-        address.into_address(),
-    ))
+    Ok((version, handshake_rtt))
 }
 
 fn handshake_manifest_v1<S: SocketProvider>(
@@ -536,9 +722,11 @@ mod tests {
     use std::str::FromStr;
     use std::vec;
 
+    use parking_lot::Mutex;
     use rstest::*;
 
     use super::*;
+    use crate::driver::config::connector::Transport;
 
     // [bolt-version-bump] search tag when changing bolt version support
     #[rstest]
@@ -947,4 +1135,166 @@ mod tests {
         assert_eq!(connect_address, sock_addr2);
         assert!(dbg!(connect_timeout2) < connect_timeout1);
     }
+
+    #[derive(Debug)]
+    struct InMemoryTransport {
+        received_data: Arc<Mutex<Vec<u8>>>,
+        response: VecDeque<u8>,
+        timeouts: Option<Arc<Mutex<Vec<Option<Duration>>>>>,
+    }
+
+    impl Read for InMemoryTransport {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.response.read(buf)
+        }
+    }
+
+    impl Write for InMemoryTransport {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.received_data.lock().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Transport for InMemoryTransport {
+        fn set_read_timeout(&mut self, timeout: Option<Duration>) -> io::Result<()> {
+            let Some(timeouts) = &self.timeouts else {
+                return Err(io::Error::from(io::ErrorKind::Unsupported));
+            };
+            timeouts.lock().push(timeout);
+            Ok(())
+        }
+
+        fn set_write_timeout(&mut self, timeout: Option<Duration>) -> io::Result<()> {
+            self.set_read_timeout(timeout)
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct InMemoryConnector {
+        received_data: Arc<Mutex<Vec<u8>>>,
+        calls: Mutex<Vec<(Address, Option<Duration>)>>,
+        // `None` => the transport doesn't support timeouts
+        timeouts: Option<Arc<Mutex<Vec<Option<Duration>>>>>,
+    }
+
+    impl Connector for InMemoryConnector {
+        fn connect(
+            &self,
+            address: &Address,
+            timeout: Option<Duration>,
+        ) -> io::Result<Box<dyn Transport>> {
+            self.calls.lock().push((address.clone(), timeout));
+            Ok(Box::new(InMemoryTransport {
+                received_data: Arc::clone(&self.received_data),
+                response: [0, 0, 0, 5].into_iter().collect(),
+                timeouts: self.timeouts.clone(),
+            }))
+        }
+    }
+
+    #[rstest]
+    fn test_open_custom_connector(#[values(false, true)] with_deadline: bool) {
+        let connector = InMemoryConnector {
+            timeouts: Some(Default::default()),
+            ..Default::default()
+        };
+        // not resolvable => must be handed to the connector as is
+        let address = Arc::new(Address::from(("neo4j.invalid", 7687)));
+        let deadline = with_deadline.then(|| Instant::now() + Duration::from_secs(1000));
+
+        let bolt = open(
+            CustomConnector(&connector),
+            Arc::clone(&address),
+            deadline,
+            Some(Duration::from_secs(5)),
+            Some(KeepAliveConfig::Default),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(bolt.protocol_version(), (5, 0));
+        let calls = connector.calls.lock();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].0.unresolved_host(), "neo4j.invalid");
+        assert_eq!(calls[0].0.port(), 7687);
+        assert_eq!(calls[0].1.is_some(), with_deadline);
+        let mut expected_data = BOLT_MAGIC_PREAMBLE.to_vec();
+        expected_data.extend(&BOLT_VERSION_OFFER);
+        assert_eq!(*connector.received_data.lock(), expected_data);
+        let timeouts = connector.timeouts.as_ref().unwrap().lock();
+        // each deadline IO sets the timeouts and restores them afterward
+        assert_eq!(!timeouts.is_empty(), with_deadline);
+        assert!(timeouts.iter().any(Option::is_some) == with_deadline);
+        assert_eq!(timeouts.last().copied().flatten(), None);
+    }
+
+    #[test]
+    fn test_open_custom_connector_without_timeout_support() {
+        let connector = InMemoryConnector::default();
+        let address = Arc::new(Address::from(("localhost", 7687)));
+
+        open(
+            CustomConnector(&connector),
+            Arc::clone(&address),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(1000);
+        let res = open(
+            CustomConnector(&connector),
+            address,
+            Some(deadline),
+            None,
+            None,
+            None,
+        );
+        let Err(Neo4jError::InvalidConfig { message }) = res else {
+            panic!("Expected InvalidConfig error, got {res:?}");
+        };
+        assert!(message.contains("failed to configure timeout"), "{message}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_open_unix_socket_respects_deadline() {
+        use std::os::unix::net::UnixListener;
+        use std::thread;
+
+        let path = std::env::temp_dir().join(format!(
+            "neo4j-rust-driver-test-deadline-{}.sock",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        // accept the connection but never answer the handshake
+        let server = thread::spawn(move || listener.accept().unwrap());
+
+        let address = Arc::new(Address::unix_socket(&path));
+        let deadline = Instant::now() + Duration::from_millis(100);
+        let res = open(UnixConnector, address, Some(deadline), None, None, None);
+
+        let Err(Neo4jError::Disconnect {
+            source: Some(err), ..
+        }) = res
+        else {
+            panic!("Expected Disconnect error, got {res:?}");
+        };
+        assert!(
+            matches!(
+                err.kind(),
+                io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+            ),
+            "Expected socket timeout, got {err:?}"
+        );
+        drop(server.join().unwrap());
+        let _ = std::fs::remove_file(&path);
+    }
 }
@@ -60,6 +60,8 @@ pub(crate) struct ResponseCallbacks {
     on_ignored_cb: OptBox<dyn FnMut() -> Result<()> + Send + Sync>,
     on_record_cb: OptBox<dyn FnMut(BoltRecordFields) -> Result<()> + Send + Sync>,
     on_summary_cb: OptBox<dyn FnMut() + Send + Sync>,
+    #[cfg(feature = "tracing")]
+    span: Option<ResponseSpan>,
 }
 
 impl ResponseCallbacks {
@@ -70,6 +72,8 @@ impl ResponseCallbacks {
             on_ignored_cb: None,
             on_record_cb: None,
             on_summary_cb: None,
+            #[cfg(feature = "tracing")]
+            span: None,
         }
         .with_on_failure(|error| {
             Err(Neo4jError::ServerError {
@@ -130,12 +134,25 @@ impl ResponseCallbacks {
         self
     }
 
+    /// Attach a span that is closed once the response to the message has been received.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn with_span(mut self, span: tracing::Span) -> Self {
+        self.span = Some(ResponseSpan { span, records: 0 });
+        self
+    }
+
     pub(crate) fn on_success(&mut self, meta: ValueReceive) -> Result<()> {
         let res = match meta {
-            ValueReceive::Map(meta) => match self.on_success_cb.as_mut() {
-                None => Ok(()),
-                Some(cb) => cb(meta),
-            },
+            ValueReceive::Map(meta) => {
+                #[cfg(feature = "tracing")]
+                if let Some(span) = self.span.take() {
+                    span.on_success(&meta);
+                }
+                match self.on_success_cb.as_mut() {
+                    None => Ok(()),
+                    Some(cb) => cb(meta),
+                }
+            }
             _ => Err(Neo4jError::protocol_error(
                 "onSuccess meta was not a Dictionary",
             )),
@@ -145,6 +162,10 @@ impl ResponseCallbacks {
     }
 
     pub(crate) fn on_failure(&mut self, error: ServerError) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        if let Some(span) = self.span.take() {
+            span.on_failure(&error);
+        }
         let res = match self.on_failure_cb.as_mut() {
             None => Ok(()),
             Some(cb) => cb(error),
@@ -154,12 +175,20 @@ impl ResponseCallbacks {
     }
 
     pub(crate) fn on_ignored(&mut self) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        if let Some(span) = self.span.take() {
+            span.on_ignored();
+        }
         let res = self.on_ignored_cb.as_mut().map(|cb| cb()).unwrap_or(Ok(()));
         self.on_summary();
         res
     }
 
     pub(crate) fn on_record(&mut self, data: ValueReceive) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        if let Some(span) = self.span.as_mut() {
+            span.records += 1;
+        }
         match data {
             ValueReceive::List(values) => match self.on_record_cb.as_mut() {
                 None => Ok(()),
@@ -187,3 +216,135 @@ impl Debug for ResponseCallbacks {
             .finish()
     }
 }
+
+#[cfg(feature = "tracing")]
+#[derive(Debug)]
+struct ResponseSpan {
+    span: tracing::Span,
+    records: u64,
+}
+
+#[cfg(feature = "tracing")]
+impl ResponseSpan {
+    fn on_success(self, meta: &BoltMeta) {
+        self.span.record("neo4j.records", self.records);
+        if let Some(ValueReceive::String(query_type)) = meta.get("type") {
+            self.span.record("neo4j.query_type", query_type.as_str());
+        }
+        if let Some(ValueReceive::Boolean(has_more)) = meta.get("has_more") {
+            self.span.record("neo4j.has_more", has_more);
+        }
+    }
+
+    fn on_failure(self, error: &ServerError) {
+        self.span.record("neo4j.records", self.records);
+        self.span.record("error.type", error.code());
+    }
+
+    fn on_ignored(self) {
+        self.span.record("error.type", "ignored");
+    }
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use parking_lot::Mutex;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct RecordingSubscriber {
+        next_id: AtomicU64,
+        fields: Arc<Mutex<HashMap<String, String>>>,
+        closed: Arc<Mutex<bool>>,
+    }
+
+    impl Visit for RecordingSubscriber {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.fields
+                .lock()
+                .insert(field.name().into(), format!("{value:?}"));
+        }
+    }
+
+    impl Subscriber for RecordingSubscriber {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            span.record(&mut self.visitor());
+            Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+
+        fn record(&self, _: &Id, values: &Record<'_>) {
+            values.record(&mut self.visitor());
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+
+        fn try_close(&self, _: Id) -> bool {
+            *self.closed.lock() = true;
+            true
+        }
+    }
+
+    impl RecordingSubscriber {
+        fn visitor(&self) -> Self {
+            Self {
+                next_id: AtomicU64::new(0),
+                fields: Arc::clone(&self.fields),
+                closed: Arc::clone(&self.closed),
+            }
+        }
+    }
+
+    #[test]
+    fn test_span_records_pull_result() {
+        let subscriber = RecordingSubscriber::default();
+        let fields = Arc::clone(&subscriber.fields);
+        let closed = Arc::clone(&subscriber.closed);
+        tracing::subscriber::with_default(subscriber, || {
+            let mut callbacks = ResponseCallbacks::new().with_span(tracing::info_span!(
+                "neo4j.pull",
+                neo4j.records = tracing::field::Empty,
+                neo4j.query_type = tracing::field::Empty,
+                neo4j.has_more = tracing::field::Empty,
+            ));
+            callbacks.on_record(ValueReceive::List(vec![])).unwrap();
+            callbacks.on_record(ValueReceive::List(vec![])).unwrap();
+            assert!(!*closed.lock());
+            let meta = HashMap::from([
+                (
+                    String::from("type"),
+                    ValueReceive::String(String::from("r")),
+                ),
+                (String::from("has_more"), ValueReceive::Boolean(false)),
+            ]);
+            callbacks.on_success(ValueReceive::Map(meta)).unwrap();
+        });
+        assert!(*closed.lock());
+        let fields = fields.lock();
+        assert_eq!(fields.get("neo4j.records").map(String::as_str), Some("2"));
+        assert_eq!(
+            fields.get("neo4j.query_type").map(String::as_str),
+            Some("\"r\"")
+        );
+        assert_eq!(
+            fields.get("neo4j.has_more").map(String::as_str),
+            Some("false")
+        );
+    }
+}
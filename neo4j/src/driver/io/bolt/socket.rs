@@ -13,14 +13,159 @@
 // limitations under the License.
 
 use std::io::{BufReader, BufWriter, IoSlice, IoSliceMut, Read, Result as IoResult, Write};
-use std::net::TcpStream;
+use std::net::{Shutdown, TcpStream};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
 use std::sync::Arc;
+use std::time::Duration;
 
+use parking_lot::Mutex;
 use rustls::{ClientConfig, ClientConnection, StreamOwned};
 use rustls_pki_types::ServerName;
 
+use super::super::deadline::SocketTimeouts;
+use crate::driver::config::connector::Transport;
 use crate::error_::{Neo4jError, Result};
 
+/// The OS socket (or custom transport) backing a connection.
+///
+/// Used to shut down broken connections and to apply timeouts.
+#[derive(Debug)]
+pub(crate) enum RawSocket {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+    Custom(SharedTransport),
+}
+
+impl RawSocket {
+    pub(crate) fn shutdown(&self, how: Shutdown) -> IoResult<()> {
+        match self {
+            RawSocket::Tcp(socket) => socket.shutdown(how),
+            #[cfg(unix)]
+            RawSocket::Unix(socket) => socket.shutdown(how),
+            // transports have no notion of shutting down
+            RawSocket::Custom(_) => Ok(()),
+        }
+    }
+
+    fn timeouts(&self) -> &dyn SocketTimeouts {
+        match self {
+            RawSocket::Tcp(socket) => socket,
+            #[cfg(unix)]
+            RawSocket::Unix(socket) => socket,
+            RawSocket::Custom(transport) => transport,
+        }
+    }
+}
+
+/// A [`Transport`] shared between the connection's stream and its [`RawSocket`].
+///
+/// Unlike [`TcpStream`]s, transports can't be cloned to apply timeouts while the stream is
+/// borrowed for reading or writing.
+/// As transports don't expose their timeouts, the ones last set through this handle are tracked.
+#[derive(Debug, Clone)]
+pub(crate) struct SharedTransport(Arc<Mutex<SharedTransportInner>>);
+
+#[derive(Debug)]
+struct SharedTransportInner {
+    transport: Box<dyn Transport>,
+    read_timeout: Option<Duration>,
+    write_timeout: Option<Duration>,
+}
+
+impl SharedTransport {
+    pub(crate) fn new(transport: Box<dyn Transport>) -> Self {
+        Self(Arc::new(Mutex::new(SharedTransportInner {
+            transport,
+            read_timeout: None,
+            write_timeout: None,
+        })))
+    }
+}
+
+impl SocketTimeouts for SharedTransport {
+    #[inline]
+    fn read_timeout(&self) -> IoResult<Option<Duration>> {
+        Ok(self.0.lock().read_timeout)
+    }
+
+    #[inline]
+    fn write_timeout(&self) -> IoResult<Option<Duration>> {
+        Ok(self.0.lock().write_timeout)
+    }
+
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> IoResult<()> {
+        let mut inner = self.0.lock();
+        inner.transport.set_read_timeout(timeout)?;
+        inner.read_timeout = timeout;
+        Ok(())
+    }
+
+    fn set_write_timeout(&self, timeout: Option<Duration>) -> IoResult<()> {
+        let mut inner = self.0.lock();
+        inner.transport.set_write_timeout(timeout)?;
+        inner.write_timeout = timeout;
+        Ok(())
+    }
+}
+
+impl Read for SharedTransport {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        self.0.lock().transport.read(buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> IoResult<usize> {
+        self.0.lock().transport.read_vectored(bufs)
+    }
+}
+
+impl Write for SharedTransport {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        self.0.lock().transport.write(buf)
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> IoResult<usize> {
+        self.0.lock().transport.write_vectored(bufs)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> IoResult<()> {
+        self.0.lock().transport.flush()
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> IoResult<()> {
+        self.0.lock().transport.write_all(buf)
+    }
+}
+
+impl SocketTimeouts for RawSocket {
+    #[inline]
+    fn read_timeout(&self) -> IoResult<Option<Duration>> {
+        self.timeouts().read_timeout()
+    }
+
+    #[inline]
+    fn write_timeout(&self) -> IoResult<Option<Duration>> {
+        self.timeouts().write_timeout()
+    }
+
+    #[inline]
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> IoResult<()> {
+        self.timeouts().set_read_timeout(timeout)
+    }
+
+    #[inline]
+    fn set_write_timeout(&self, timeout: Option<Duration>) -> IoResult<()> {
+        self.timeouts().set_write_timeout(timeout)
+    }
+}
+
 #[derive(Debug)]
 pub(crate) enum BufTcpStream {
     Buffered {
@@ -28,6 +173,7 @@ pub(crate) enum BufTcpStream {
         write: BufWriter<TcpStream>,
     },
     Unbuffered(TcpStream),
+    Custom(BufReader<WriteBuffered>),
 }
 
 impl BufTcpStream {
@@ -44,6 +190,51 @@ impl BufTcpStream {
             }
         }
     }
+
+    pub(super) fn new_custom(transport: SharedTransport) -> Self {
+        Self::Custom(BufReader::new(WriteBuffered(BufWriter::new(transport))))
+    }
+}
+
+/// Buffers writes to a [`Transport`] while passing reads straight through.
+///
+/// Custom transports can't be cloned into a read and a write half like [`TcpStream`]s.
+/// Instead, this is wrapped in a [`BufReader`] to buffer in both directions.
+#[derive(Debug)]
+pub(crate) struct WriteBuffered(BufWriter<SharedTransport>);
+
+impl Read for WriteBuffered {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        self.0.get_mut().read(buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> IoResult<usize> {
+        self.0.get_mut().read_vectored(bufs)
+    }
+}
+
+impl Write for WriteBuffered {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        self.0.write(buf)
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> IoResult<usize> {
+        self.0.write_vectored(bufs)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> IoResult<()> {
+        self.0.flush()
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> IoResult<()> {
+        self.0.write_all(buf)
+    }
 }
 
 impl Read for BufTcpStream {
@@ -52,6 +243,7 @@ impl Read for BufTcpStream {
         match self {
             BufTcpStream::Buffered { read, .. } => read.read(buf),
             BufTcpStream::Unbuffered(read) => read.read(buf),
+            BufTcpStream::Custom(stream) => stream.read(buf),
         }
     }
 
@@ -60,6 +252,7 @@ impl Read for BufTcpStream {
         match self {
             BufTcpStream::Buffered { read, .. } => read.read_vectored(bufs),
             BufTcpStream::Unbuffered(read) => read.read_vectored(bufs),
+            BufTcpStream::Custom(stream) => stream.read_vectored(bufs),
         }
     }
 
@@ -68,6 +261,7 @@ impl Read for BufTcpStream {
         match self {
             BufTcpStream::Buffered { read, .. } => read.read_to_end(buf),
             BufTcpStream::Unbuffered(read) => read.read_to_end(buf),
+            BufTcpStream::Custom(stream) => stream.read_to_end(buf),
         }
     }
 
@@ -76,6 +270,7 @@ impl Read for BufTcpStream {
         match self {
             BufTcpStream::Buffered { read, .. } => read.read_to_string(buf),
             BufTcpStream::Unbuffered(read) => read.read_to_string(buf),
+            BufTcpStream::Custom(stream) => stream.read_to_string(buf),
         }
     }
 
@@ -84,6 +279,7 @@ impl Read for BufTcpStream {
         match self {
             BufTcpStream::Buffered { read, .. } => read.read_exact(buf),
             BufTcpStream::Unbuffered(read) => read.read_exact(buf),
+            BufTcpStream::Custom(stream) => stream.read_exact(buf),
         }
     }
 }
@@ -94,6 +290,7 @@ impl Write for BufTcpStream {
         match self {
             BufTcpStream::Buffered { write, .. } => write.write(buf),
             BufTcpStream::Unbuffered(write) => write.write(buf),
+            BufTcpStream::Custom(stream) => stream.get_mut().write(buf),
         }
     }
 
@@ -102,6 +299,7 @@ impl Write for BufTcpStream {
         match self {
             BufTcpStream::Buffered { write, .. } => write.write_vectored(bufs),
             BufTcpStream::Unbuffered(write) => write.write_vectored(bufs),
+            BufTcpStream::Custom(stream) => stream.get_mut().write_vectored(bufs),
         }
     }
 
@@ -110,6 +308,7 @@ impl Write for BufTcpStream {
         match self {
             BufTcpStream::Buffered { write, .. } => write.flush(),
             BufTcpStream::Unbuffered(write) => write.flush(),
+            BufTcpStream::Custom(stream) => stream.get_mut().flush(),
         }
     }
 
@@ -118,6 +317,7 @@ impl Write for BufTcpStream {
         match self {
             BufTcpStream::Buffered { write, .. } => write.write_all(buf),
             BufTcpStream::Unbuffered(write) => write.write_all(buf),
+            BufTcpStream::Custom(stream) => stream.get_mut().write_all(buf),
         }
     }
 }
@@ -0,0 +1,172 @@
+// Copyright Rouven Bauer
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The server side of a Bolt connection as used by [`crate::testing`].
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::result;
+
+use super::BoltStructTranslator;
+use super::bolt_common::ServerAwareBoltVersion;
+use super::bolt4x4::Bolt4x4StructTranslator;
+use super::bolt5x0::Bolt5x0StructTranslator;
+use super::bolt6x0::Bolt6x0StructTranslator;
+use super::chunk::{Chunker, Dechunker};
+use super::handshake::BOLT_MAGIC_PREAMBLE;
+use super::message::BoltMessage;
+use super::packstream::{
+    PackStreamDeserializer, PackStreamDeserializerImpl, PackStreamSerializer,
+    PackStreamSerializerImpl,
+};
+use crate::error_::{Neo4jError, Result};
+use crate::value::{ValueReceive, ValueSend};
+
+pub(crate) fn is_supported_version(major: u8, minor: u8) -> bool {
+    ServerAwareBoltVersion::parse(major, minor).is_some()
+}
+
+#[derive(Debug)]
+pub(crate) struct StubConnection<S: Read + Write> {
+    reader: BufReader<S>,
+    translator: StubTranslator,
+}
+
+impl<S: Read + Write> StubConnection<S> {
+    /// Perform the server side of the Bolt handshake, agreeing on `version`.
+    pub(crate) fn accept(stream: S, version: (u8, u8)) -> Result<Self> {
+        let protocol_version =
+            ServerAwareBoltVersion::parse(version.0, version.1).ok_or_else(|| {
+                Neo4jError::InvalidConfig {
+                    message: format!("unsupported bolt version {}.{}", version.0, version.1),
+                }
+            })?;
+        let mut reader = BufReader::new(stream);
+        let mut preamble = [0; 4];
+        Neo4jError::wrap_read(reader.read_exact(&mut preamble))?;
+        if preamble != BOLT_MAGIC_PREAMBLE {
+            return Err(Neo4jError::protocol_error(format!(
+                "expected bolt magic preamble, received {preamble:02X?}"
+            )));
+        }
+        let mut offers = [0; 16];
+        Neo4jError::wrap_read(reader.read_exact(&mut offers))?;
+        let offered = offers.chunks_exact(4).any(|offer| {
+            let [_, range, minor, major] = *offer else {
+                unreachable!("chunks_exact(4) yields chunks of 4 bytes")
+            };
+            major == version.0 && (minor.saturating_sub(range)..=minor).contains(&version.1)
+        });
+        if !offered {
+            let _ = reader.get_mut().write_all(&[0, 0, 0, 0]);
+            return Err(Neo4jError::protocol_error(format!(
+                "client didn't offer bolt version {}.{}, offered {offers:02X?}",
+                version.0, version.1
+            )));
+        }
+        Neo4jError::wrap_write(reader.get_mut().write_all(&[0, 0, version.1, version.0]))?;
+        Neo4jError::wrap_write(reader.get_mut().flush())?;
+        Ok(Self {
+            reader,
+            translator: StubTranslator::new(protocol_version),
+        })
+    }
+
+    /// Read the next message sent by the client.
+    ///
+    /// Returns the message's tag and fields or `None` if the client closed the connection in
+    /// between messages.
+    pub(crate) fn read_message(&mut self) -> Result<Option<(u8, Vec<ValueReceive>)>> {
+        // The dechunker doesn't consume the end marker (empty chunk) of the previous message.
+        // Skip it to tell whether the client closed the connection.
+        loop {
+            let buf = Neo4jError::wrap_read(self.reader.fill_buf())?;
+            if buf.is_empty() {
+                return Ok(None);
+            }
+            if !buf.starts_with(&[0, 0]) {
+                break;
+            }
+            self.reader.consume(2);
+        }
+        let translator = &self.translator;
+        let mut dechunker = Dechunker::new(&mut self.reader);
+        let message = BoltMessage::load(&mut dechunker, |reader| {
+            PackStreamDeserializerImpl::new(reader)
+                .load(translator)
+                .map_err(Into::into)
+        })?;
+        Ok(Some((message.tag, message.fields)))
+    }
+
+    pub(crate) fn write_message(&mut self, tag: u8, fields: &[ValueSend]) -> Result<()> {
+        let size = u8::try_from(fields.len())
+            .ok()
+            .filter(|size| *size <= 0x0F)
+            .ok_or_else(|| Neo4jError::InvalidConfig {
+                message: format!("too many fields in message: {}", fields.len()),
+            })?;
+        let mut message_buff = Vec::new();
+        let mut serializer = PackStreamSerializerImpl::new(&mut message_buff);
+        serializer.write_struct_header(tag, size)?;
+        for field in fields {
+            self.translator.serialize(&mut serializer, field)?;
+        }
+        let stream = self.reader.get_mut();
+        for chunk in Chunker::new(&[message_buff]) {
+            Neo4jError::wrap_write(stream.write_all(&chunk))?;
+        }
+        Neo4jError::wrap_write(stream.flush())
+    }
+
+    pub(crate) fn into_inner(self) -> S {
+        self.reader.into_inner()
+    }
+}
+
+#[derive(Debug)]
+enum StubTranslator {
+    V4x4(Bolt4x4StructTranslator),
+    V5x0(Bolt5x0StructTranslator),
+    V6x0(Bolt6x0StructTranslator),
+}
+
+impl BoltStructTranslator for StubTranslator {
+    fn new(bolt_version: ServerAwareBoltVersion) -> Self {
+        match bolt_version {
+            ServerAwareBoltVersion::V4x4 => Self::V4x4(BoltStructTranslator::new(bolt_version)),
+            ServerAwareBoltVersion::V6x0 => Self::V6x0(BoltStructTranslator::new(bolt_version)),
+            _ => Self::V5x0(BoltStructTranslator::new(bolt_version)),
+        }
+    }
+
+    fn serialize<S: PackStreamSerializer>(
+        &self,
+        serializer: &mut S,
+        value: &ValueSend,
+    ) -> result::Result<(), S::Error> {
+        match self {
+            Self::V4x4(translator) => translator.serialize(serializer, value),
+            Self::V5x0(translator) => translator.serialize(serializer, value),
+            Self::V6x0(translator) => translator.serialize(serializer, value),
+        }
+    }
+
+    fn deserialize_struct(&self, tag: u8, fields: Vec<ValueReceive>) -> ValueReceive {
+        match self {
+            Self::V4x4(translator) => translator.deserialize_struct(tag, fields),
+            Self::V5x0(translator) => translator.deserialize_struct(tag, fields),
+            Self::V6x0(translator) => translator.deserialize_struct(tag, fields),
+        }
+    }
+}
@@ -16,6 +16,8 @@ use std::cmp;
 use std::fmt::Debug;
 use std::io::{self, Read, Write};
 use std::net::TcpStream;
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
 use std::time::Duration;
 
 use log::warn;
@@ -41,6 +43,44 @@ fn wrap_get_timeout_error<T>(e: Result<T>) -> Result<T> {
     }
 }
 
+/// A socket with read and write timeouts, used to enforce deadlines on blocking IO.
+pub(crate) trait SocketTimeouts: Debug {
+    fn read_timeout(&self) -> io::Result<Option<Duration>>;
+    fn write_timeout(&self) -> io::Result<Option<Duration>>;
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+    fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+}
+
+macro_rules! impl_socket_timeouts {
+    ($socket:ty) => {
+        impl SocketTimeouts for $socket {
+            #[inline]
+            fn read_timeout(&self) -> io::Result<Option<Duration>> {
+                <$socket>::read_timeout(self)
+            }
+
+            #[inline]
+            fn write_timeout(&self) -> io::Result<Option<Duration>> {
+                <$socket>::write_timeout(self)
+            }
+
+            #[inline]
+            fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+                <$socket>::set_read_timeout(self, timeout)
+            }
+
+            #[inline]
+            fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+                <$socket>::set_write_timeout(self, timeout)
+            }
+        }
+    };
+}
+
+impl_socket_timeouts!(TcpStream);
+#[cfg(unix)]
+impl_socket_timeouts!(UnixStream);
+
 enum DeadlineErrorDuring {
     GetTimeout,
     SetTimeout,
@@ -50,7 +90,7 @@ enum DeadlineErrorDuring {
 pub(crate) struct DeadlineIO<'tcp, S> {
     stream: S,
     deadline: Option<Instant>,
-    socket: Option<&'tcp TcpStream>,
+    socket: Option<&'tcp dyn SocketTimeouts>,
     error_during: Option<DeadlineErrorDuring>,
 }
 
@@ -58,7 +98,7 @@ impl<'tcp, S: Read + Write> DeadlineIO<'tcp, S> {
     pub(crate) fn new(
         stream: S,
         deadline: Option<Instant>,
-        socket: Option<&'tcp TcpStream>,
+        socket: Option<&'tcp dyn SocketTimeouts>,
     ) -> Self {
         Self {
             stream,
@@ -130,12 +170,14 @@ impl<'tcp, S: Read + Write> DeadlineIO<'tcp, S> {
     }
 }
 
-fn get_socket_timeout(socket: &TcpStream) -> io::Result<(Option<Duration>, Option<Duration>)> {
+fn get_socket_timeout(
+    socket: &dyn SocketTimeouts,
+) -> io::Result<(Option<Duration>, Option<Duration>)> {
     Ok((socket.read_timeout()?, socket.write_timeout()?))
 }
 
 fn set_socket_timeouts(
-    socket: &TcpStream,
+    socket: &dyn SocketTimeouts,
     timeouts: (Option<Duration>, Option<Duration>),
 ) -> io::Result<()> {
     let res1 = socket.set_read_timeout(timeouts.0);
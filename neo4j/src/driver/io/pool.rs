@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod circuit_breaker;
+mod housekeeping;
+mod metrics;
 mod routing;
 mod single_pool;
 mod ssr_tracker;
@@ -21,8 +24,8 @@ use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Formatter};
 use std::io::{Read, Write};
 use std::ops::{Deref, DerefMut};
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant as StdInstant};
 use std::{fmt, mem};
 
 use atomic_refcell::AtomicRefCell;
@@ -38,17 +41,23 @@ use crate::address_::resolution::AddressResolver;
 use crate::bookmarks::Bookmarks;
 use crate::driver::RoutingControl;
 use crate::driver::config::auth::{AuthToken, auth_managers};
+use crate::driver::config::connector::Connector;
+use crate::driver::config::load_balancing::{LoadBalancingStrategy, ServerCandidate};
 use crate::driver::config::notification::NotificationFilter;
 use crate::driver::config::{AuthConfig, KeepAliveConfig};
 use crate::error_::{Neo4jError, Result, ServerError};
 use crate::sync::MostlyRLock;
 use crate::time::Instant;
 use crate::value::ValueSend;
+use circuit_breaker::CircuitBreakers;
+pub use circuit_breaker::{CircuitBreakerConfig, CircuitState, ConfigureCircuitBreakerError};
+use housekeeping::Housekeeper;
+pub use housekeeping::HousekeepingConfig;
+pub use metrics::{ConnectionPoolMetrics, ConnectionPoolObserver};
 use routing::RoutingTable;
-#[cfg(feature = "_internal_testkit_backend")]
-pub use single_pool::ConnectionPoolMetrics;
+pub use routing::RoutingTableSnapshot;
 pub(crate) use single_pool::SessionAuth;
-use single_pool::{SimplePool, SinglePooledBolt, UnpreparedSinglePooledBolt};
+use single_pool::{PendingAcquisition, SimplePool, SinglePooledBolt, UnpreparedSinglePooledBolt};
 use ssr_tracker::SsrTracker;
 
 // 7 is a reasonable common upper bound for the size of clusters
@@ -57,13 +66,44 @@ const DEFAULT_CLUSTER_SIZE: usize = 7;
 
 type Addresses = Vec<Arc<Address>>;
 
+/// Handle to the driver's [`Pool`].
+///
+/// Either borrowed from the driver or shared with it,
+/// the latter allowing for sessions (and connections) that don't borrow the driver.
+#[derive(Debug, Clone)]
+pub(crate) enum PoolRef<'pool> {
+    Borrowed(&'pool Pool),
+    Shared(Arc<Pool>),
+}
+
+impl<'pool> PoolRef<'pool> {
+    pub(crate) fn acquire(&self, args: AcquireConfig) -> Result<PooledBolt<'pool>> {
+        let bolt = self.acquire_bolt(args)?;
+        Ok(PooledBolt {
+            bolt: Some(bolt),
+            pool: self.clone(),
+        })
+    }
+}
+
+impl Deref for PoolRef<'_> {
+    type Target = Pool;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            PoolRef::Borrowed(pool) => pool,
+            PoolRef::Shared(pool) => pool,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct PooledBolt<'pool> {
     bolt: Option<SinglePooledBolt>,
-    pool: &'pool Pool,
+    pool: PoolRef<'pool>,
 }
 
-impl<'pool> PooledBolt<'pool> {
+impl PooledBolt<'_> {
     fn wrap_io(&mut self, mut io_op: impl FnMut(&mut Self) -> Result<()>) -> Result<()> {
         let was_broken = self.deref().unexpectedly_closed();
         let res = io_op(self);
@@ -76,7 +116,7 @@ impl<'pool> PooledBolt<'pool> {
     #[inline]
     pub(crate) fn read_one(&mut self, deadline: Option<Instant>) -> Result<()> {
         self.wrap_io(|this| {
-            let mut cb = Self::new_server_error_handler(self.pool);
+            let mut cb = Self::new_server_error_handler(&this.pool);
             this.bolt
                 .as_mut()
                 .expect("bolt option should be Some from init to drop")
@@ -88,7 +128,7 @@ impl<'pool> PooledBolt<'pool> {
     #[inline]
     pub(crate) fn read_all(&mut self, deadline: Option<Instant>) -> Result<()> {
         self.wrap_io(|this| {
-            let mut cb = Self::new_server_error_handler(self.pool);
+            let mut cb = Self::new_server_error_handler(&this.pool);
             this.bolt
                 .as_mut()
                 .expect("bolt option should be Some from init to drop")
@@ -98,8 +138,8 @@ impl<'pool> PooledBolt<'pool> {
     }
 
     fn new_server_error_handler<RW: Read + Write>(
-        pool: &'pool Pool,
-    ) -> impl FnMut(&mut BoltData<RW>, &mut ServerError) -> Result<()> + 'pool {
+        pool: &Pool,
+    ) -> impl FnMut(&mut BoltData<RW>, &mut ServerError) -> Result<()> + '_ {
         move |bolt_data, error| pool.handle_server_error(bolt_data, error)
     }
 
@@ -151,13 +191,20 @@ pub(crate) struct PoolConfig {
     pub(crate) user_agent: String,
     pub(crate) auth: AuthConfig,
     pub(crate) max_connection_lifetime: Option<Duration>,
+    pub(crate) idle_time_before_connection_test: Option<Duration>,
     pub(crate) max_connection_pool_size: usize,
+    pub(crate) min_idle_connections: usize,
     pub(crate) connection_timeout: Option<Duration>,
     pub(crate) keep_alive: Option<KeepAliveConfig>,
     pub(crate) connection_acquisition_timeout: Option<Duration>,
     pub(crate) resolver: Option<Box<dyn AddressResolver>>,
     pub(crate) notification_filters: Arc<NotificationFilter>,
     pub(crate) telemetry: bool,
+    pub(crate) observer: Option<Arc<dyn ConnectionPoolObserver>>,
+    pub(crate) connector: Option<Arc<dyn Connector>>,
+    pub(crate) load_balancing_strategy: Arc<dyn LoadBalancingStrategy>,
+    pub(crate) circuit_breaker: Option<CircuitBreakerConfig>,
+    pub(crate) housekeeping: Option<HousekeepingConfig>,
 }
 
 impl PoolConfig {
@@ -176,18 +223,26 @@ pub(crate) struct Pool {
     pub(crate) config: Arc<PoolConfig>,
     ssr_tracker: Arc<SsrTracker>,
     pools: Pools,
+    // stops the housekeeping thread when dropped
+    _housekeeper: Option<Housekeeper>,
 }
 
 impl Pool {
-    pub(crate) fn new(address: Arc<Address>, config: PoolConfig) -> Self {
+    pub(crate) fn new(address: Arc<Address>, config: PoolConfig) -> Arc<Self> {
         let config = Arc::new(config);
         let ssr_tracker = Arc::new(SsrTracker::new());
         let pools = Pools::new(address, Arc::clone(&config), Arc::clone(&ssr_tracker));
-        Self {
-            config,
-            ssr_tracker,
-            pools,
-        }
+        Arc::new_cyclic(|pool: &Weak<Self>| {
+            let housekeeper = config.housekeeping.as_ref().and_then(|housekeeping| {
+                Housekeeper::start(Weak::clone(pool), housekeeping.interval())
+            });
+            Self {
+                config,
+                ssr_tracker,
+                pools,
+                _housekeeper: housekeeper,
+            }
+        })
     }
 
     #[inline]
@@ -205,12 +260,30 @@ impl Pool {
         self.ssr_tracker.ssr_enabled()
     }
 
-    #[cfg(feature = "_internal_testkit_backend")]
     #[inline]
     pub(crate) fn get_metrics(&self, address: Arc<Address>) -> Option<ConnectionPoolMetrics> {
         self.pools.get_metrics(address)
     }
 
+    #[inline]
+    pub(crate) fn get_all_metrics(&self) -> HashMap<Arc<Address>, ConnectionPoolMetrics> {
+        self.pools.get_all_metrics()
+    }
+
+    pub(crate) fn get_circuit_state(&self, address: &Address) -> Option<CircuitState> {
+        let Pools::Routing(pools) = &self.pools else {
+            return None;
+        };
+        pools.get_circuit_state(address)
+    }
+
+    pub(crate) fn get_all_circuit_states(&self) -> HashMap<Arc<Address>, CircuitState> {
+        let Pools::Routing(pools) = &self.pools else {
+            return HashMap::new();
+        };
+        pools.get_all_circuit_states()
+    }
+
     pub(crate) fn resolve_home_db(&self, args: UpdateRtArgs) -> Result<Option<Arc<String>>> {
         let Pools::Routing(pools) = &self.pools else {
             panic!("don't call resolve_home_db on a direct pool")
@@ -223,35 +296,157 @@ impl Pool {
             mode_hint: None,
         };
         let mut resolved_db = None;
+        let mut to_fill = Vec::new();
         {
             let resolved_db = &mut resolved_db;
+            let to_fill = &mut to_fill;
             drop(pools.routing_tables.update(move |mut rts| {
-                *resolved_db = pools.update_rts(args, &mut rts)?;
+                *resolved_db = pools.update_rts(args, &mut rts, to_fill)?;
                 Ok(())
             })?);
         }
+        RoutingPool::fill_in_background(&to_fill);
         Ok(resolved_db)
     }
 
-    pub(crate) fn acquire(&self, args: AcquireConfig) -> Result<PooledBolt<'_>> {
-        Ok(PooledBolt {
-            bolt: Some(match &self.pools {
-                Pools::Direct(single_pool) => {
-                    let mut connection = None;
-                    while connection.is_none() {
-                        connection = single_pool.acquire(args.update_rt_args.deadline)?.prepare(
-                            args.update_rt_args.deadline,
-                            args.update_rt_args.idle_time_before_connection_test,
-                            args.update_rt_args.session_auth,
-                            None,
-                        )?;
+    pub(crate) fn routing_table(&self, db: &Option<Arc<String>>) -> Option<RoutingTableSnapshot> {
+        let Pools::Routing(pools) = &self.pools else {
+            return None;
+        };
+        pools
+            .routing_tables
+            .read()
+            .get(db)
+            .map(RoutingTable::snapshot)
+    }
+
+    /// Fetch a new routing table regardless of the current one's freshness.
+    ///
+    /// Returns `Ok(None)` for direct pools.
+    pub(crate) fn refresh_routing_table(
+        &self,
+        args: UpdateRtArgs,
+    ) -> Result<Option<RoutingTableSnapshot>> {
+        let Pools::Routing(pools) = &self.pools else {
+            return Ok(None);
+        };
+        let args = InternalUpdateRtArgs {
+            update_rt_args: args,
+            mode_hint: None,
+        };
+        let mut db = None;
+        let mut to_fill = Vec::new();
+        let rts = {
+            let db = &mut db;
+            let to_fill = &mut to_fill;
+            pools.routing_tables.update(move |mut rts| {
+                *db = pools.update_rts(args, &mut rts, to_fill)?;
+                Ok(())
+            })?
+        };
+        let snapshot = rts.get(&db).map(RoutingTable::snapshot);
+        drop(rts);
+        RoutingPool::fill_in_background(&to_fill);
+        Ok(snapshot)
+    }
+
+    pub(crate) fn invalidate_routing_table(&self, db: &Option<Arc<String>>) {
+        if let Pools::Routing(pools) = &self.pools {
+            drop(pools.routing_tables.update(|mut rts| {
+                if let Some(rt) = rts.get_mut(db) {
+                    rt.invalidate();
+                }
+                Ok(())
+            }));
+        }
+    }
+
+    /// Fill all reader and writer pools up to `min_idle_connections`.
+    ///
+    /// If no routing table is known yet, the home database's routing table is fetched first.
+    /// Keeps going when filling a pool fails and returns the first error.
+    pub(crate) fn warm_up(&self, args: UpdateRtArgs) -> Result<()> {
+        match &self.pools {
+            Pools::Direct(single_pool) => single_pool.fill(args.deadline),
+            Pools::Routing(pools) => {
+                if pools.reader_and_writer_addresses().is_empty() {
+                    self.refresh_routing_table(args)?;
+                }
+                let mut res = Ok(());
+                for address in pools.reader_and_writer_addresses() {
+                    let pool = pools
+                        .ensure_pool_exists(&address)
+                        .get(&address)
+                        .expect("just created above")
+                        .clone();
+                    if let Err(err) = pool.fill(args.deadline) {
+                        if let Neo4jError::Disconnect { .. } = err {
+                            pools.deactivate_server(&address);
+                        }
+                        if res.is_ok() {
+                            res = Err(err);
+                        }
                     }
-                    connection.expect("loop above asserts existence")
                 }
-                Pools::Routing(routing_pool) => routing_pool.acquire(args)?,
-            }),
-            pool: self,
-        })
+                res
+            }
+        }
+    }
+
+    /// One sweep of the housekeeping thread (see [`HousekeepingConfig`]).
+    fn housekeeping(&self) {
+        let Some(housekeeping) = &self.config.housekeeping else {
+            return;
+        };
+        match &self.pools {
+            Pools::Direct(single_pool) => {
+                single_pool.sweep();
+                if let Err(err) = single_pool.fill(self.config.connection_acquisition_deadline()) {
+                    info!("failed to fill connection pool: {err}");
+                }
+            }
+            Pools::Routing(pools) => pools.housekeeping(housekeeping),
+        }
+    }
+
+    pub(crate) fn acquire(&self, args: AcquireConfig) -> Result<PooledBolt<'_>> {
+        PoolRef::Borrowed(self).acquire(args)
+    }
+
+    fn acquire_bolt(&self, args: AcquireConfig) -> Result<SinglePooledBolt> {
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "neo4j.acquire_connection",
+            db.system.name = "neo4j",
+            db.namespace = args.update_rt_args.db.map(|db| db.db.as_str()),
+            neo4j.mode = ?args.mode,
+            server.address = tracing::field::Empty,
+            neo4j.bolt_id = tracing::field::Empty,
+        )
+        .entered();
+        let bolt = match &self.pools {
+            Pools::Direct(single_pool) => {
+                let acquisition_start = StdInstant::now();
+                let mut connection = None;
+                while connection.is_none() {
+                    connection = single_pool.acquire(args.update_rt_args.deadline)?.prepare(
+                        args.update_rt_args.deadline,
+                        acquisition_start,
+                        args.update_rt_args.idle_time_before_connection_test,
+                        args.update_rt_args.session_auth,
+                        None,
+                    )?;
+                }
+                connection.expect("loop above asserts existence")
+            }
+            Pools::Routing(routing_pool) => routing_pool.acquire(args)?,
+        };
+        #[cfg(feature = "tracing")]
+        {
+            span.record("server.address", tracing::field::display(bolt.address()));
+            span.record("neo4j.bolt_id", bolt.bolt_id());
+        }
+        Ok(bolt)
     }
 
     fn handle_server_error<RW: Read + Write>(
@@ -299,7 +494,6 @@ impl Pools {
         }
     }
 
-    #[cfg(feature = "_internal_testkit_backend")]
     fn get_metrics(&self, address: Arc<Address>) -> Option<ConnectionPoolMetrics> {
         match self {
             Pools::Direct(pool) => {
@@ -312,6 +506,15 @@ impl Pools {
             Pools::Routing(pools) => pools.get_metrics(address),
         }
     }
+
+    fn get_all_metrics(&self) -> HashMap<Arc<Address>, ConnectionPoolMetrics> {
+        match self {
+            Pools::Direct(pool) => {
+                HashMap::from([(Arc::clone(pool.address()), pool.get_metrics())])
+            }
+            Pools::Routing(pools) => pools.get_all_metrics(),
+        }
+    }
 }
 
 type RoutingTables = HashMap<Option<Arc<String>>, RoutingTable>;
@@ -325,11 +528,16 @@ struct RoutingPool {
     address: Arc<Address>,
     config: Arc<PoolConfig>,
     ssr_tracker: Arc<SsrTracker>,
+    circuit_breakers: Option<Box<CircuitBreakers>>,
 }
 
 impl RoutingPool {
     fn new(address: Arc<Address>, config: Arc<PoolConfig>, ssr_tracker: Arc<SsrTracker>) -> Self {
         assert!(config.routing_context.is_some());
+        let circuit_breakers = config
+            .circuit_breaker
+            .clone()
+            .map(|config| Box::new(CircuitBreakers::new(config)));
         Self {
             pools: MostlyRLock::new(HashMap::with_capacity(DEFAULT_CLUSTER_SIZE)),
             wait_cond: Arc::new((Mutex::new(()), Condvar::new())),
@@ -337,6 +545,7 @@ impl RoutingPool {
             address,
             config,
             ssr_tracker,
+            circuit_breakers,
         }
     }
 
@@ -349,6 +558,7 @@ impl RoutingPool {
                 .map(|db| format!("{db:?}"))
                 .unwrap_or(String::from("default database"))
         );
+        let acquisition_start = StdInstant::now();
         let (mut targets, db) = self.choose_addresses_from_fresh_rt(args)?;
         let deadline = args.update_rt_args.deadline;
         'target: for target in &targets {
@@ -357,11 +567,15 @@ impl RoutingPool {
                     |bolt_data: &mut _, error: &mut _| self.handle_server_error(bolt_data, error);
                 match connection.prepare(
                     deadline,
+                    acquisition_start,
                     args.update_rt_args.idle_time_before_connection_test,
                     args.update_rt_args.session_auth,
                     Some(&mut on_server_error),
                 ) {
-                    Ok(Some(connection)) => return Ok(connection),
+                    Ok(Some(connection)) => {
+                        self.record_success(target);
+                        return Ok(connection);
+                    }
                     Ok(None) => continue,
                     Err(Neo4jError::Disconnect { .. }) => {
                         self.deactivate_server(target);
@@ -378,35 +592,38 @@ impl RoutingPool {
             targets = self.choose_addresses(args, &db)?;
             // a connection could've been returned while we didn't hold the lock
             // => try again with the lock
-            let connection = targets
-                .iter()
-                .map(|target| self.acquire_routing_address_no_wait(target))
-                .skip_while(Option::is_none)
-                .map(Option::unwrap)
-                .next();
-            if let Some(connection) = connection {
+            let connection = targets.iter().find_map(|target| {
+                self.acquire_routing_address_no_wait(target)
+                    .map(|connection| (target, connection))
+            });
+            if let Some((target, connection)) = connection {
                 drop(cond_lock);
                 let mut on_server_error =
                     |bolt_data: &mut _, error: &mut _| self.handle_server_error(bolt_data, error);
                 match connection.prepare(
                     deadline,
+                    acquisition_start,
                     args.update_rt_args.idle_time_before_connection_test,
                     args.update_rt_args.session_auth,
                     Some(&mut on_server_error),
                 ) {
-                    Ok(Some(connection)) => return Ok(connection),
+                    Ok(Some(connection)) => {
+                        self.record_success(target);
+                        return Ok(connection);
+                    }
                     Ok(None) => {
                         cond_lock = self.wait_cond.0.lock();
                         continue;
                     }
                     Err(Neo4jError::Disconnect { .. }) => {
-                        self.deactivate_server(&targets[0]);
+                        self.deactivate_server(target);
                         cond_lock = self.wait_cond.0.lock();
                         continue;
                     }
                     Err(e) => return Err(e),
                 }
             }
+            let _pending = self.pending_acquisitions(&targets);
             match deadline {
                 None => self.wait_cond.1.wait(&mut cond_lock),
                 Some(timeout) => {
@@ -416,6 +633,10 @@ impl RoutingPool {
                         .wait_until(&mut cond_lock, timeout.raw())
                         .timed_out()
                     {
+                        drop(cond_lock);
+                        if let Some(observer) = &self.config.observer {
+                            observer.on_acquisition_timed_out(None);
+                        }
                         return Err(Neo4jError::connection_acquisition_timeout(
                             "waiting for room in the connection pool",
                         ));
@@ -425,14 +646,29 @@ impl RoutingPool {
         }
     }
 
+    fn pending_acquisitions(&self, targets: &[Arc<Address>]) -> Vec<PendingAcquisition> {
+        let pools = self.pools.read();
+        targets
+            .iter()
+            .filter_map(|target| pools.get(target).map(SimplePool::pending_acquisition))
+            .collect()
+    }
+
     /// Guarantees that Vec is not empty
     fn choose_addresses_from_fresh_rt(
         &self,
         args: AcquireConfig,
     ) -> Result<(Addresses, Option<Arc<String>>)> {
-        let (lock, db) = self.get_fresh_rt(args)?;
-        let rt = lock.get(&db).expect("created above");
-        Ok((self.servers_by_usage(rt.servers_for_mode(args.mode))?, db))
+        let mut to_fill = Vec::new();
+        let res = self
+            .get_fresh_rt(args, &mut to_fill)
+            .and_then(|(lock, db)| {
+                let rt = lock.get(&db).expect("created above");
+                Ok((self.servers_by_usage(rt.servers_for_mode(args.mode))?, db))
+            });
+        // don't hold the routing table lock while spawning fillers
+        Self::fill_in_background(&to_fill);
+        res
     }
 
     /// Guarantees that Vec is not empty
@@ -461,6 +697,7 @@ impl RoutingPool {
         target: &Arc<Address>,
         args: UpdateRtArgs,
     ) -> Result<SinglePooledBolt> {
+        let acquisition_start = StdInstant::now();
         let mut connection = None;
         while connection.is_none() {
             let unprepared_connection = {
@@ -474,6 +711,7 @@ impl RoutingPool {
                 |bolt_data: &mut _, error: &mut _| self.handle_server_error(bolt_data, error);
             connection = unprepared_connection.prepare(
                 args.deadline,
+                acquisition_start,
                 args.idle_time_before_connection_test,
                 args.session_auth,
                 Some(&mut on_server_error),
@@ -504,6 +742,7 @@ impl RoutingPool {
     fn get_fresh_rt(
         &self,
         args: AcquireConfig,
+        to_fill: &mut Vec<SimplePool>,
     ) -> Result<(RwLockReadGuard<'_, RoutingTables>, Option<Arc<String>>)> {
         let rt_args = args.update_rt_args;
         let int_rt_args = InternalUpdateRtArgs {
@@ -528,7 +767,7 @@ impl RoutingPool {
                 let key = rt_args.rt_key();
                 let rt = rts.entry(key).or_insert_with(|| self.empty_rt());
                 if !rt.is_fresh(args.mode) {
-                    let mut new_db = self.update_rts(int_rt_args, &mut rts)?;
+                    let mut new_db = self.update_rts(int_rt_args, &mut rts, to_fill)?;
                     if new_db.is_some() && db_name_ref.borrow().is_none() {
                         mem::swap(&mut *db_name_ref.borrow_mut(), &mut new_db);
                     }
@@ -541,27 +780,54 @@ impl RoutingPool {
 
     /// Guarantees that Vec is not empty
     fn servers_by_usage(&self, addresses: &[Arc<Address>]) -> Result<Addresses> {
-        Ok(match addresses.len() {
-            0 => return Err(Neo4jError::disconnect("routing options depleted")),
-            1 => vec![Arc::clone(&addresses[0])],
-            _ => {
-                let pools = self.pools.read();
-                addresses
-                    .iter()
-                    .map(|addr| (addr, pools.get(addr).map(|p| p.in_use()).unwrap_or(0)))
-                    .sorted_unstable_by_key(|(_, usage)| *usage)
-                    .map(|(addr, _)| Arc::clone(addr))
-                    // .rev()
-                    .collect()
-            }
-        })
+        if addresses.is_empty() {
+            return Err(Neo4jError::disconnect("routing options depleted"));
+        }
+        let Some(circuit_breakers) = &self.circuit_breakers else {
+            return Ok(self.order_servers(addresses));
+        };
+        // servers due for a probe go first
+        let (mut servers, usable) = circuit_breakers.select(addresses);
+        servers.extend(self.order_servers(&usable));
+        Ok(servers)
     }
 
+    fn order_servers(&self, addresses: &[Arc<Address>]) -> Addresses {
+        if addresses.len() < 2 {
+            return addresses.to_vec();
+        }
+        let mut candidates = {
+            let pools = self.pools.read();
+            addresses
+                .iter()
+                .map(|addr| {
+                    let metrics = pools.get(addr).map(|p| p.get_metrics());
+                    ServerCandidate::new(Arc::clone(addr), metrics.unwrap_or_default())
+                })
+                .collect::<Vec<_>>()
+        };
+        self.config.load_balancing_strategy.order(&mut candidates);
+        candidates
+            .into_iter()
+            .map(ServerCandidate::into_address)
+            .collect()
+    }
+
+    /// Pools of the new routing table that need filling are added to `to_fill`.
+    /// It's up to the caller to fill them once the routing table lock is released.
     fn update_rts(
         &self,
         args: InternalUpdateRtArgs,
         rts: &mut RoutingTables,
+        to_fill: &mut Vec<SimplePool>,
     ) -> Result<Option<Arc<String>>> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "neo4j.fetch_routing_table",
+            db.system.name = "neo4j",
+            db.namespace = args.update_rt_args.db_request_str(),
+        )
+        .entered();
         debug!(
             "Fetching new routing table for {:?}",
             args.update_rt_args.db
@@ -602,6 +868,7 @@ impl RoutingPool {
                     _ => new_rt.database.clone(),
                 };
                 debug!("Storing new routing table for {db:?}: {new_rt:?}");
+                to_fill.extend(self.pools_to_fill(&new_rt));
                 rts.insert(db.as_ref().map(Arc::clone), new_rt);
                 self.clean_up_pools(rts);
                 if let Some(cb) = args.update_rt_args.db_resolution_cb {
@@ -748,40 +1015,118 @@ impl RoutingPool {
         rt
     }
 
+    fn reader_and_writer_addresses(&self) -> Addresses {
+        self.routing_tables
+            .read()
+            .values()
+            .flat_map(|rt| rt.readers.iter().chain(&rt.writers))
+            .unique()
+            .map(Arc::clone)
+            .collect()
+    }
+
+    fn housekeeping(&self, housekeeping: &HousekeepingConfig) {
+        let purge_delay = housekeeping.routing_table_purge_delay();
+        drop(self.routing_tables.update(|mut rts| {
+            rts.retain(|db, rt| {
+                let purge = rt.is_expired_for(purge_delay);
+                if purge {
+                    debug!("purging routing table for {db:?}");
+                }
+                !purge
+            });
+            self.clean_up_pools(&mut rts);
+            Ok(())
+        }));
+
+        // don't hold the lock while doing IO
+        let pools = self.pools.read().values().cloned().collect::<Vec<_>>();
+        for pool in &pools {
+            pool.sweep();
+        }
+        // closed connections made room => wake up waiting acquisitions
+        {
+            let _lock = self.wait_cond.0.lock();
+            self.wait_cond.1.notify_all();
+        }
+
+        if self.config.min_idle_connections == 0 {
+            return;
+        }
+        let pools = self
+            .reader_and_writer_addresses()
+            .iter()
+            .map(|address| self.get_or_create_pool(address))
+            .collect::<Vec<_>>();
+        Self::fill_in_background(&pools);
+    }
+
+    fn pools_to_fill(&self, rt: &RoutingTable) -> Vec<SimplePool> {
+        if self.config.min_idle_connections == 0 {
+            return Vec::new();
+        }
+        rt.readers
+            .iter()
+            .chain(&rt.writers)
+            .unique()
+            .map(|address| self.get_or_create_pool(address))
+            .collect()
+    }
+
+    fn get_or_create_pool(&self, address: &Arc<Address>) -> SimplePool {
+        self.ensure_pool_exists(address)
+            .get(address)
+            .expect("just created above")
+            .clone()
+    }
+
+    fn fill_in_background(pools: &[SimplePool]) {
+        // one pool per thread => an unreachable server doesn't hold up filling the others
+        for pool in pools {
+            pool.fill_in_background();
+        }
+    }
+
     fn empty_rt(&self) -> RoutingTable {
         RoutingTable::new(Arc::clone(&self.address))
     }
 
     fn clean_up_pools(&self, rts: &mut RoutingTables) {
+        let used_addresses = rts
+            .values()
+            .map(|rt| {
+                [&rt.readers, &rt.routers, &rt.writers]
+                    .into_iter()
+                    .flat_map(|addrs| addrs.iter().map(Arc::clone))
+                    .collect::<Vec<_>>()
+            })
+            .fold(
+                HashSet::with_capacity(DEFAULT_CLUSTER_SIZE),
+                |mut set, addrs| {
+                    addrs.into_iter().for_each(|addr| {
+                        set.insert(addr);
+                    });
+                    set
+                },
+            );
         drop(self.pools.update(|mut pools| {
-            let used_addresses = rts
-                .values()
-                .map(|rt| {
-                    [&rt.readers, &rt.routers, &rt.writers]
-                        .into_iter()
-                        .flat_map(|addrs| addrs.iter().map(Arc::clone))
-                        .collect::<Vec<_>>()
-                })
-                .fold(
-                    HashSet::with_capacity(DEFAULT_CLUSTER_SIZE),
-                    |mut set, addrs| {
-                        addrs.into_iter().for_each(|addr| {
-                            set.insert(addr);
-                        });
-                        set
-                    },
-                );
             let existing_addresses = pools.keys().map(Arc::clone).collect::<HashSet<_>>();
             for addr in existing_addresses {
-                if !used_addresses.contains(&addr) {
-                    pools.remove(&addr);
+                if !used_addresses.contains(&addr)
+                    && let Some(pool) = pools.remove(&addr)
+                {
+                    pool.retire();
                 }
             }
             Ok(())
         }));
+        if let Some(circuit_breakers) = &self.circuit_breakers {
+            circuit_breakers.retain(&used_addresses);
+        }
     }
 
     fn deactivate_server(&self, addr: &Address) {
+        self.record_failure(addr);
         drop(self.routing_tables.update(|mut rts| {
             drop(self.pools.update(|mut pools| {
                 Self::deactivate_server_locked(addr, &mut rts, &mut pools);
@@ -792,6 +1137,7 @@ impl RoutingPool {
     }
 
     fn deactivate_server_locked_rts(&self, addr: &Address, rts: &mut RoutingTables) {
+        self.record_failure(addr);
         drop(self.pools.update(|mut pools| {
             Self::deactivate_server_locked(addr, rts, &mut pools);
             Ok(())
@@ -801,7 +1147,21 @@ impl RoutingPool {
     fn deactivate_server_locked(addr: &Address, rts: &mut RoutingTables, pools: &mut RoutingPools) {
         debug!("deactivating address: {addr:?}");
         rts.iter_mut().for_each(|(_, rt)| rt.deactivate(addr));
-        pools.remove(addr);
+        if let Some(pool) = pools.remove(addr) {
+            pool.retire();
+        }
+    }
+
+    fn record_success(&self, addr: &Arc<Address>) {
+        if let Some(circuit_breakers) = &self.circuit_breakers {
+            circuit_breakers.record_success(addr);
+        }
+    }
+
+    fn record_failure(&self, addr: &Address) {
+        if let Some(circuit_breakers) = &self.circuit_breakers {
+            circuit_breakers.record_failure(addr);
+        }
     }
 
     fn deactivate_writer(&self, addr: &Address) {
@@ -852,10 +1212,28 @@ impl RoutingPool {
         }
     }
 
-    #[cfg(feature = "_internal_testkit_backend")]
     fn get_metrics(&self, address: Arc<Address>) -> Option<ConnectionPoolMetrics> {
         self.pools.read().get(&address).map(SimplePool::get_metrics)
     }
+
+    fn get_all_metrics(&self) -> HashMap<Arc<Address>, ConnectionPoolMetrics> {
+        self.pools
+            .read()
+            .iter()
+            .map(|(address, pool)| (Arc::clone(address), pool.get_metrics()))
+            .collect()
+    }
+
+    fn get_circuit_state(&self, address: &Address) -> Option<CircuitState> {
+        self.circuit_breakers.as_ref()?.state(address)
+    }
+
+    fn get_all_circuit_states(&self) -> HashMap<Arc<Address>, CircuitState> {
+        self.circuit_breakers
+            .as_ref()
+            .map(|circuit_breakers| circuit_breakers.states())
+            .unwrap_or_default()
+    }
 }
 
 fn handle_server_error(
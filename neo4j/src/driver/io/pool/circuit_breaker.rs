@@ -0,0 +1,615 @@
+// Copyright Rouven Bauer
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::result::Result as StdResult;
+use std::sync::Arc;
+use std::time::Duration;
+
+use log::{info, warn};
+use parking_lot::Mutex;
+use thiserror::Error;
+
+use crate::address_::Address;
+use crate::time::Instant;
+
+// imports for docs
+#[allow(unused)]
+use crate::driver::{Driver, DriverConfig};
+
+const DEFAULT_CONSECUTIVE_FAILURE_THRESHOLD: usize = 5;
+const DEFAULT_OPEN_DURATION: Duration = Duration::from_secs(30);
+
+/// Configuration of the per-server circuit breaker.
+///
+/// When routing, the driver tracks the outcome of connecting to each server.
+/// If a server fails too often, its circuit *opens* and the server is ejected:
+/// the driver won't send work to it, even if new routing tables keep listing it.
+/// After [`CircuitBreakerConfig::with_open_duration()`], the circuit becomes *half-open* and the
+/// driver sends a single probe (the next acquisition of a connection to that server).
+/// If the probe succeeds, the circuit *closes* again, otherwise it opens for another period.
+///
+/// If all readers (or writers) of a routing table are ejected, the driver ignores the circuit
+/// breaker for them rather than failing.
+///
+/// Register with [`DriverConfig::with_circuit_breaker()`].
+/// See [`Driver::get_circuit_state()`] for inspecting the circuit states.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+///
+/// use neo4j::driver::{CircuitBreakerConfig, DriverConfig};
+///
+/// let circuit_breaker = CircuitBreakerConfig::new()
+///     .with_consecutive_failure_threshold(3)
+///     .unwrap()
+///     .with_error_rate_threshold(0.5, 20)
+///     .unwrap()
+///     .with_open_duration(Duration::from_secs(10));
+/// let config = DriverConfig::new().with_circuit_breaker(circuit_breaker);
+/// # drop(config);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CircuitBreakerConfig {
+    consecutive_failures: Option<usize>,
+    error_rate: Option<(f64, usize)>,
+    open_duration: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            consecutive_failures: Some(DEFAULT_CONSECUTIVE_FAILURE_THRESHOLD),
+            error_rate: None,
+            open_duration: DEFAULT_OPEN_DURATION,
+        }
+    }
+}
+
+impl CircuitBreakerConfig {
+    /// Create a new circuit breaker configuration with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open the circuit after `failures` failures in a row.
+    ///
+    /// Default: `5`.
+    ///
+    /// Returns an error if `failures` is `0`.
+    #[allow(clippy::result_large_err)]
+    pub fn with_consecutive_failure_threshold(
+        mut self,
+        failures: usize,
+    ) -> StdResult<Self, ConfigureCircuitBreakerError<Self>> {
+        if failures == 0 {
+            return Err(ConfigureCircuitBreakerError {
+                builder: self,
+                reason: "consecutive failure threshold must be at least 1",
+            });
+        }
+        self.consecutive_failures = Some(failures);
+        Ok(self)
+    }
+
+    /// Don't open the circuit because of consecutive failures.
+    #[inline]
+    pub fn without_consecutive_failure_threshold(mut self) -> Self {
+        self.consecutive_failures = None;
+        self
+    }
+
+    /// Open the circuit if the ratio of failures among the last `window` outcomes reaches `rate`.
+    ///
+    /// The rate is only evaluated once `window` outcomes have been recorded.
+    ///
+    /// This is disabled by default.
+    ///
+    /// Returns an error if `rate` is not in `(0, 1]` or if `window` is `0`.
+    #[allow(clippy::result_large_err)]
+    pub fn with_error_rate_threshold(
+        mut self,
+        rate: f64,
+        window: usize,
+    ) -> StdResult<Self, ConfigureCircuitBreakerError<Self>> {
+        if !(rate > 0.0 && rate <= 1.0) {
+            return Err(ConfigureCircuitBreakerError {
+                builder: self,
+                reason: "error rate threshold must be in (0, 1]",
+            });
+        }
+        if window == 0 {
+            return Err(ConfigureCircuitBreakerError {
+                builder: self,
+                reason: "error rate window must be at least 1",
+            });
+        }
+        self.error_rate = Some((rate, window));
+        Ok(self)
+    }
+
+    /// Don't open the circuit because of the error rate.
+    ///
+    /// This is the *default*.
+    #[inline]
+    pub fn without_error_rate_threshold(mut self) -> Self {
+        self.error_rate = None;
+        self
+    }
+
+    /// How long an opened circuit stays open before a probe is sent to the server.
+    ///
+    /// Default: `30` seconds.
+    #[inline]
+    pub fn with_open_duration(mut self, open_duration: Duration) -> Self {
+        self.open_duration = open_duration;
+        self
+    }
+}
+
+/// Used when configuring an invalid [`CircuitBreakerConfig`].
+#[derive(Debug, Error)]
+#[error("invalid circuit breaker configuration: {reason}")]
+pub struct ConfigureCircuitBreakerError<Builder> {
+    pub builder: Builder,
+    reason: &'static str,
+}
+
+/// The state of the circuit breaker for a single server.
+///
+/// See [`CircuitBreakerConfig`] and [`Driver::get_circuit_state()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CircuitState {
+    /// The server is healthy and used normally.
+    Closed,
+    /// The server failed too often and is ejected.
+    Open,
+    /// The server was ejected, and the driver is (about to start) probing it.
+    HalfOpen,
+}
+
+impl Display for CircuitState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CircuitState::Closed => write!(f, "closed"),
+            CircuitState::Open => write!(f, "open"),
+            CircuitState::HalfOpen => write!(f, "half-open"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct CircuitBreakers {
+    config: CircuitBreakerConfig,
+    breakers: Mutex<HashMap<Arc<Address>, Breaker>>,
+}
+
+#[derive(Debug)]
+struct Breaker {
+    state: BreakerState,
+    consecutive_failures: usize,
+    // true = failure
+    window: VecDeque<bool>,
+    window_failures: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BreakerState {
+    Closed,
+    Open { since: Instant },
+    HalfOpen { probing_since: Instant },
+}
+
+impl From<BreakerState> for CircuitState {
+    fn from(state: BreakerState) -> Self {
+        match state {
+            BreakerState::Closed => CircuitState::Closed,
+            BreakerState::Open { .. } => CircuitState::Open,
+            BreakerState::HalfOpen { .. } => CircuitState::HalfOpen,
+        }
+    }
+}
+
+impl Breaker {
+    fn new() -> Self {
+        Self {
+            state: BreakerState::Closed,
+            consecutive_failures: 0,
+            window: VecDeque::new(),
+            window_failures: 0,
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Record an outcome and return whether the circuit should open.
+    fn record(&mut self, failure: bool, config: &CircuitBreakerConfig) -> bool {
+        if failure {
+            self.consecutive_failures += 1;
+        } else {
+            self.consecutive_failures = 0;
+        }
+        if let Some((_, window)) = config.error_rate {
+            self.window.push_back(failure);
+            self.window_failures += usize::from(failure);
+            if self.window.len() > window && self.window.pop_front() == Some(true) {
+                self.window_failures -= 1;
+            }
+        }
+        failure && self.exceeds_thresholds(config)
+    }
+
+    fn exceeds_thresholds(&self, config: &CircuitBreakerConfig) -> bool {
+        let consecutive = config
+            .consecutive_failures
+            .is_some_and(|threshold| self.consecutive_failures >= threshold);
+        let rate = config.error_rate.is_some_and(|(rate, window)| {
+            self.window.len() == window && self.window_failures as f64 >= rate * window as f64
+        });
+        consecutive || rate
+    }
+}
+
+impl CircuitBreakers {
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            breakers: Default::default(),
+        }
+    }
+
+    pub(crate) fn record_success(&self, address: &Arc<Address>) {
+        let mut breakers = self.breakers.lock();
+        let breaker = breakers
+            .entry(Arc::clone(address))
+            .or_insert_with(Breaker::new);
+        match breaker.state {
+            BreakerState::Closed => {
+                breaker.record(false, &self.config);
+            }
+            // late result of a connection acquired before the circuit opened
+            BreakerState::Open { .. } => {}
+            BreakerState::HalfOpen { .. } => {
+                info!("circuit breaker for {address}: probe succeeded, half-open -> closed");
+                breaker.reset();
+            }
+        }
+    }
+
+    pub(crate) fn record_failure(&self, address: &Address) {
+        let mut breakers = self.breakers.lock();
+        let breaker = match breakers.get_mut(address) {
+            Some(breaker) => breaker,
+            None => breakers
+                .entry(Arc::new(address.clone()))
+                .or_insert_with(Breaker::new),
+        };
+        match breaker.state {
+            BreakerState::Closed => {
+                if breaker.record(true, &self.config) {
+                    warn!(
+                        "circuit breaker for {address}: ejecting server for {:?} \
+                         after too many failures, closed -> open",
+                        self.config.open_duration
+                    );
+                    breaker.state = BreakerState::Open {
+                        since: Instant::now(),
+                    };
+                }
+            }
+            BreakerState::Open { .. } => {}
+            BreakerState::HalfOpen { .. } => {
+                warn!("circuit breaker for {address}: probe failed, half-open -> open");
+                breaker.state = BreakerState::Open {
+                    since: Instant::now(),
+                };
+            }
+        }
+    }
+
+    /// Split `addresses` into servers due for a probe and servers that may be used normally.
+    ///
+    /// Servers due for a probe become half-open, i.e., the caller is expected to try them.
+    /// Ejected servers are left out, unless all servers are ejected.
+    pub(crate) fn select(&self, addresses: &[Arc<Address>]) -> (Addresses, Addresses) {
+        let mut breakers = self.breakers.lock();
+        let mut probes = Vec::new();
+        let mut usable = Vec::with_capacity(addresses.len());
+        for address in addresses {
+            let Some(breaker) = breakers.get_mut(address) else {
+                usable.push(Arc::clone(address));
+                continue;
+            };
+            match breaker.state {
+                BreakerState::Closed => usable.push(Arc::clone(address)),
+                BreakerState::Open { since } => {
+                    if since.elapsed() >= self.config.open_duration {
+                        info!("circuit breaker for {address}: probing server, open -> half-open");
+                        breaker.state = BreakerState::HalfOpen {
+                            probing_since: Instant::now(),
+                        };
+                        probes.push(Arc::clone(address));
+                    }
+                }
+                BreakerState::HalfOpen { probing_since } => {
+                    // the probe was never sent (e.g., another server was picked instead)
+                    if probing_since.elapsed() >= self.config.open_duration {
+                        breaker.state = BreakerState::HalfOpen {
+                            probing_since: Instant::now(),
+                        };
+                        probes.push(Arc::clone(address));
+                    }
+                }
+            }
+        }
+        if probes.is_empty() && usable.is_empty() {
+            info!("circuit breaker: all candidate servers are ejected, ignoring circuit breaker");
+            return (Vec::new(), addresses.to_vec());
+        }
+        (probes, usable)
+    }
+
+    pub(crate) fn state(&self, address: &Address) -> Option<CircuitState> {
+        self.breakers
+            .lock()
+            .get(address)
+            .map(|breaker| breaker.state.into())
+    }
+
+    pub(crate) fn states(&self) -> HashMap<Arc<Address>, CircuitState> {
+        self.breakers
+            .lock()
+            .iter()
+            .map(|(address, breaker)| (Arc::clone(address), breaker.state.into()))
+            .collect()
+    }
+
+    /// Forget about servers that are no longer used.
+    ///
+    /// Ejected servers are kept until their open duration has passed so that a server that drops
+    /// out of the routing tables and comes back right away is not used prematurely.
+    pub(crate) fn retain(&self, used_addresses: &HashSet<Arc<Address>>) {
+        let open_duration = self.config.open_duration;
+        self.breakers.lock().retain(|address, breaker| {
+            used_addresses.contains(address)
+                || matches!(
+                    breaker.state,
+                    BreakerState::Open { since } if since.elapsed() < open_duration
+                )
+        });
+    }
+}
+
+type Addresses = Vec<Arc<Address>>;
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    fn address(host: &str) -> Arc<Address> {
+        Arc::new(Address::from((host, 7687)))
+    }
+
+    fn hosts(addresses: &[Arc<Address>]) -> Vec<&str> {
+        addresses.iter().map(|a| a.host()).collect()
+    }
+
+    fn breakers(config: CircuitBreakerConfig) -> CircuitBreakers {
+        CircuitBreakers::new(config)
+    }
+
+    #[test]
+    fn test_opens_after_consecutive_failures() {
+        let breakers = breakers(
+            CircuitBreakerConfig::new()
+                .with_consecutive_failure_threshold(3)
+                .unwrap(),
+        );
+        let a = address("a");
+        breakers.record_failure(&a);
+        breakers.record_failure(&a);
+        breakers.record_success(&a);
+        breakers.record_failure(&a);
+        breakers.record_failure(&a);
+        assert_eq!(breakers.state(&a), Some(CircuitState::Closed));
+
+        breakers.record_failure(&a);
+        assert_eq!(breakers.state(&a), Some(CircuitState::Open));
+    }
+
+    #[test]
+    fn test_opens_at_error_rate() {
+        let breakers = breakers(
+            CircuitBreakerConfig::new()
+                .without_consecutive_failure_threshold()
+                .with_error_rate_threshold(0.5, 4)
+                .unwrap(),
+        );
+        let a = address("a");
+        breakers.record_failure(&a);
+        breakers.record_failure(&a);
+        // window not full yet
+        assert_eq!(breakers.state(&a), Some(CircuitState::Closed));
+        breakers.record_success(&a);
+        breakers.record_success(&a);
+        // 2/4 failures, but only failures open the circuit
+        assert_eq!(breakers.state(&a), Some(CircuitState::Closed));
+        breakers.record_failure(&a);
+        assert_eq!(breakers.state(&a), Some(CircuitState::Open));
+    }
+
+    #[test]
+    fn test_error_rate_window_slides() {
+        let breakers = breakers(
+            CircuitBreakerConfig::new()
+                .without_consecutive_failure_threshold()
+                .with_error_rate_threshold(0.75, 4)
+                .unwrap(),
+        );
+        let a = address("a");
+        breakers.record_failure(&a);
+        breakers.record_failure(&a);
+        breakers.record_success(&a);
+        breakers.record_success(&a);
+        // first failure slides out of the window
+        breakers.record_failure(&a);
+        breakers.record_success(&a);
+        assert_eq!(breakers.state(&a), Some(CircuitState::Closed));
+        breakers.record_failure(&a);
+        breakers.record_failure(&a);
+        assert_eq!(breakers.state(&a), Some(CircuitState::Open));
+    }
+
+    #[test]
+    fn test_open_server_is_not_selected() {
+        let breakers = breakers(
+            CircuitBreakerConfig::new()
+                .with_consecutive_failure_threshold(1)
+                .unwrap()
+                .with_open_duration(Duration::from_secs(3600)),
+        );
+        let (a, b) = (address("a"), address("b"));
+        breakers.record_failure(&a);
+
+        let (probes, usable) = breakers.select(&[Arc::clone(&a), Arc::clone(&b)]);
+        assert!(probes.is_empty());
+        assert_eq!(hosts(&usable), ["b"]);
+    }
+
+    #[test]
+    fn test_all_servers_open_ignores_breaker() {
+        let breakers = breakers(
+            CircuitBreakerConfig::new()
+                .with_consecutive_failure_threshold(1)
+                .unwrap()
+                .with_open_duration(Duration::from_secs(3600)),
+        );
+        let (a, b) = (address("a"), address("b"));
+        breakers.record_failure(&a);
+        breakers.record_failure(&b);
+
+        let (probes, usable) = breakers.select(&[Arc::clone(&a), Arc::clone(&b)]);
+        assert!(probes.is_empty());
+        assert_eq!(hosts(&usable), ["a", "b"]);
+    }
+
+    #[rstest]
+    #[case::probe_succeeds(false, CircuitState::Closed)]
+    #[case::probe_fails(true, CircuitState::Open)]
+    fn test_half_open_probe(#[case] probe_fails: bool, #[case] expected: CircuitState) {
+        let breakers = breakers(
+            CircuitBreakerConfig::new()
+                .with_consecutive_failure_threshold(1)
+                .unwrap()
+                .with_open_duration(Duration::ZERO),
+        );
+        let (a, b) = (address("a"), address("b"));
+        breakers.record_failure(&a);
+        assert_eq!(breakers.state(&a), Some(CircuitState::Open));
+
+        let (probes, usable) = breakers.select(&[Arc::clone(&a), Arc::clone(&b)]);
+        assert_eq!(hosts(&probes), ["a"]);
+        assert_eq!(hosts(&usable), ["b"]);
+        assert_eq!(breakers.state(&a), Some(CircuitState::HalfOpen));
+
+        if probe_fails {
+            breakers.record_failure(&a);
+        } else {
+            breakers.record_success(&a);
+        }
+        assert_eq!(breakers.state(&a), Some(expected));
+    }
+
+    #[test]
+    fn test_only_one_probe_at_a_time() {
+        let breakers = breakers(
+            CircuitBreakerConfig::new()
+                .with_consecutive_failure_threshold(1)
+                .unwrap()
+                .with_open_duration(Duration::from_millis(200)),
+        );
+        let (a, b) = (address("a"), address("b"));
+        breakers.record_failure(&a);
+        std::thread::sleep(Duration::from_millis(200));
+
+        let (probes, _) = breakers.select(&[Arc::clone(&a), Arc::clone(&b)]);
+        assert_eq!(hosts(&probes), ["a"]);
+        let (probes, usable) = breakers.select(&[Arc::clone(&a), Arc::clone(&b)]);
+        assert!(probes.is_empty());
+        assert_eq!(hosts(&usable), ["b"]);
+    }
+
+    #[rstest]
+    #[case::still_ejected(Duration::from_secs(30), true)]
+    #[case::ejection_expired(Duration::ZERO, false)]
+    fn test_states_and_retain(#[case] open_duration: Duration, #[case] keeps_ejected: bool) {
+        let breakers = breakers(
+            CircuitBreakerConfig::new()
+                .with_consecutive_failure_threshold(1)
+                .unwrap()
+                .with_open_duration(open_duration),
+        );
+        let (a, b, c) = (address("a"), address("b"), address("c"));
+        breakers.record_failure(&a);
+        breakers.record_success(&b);
+        breakers.record_success(&c);
+        assert_eq!(
+            breakers.states(),
+            HashMap::from([
+                (Arc::clone(&a), CircuitState::Open),
+                (Arc::clone(&b), CircuitState::Closed),
+                (Arc::clone(&c), CircuitState::Closed),
+            ])
+        );
+
+        breakers.retain(&HashSet::from([Arc::clone(&b)]));
+        let mut expected = HashMap::from([(b, CircuitState::Closed)]);
+        if keeps_ejected {
+            expected.insert(a, CircuitState::Open);
+        }
+        assert_eq!(breakers.states(), expected);
+    }
+
+    #[rstest]
+    #[case(0.0, 1)]
+    #[case(-0.1, 1)]
+    #[case(1.1, 1)]
+    #[case(f64::NAN, 1)]
+    #[case(0.5, 0)]
+    fn test_invalid_error_rate_threshold(#[case] rate: f64, #[case] window: usize) {
+        let config = CircuitBreakerConfig::new();
+        let err = config
+            .clone()
+            .with_error_rate_threshold(rate, window)
+            .unwrap_err();
+        assert_eq!(err.builder, config);
+    }
+
+    #[test]
+    fn test_invalid_consecutive_failure_threshold() {
+        let config = CircuitBreakerConfig::new();
+        let err = config
+            .clone()
+            .with_consecutive_failure_threshold(0)
+            .unwrap_err();
+        assert_eq!(err.builder, config);
+    }
+}
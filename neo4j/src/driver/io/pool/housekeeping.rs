@@ -0,0 +1,224 @@
+// Copyright Rouven Bauer
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Weak};
+use std::thread;
+use std::time::Duration;
+
+use log::{debug, warn};
+use parking_lot::{Condvar, Mutex};
+use rand::RngExt;
+
+use super::Pool;
+
+// imports for docs
+#[allow(unused)]
+use crate::driver::DriverConfig;
+
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(30);
+const DEFAULT_ROUTING_TABLE_PURGE_DELAY: Duration = Duration::from_secs(30);
+
+/// Configuration of the driver's background housekeeping thread.
+///
+/// Without housekeeping, the driver only checks idle connections when it's about to hand them
+/// out.
+/// With housekeeping, a background thread periodically sweeps all connection pools:
+///  * It closes (sending `GOODBYE`) idle connections that are older than
+///    [`DriverConfig::with_max_connection_lifetime()`].
+///    See [`HousekeepingConfig::with_max_connection_lifetime_jitter()`] to spread out their
+///    expiry.
+///  * It liveness-checks (sending `RESET`) idle connections that have been idle for longer than
+///    [`DriverConfig::with_idle_time_before_connection_test()`], closing those that fail.
+///  * When routing, it forgets routing tables that have been expired for longer than
+///    [`HousekeepingConfig::with_routing_table_purge_delay()`] and closes the connection pools of
+///    all servers that are no longer part of any routing table.
+///  * It refills pools to [`DriverConfig::with_min_idle_connections()`].
+///
+/// Connections in use are never touched.
+/// The thread stops once the driver (including all owned sessions) has been dropped.
+///
+/// Register with [`DriverConfig::with_housekeeping()`].
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+///
+/// use neo4j::driver::{DriverConfig, HousekeepingConfig};
+///
+/// let housekeeping = HousekeepingConfig::new()
+///     .with_interval(Duration::from_secs(10))
+///     .with_max_connection_lifetime_jitter(Duration::from_secs(60));
+/// let config = DriverConfig::new()
+///     .with_max_connection_lifetime(Duration::from_secs(1800))
+///     .with_housekeeping(housekeeping);
+/// # drop(config);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HousekeepingConfig {
+    interval: Duration,
+    max_connection_lifetime_jitter: Duration,
+    routing_table_purge_delay: Duration,
+}
+
+impl Default for HousekeepingConfig {
+    fn default() -> Self {
+        Self {
+            interval: DEFAULT_INTERVAL,
+            max_connection_lifetime_jitter: Duration::ZERO,
+            routing_table_purge_delay: DEFAULT_ROUTING_TABLE_PURGE_DELAY,
+        }
+    }
+}
+
+impl HousekeepingConfig {
+    /// Create a new housekeeping configuration with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How long the thread pauses between two sweeps.
+    ///
+    /// Default: `30` seconds.
+    #[inline]
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Close connections up to `jitter` before they reach their maximum lifetime.
+    ///
+    /// When opening a connection, the driver draws a random duration between zero and `jitter`
+    /// and subtracts it from [`DriverConfig::with_max_connection_lifetime()`] for that connection.
+    /// This way, connections opened at the same time (e.g., after a deployment) don't all expire
+    /// at once.
+    ///
+    /// Default: no jitter.
+    #[inline]
+    pub fn with_max_connection_lifetime_jitter(mut self, jitter: Duration) -> Self {
+        self.max_connection_lifetime_jitter = jitter;
+        self
+    }
+
+    /// Don't close connections before they reach their maximum lifetime.
+    ///
+    /// This is the *default*.
+    #[inline]
+    pub fn without_max_connection_lifetime_jitter(mut self) -> Self {
+        self.max_connection_lifetime_jitter = Duration::ZERO;
+        self
+    }
+
+    /// How long a routing table must have been expired before the driver forgets it.
+    ///
+    /// Only applies when routing.
+    ///
+    /// Default: `30` seconds.
+    #[inline]
+    pub fn with_routing_table_purge_delay(mut self, delay: Duration) -> Self {
+        self.routing_table_purge_delay = delay;
+        self
+    }
+
+    pub(crate) fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub(crate) fn routing_table_purge_delay(&self) -> Duration {
+        self.routing_table_purge_delay
+    }
+
+    /// Draw how much earlier than its maximum lifetime a new connection expires.
+    pub(crate) fn draw_lifetime_jitter(&self) -> Duration {
+        if self.max_connection_lifetime_jitter.is_zero() {
+            return Duration::ZERO;
+        }
+        rand::rng().random_range(Duration::ZERO..=self.max_connection_lifetime_jitter)
+    }
+}
+
+/// Handle to the background thread sweeping a [`Pool`].
+///
+/// The thread only holds a weak reference to the pool.
+/// It stops when the handle is dropped together with the pool.
+#[derive(Debug)]
+pub(crate) struct Housekeeper {
+    stopped: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl Housekeeper {
+    pub(crate) fn start(pool: Weak<Pool>, interval: Duration) -> Option<Self> {
+        let stopped = Arc::new((Mutex::new(false), Condvar::new()));
+        let spawned = thread::Builder::new()
+            .name(String::from("neo4j-pool-housekeeping"))
+            .spawn({
+                let stopped = Arc::clone(&stopped);
+                move || Self::run(pool, interval, &stopped)
+            });
+        match spawned {
+            Ok(_) => Some(Self { stopped }),
+            Err(err) => {
+                warn!("failed to spawn housekeeping thread: {err}");
+                None
+            }
+        }
+    }
+
+    fn run(pool: Weak<Pool>, interval: Duration, stopped: &(Mutex<bool>, Condvar)) {
+        debug!("housekeeping thread started");
+        loop {
+            {
+                let mut stopped_lock = stopped.0.lock();
+                if !*stopped_lock {
+                    stopped.1.wait_for(&mut stopped_lock, interval);
+                }
+                if *stopped_lock {
+                    break;
+                }
+            }
+            // Only fails while the pool is still being constructed:
+            // dropping the pool drops the handle, which stops the thread.
+            let Some(pool) = pool.upgrade() else {
+                continue;
+            };
+            pool.housekeeping();
+        }
+        debug!("housekeeping thread stopped");
+    }
+}
+
+impl Drop for Housekeeper {
+    fn drop(&mut self) {
+        // Not joining the thread: it might be the one dropping the pool.
+        *self.stopped.0.lock() = true;
+        self.stopped.1.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case::no_jitter(Duration::ZERO)]
+    #[case::jitter(Duration::from_secs(10))]
+    #[case::jitter_exceeds_lifetime(Duration::from_secs(120))]
+    fn test_draw_lifetime_jitter(#[case] jitter: Duration) {
+        let config = HousekeepingConfig::new().with_max_connection_lifetime_jitter(jitter);
+        for _ in 0..100 {
+            assert!(config.draw_lifetime_jitter() <= jitter);
+        }
+    }
+}
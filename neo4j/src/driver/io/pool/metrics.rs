@@ -0,0 +1,213 @@
+// Copyright Rouven Bauer
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::time::Duration;
+
+use crate::address_::Address;
+
+// imports for docs
+#[allow(unused)]
+use crate::driver::{Driver, DriverConfig};
+
+/// A snapshot of the state of the connection pool for a single server address.
+///
+/// See [`Driver::get_connection_pool_metrics()`] and
+/// [`Driver::get_all_connection_pool_metrics()`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ConnectionPoolMetrics {
+    /// Number of connections currently borrowed from the pool.
+    pub in_use: usize,
+    /// Number of connections currently idling in the pool.
+    pub idle: usize,
+    /// Number of connections currently being established.
+    pub creating: usize,
+    /// Number of acquisitions currently waiting for a connection to this address to become
+    /// available.
+    ///
+    /// When routing, an acquisition that could be served by several servers is counted for each
+    /// of them.
+    pub pending_acquisitions: usize,
+    /// Total number of connections successfully acquired from this pool.
+    pub acquisitions: u64,
+    /// Total time spent acquiring the connections counted in
+    /// [`ConnectionPoolMetrics::acquisitions`].
+    pub total_acquisition_time: Duration,
+    /// Longest time a single successful acquisition took.
+    pub max_acquisition_time: Duration,
+    /// Smoothed network round-trip time to the server.
+    ///
+    /// Measured during the Bolt handshake of each new connection and smoothed like TCP's SRTT
+    /// (RFC 6298).
+    /// [`None`] if no connection has been established yet.
+    pub round_trip_time: Option<Duration>,
+}
+
+impl ConnectionPoolMetrics {
+    /// Average time a successful acquisition took.
+    ///
+    /// Returns [`None`] if no connection has been acquired yet.
+    pub fn mean_acquisition_time(&self) -> Option<Duration> {
+        if self.acquisitions == 0 {
+            return None;
+        }
+        let nanos = self.total_acquisition_time.as_nanos() / u128::from(self.acquisitions);
+        Some(Duration::from_nanos(
+            u64::try_from(nanos).unwrap_or(u64::MAX),
+        ))
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct AcquisitionStats {
+    count: u64,
+    total: Duration,
+    max: Duration,
+}
+
+impl AcquisitionStats {
+    pub(crate) fn record(&mut self, duration: Duration) {
+        self.count = self.count.saturating_add(1);
+        self.total = self.total.saturating_add(duration);
+        self.max = self.max.max(duration);
+    }
+
+    pub(crate) fn apply_to(&self, metrics: &mut ConnectionPoolMetrics) {
+        metrics.acquisitions = self.count;
+        metrics.total_acquisition_time = self.total;
+        metrics.max_acquisition_time = self.max;
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct RoundTripStats {
+    smoothed: Option<Duration>,
+}
+
+impl RoundTripStats {
+    pub(crate) fn record(&mut self, rtt: Duration) {
+        self.smoothed = Some(match self.smoothed {
+            None => rtt,
+            Some(smoothed) => (smoothed * 7 + rtt) / 8,
+        });
+    }
+
+    pub(crate) fn apply_to(&self, metrics: &mut ConnectionPoolMetrics) {
+        metrics.round_trip_time = self.smoothed;
+    }
+}
+
+/// Receives events from the driver's connection pool.
+///
+/// All methods have a default implementation that does nothing.
+/// So implementors only need to override the events they are interested in.
+///
+/// Register an observer with [`DriverConfig::with_connection_pool_observer()`].
+///
+/// **⚠️ WARNING**:
+/// The methods are called synchronously from within the driver's connection pool.
+/// Implementations must therefore be fast and must not block.
+/// Further, they must not interact with the driver they are used with to avoid deadlocks.
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// use neo4j::address::Address;
+/// use neo4j::driver::{ConnectionPoolObserver, DriverConfig};
+///
+/// #[derive(Debug, Default)]
+/// struct CountingObserver {
+///     timeouts: AtomicUsize,
+/// }
+///
+/// impl ConnectionPoolObserver for CountingObserver {
+///     fn on_acquisition_timed_out(&self, _address: Option<&Address>) {
+///         self.timeouts.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// let observer = Arc::new(CountingObserver::default());
+/// let config = DriverConfig::new().with_connection_pool_observer(observer.clone());
+/// # drop(config);
+/// ```
+#[allow(unused_variables)]
+pub trait ConnectionPoolObserver: Send + Sync + Debug {
+    /// Called after a new connection has been established and authenticated.
+    fn on_connection_created(&self, address: &Address) {}
+
+    /// Called when a connection has been closed and removed from the pool.
+    fn on_connection_closed(&self, address: &Address) {}
+
+    /// Called when a connection has been handed out by the pool.
+    ///
+    /// `acquisition_time` is the time it took to obtain the connection, including waiting for
+    /// room in the pool and establishing a new connection if necessary.
+    fn on_connection_acquired(&self, address: &Address, acquisition_time: Duration) {}
+
+    /// Called when a connection has been returned to the pool.
+    fn on_connection_released(&self, address: &Address) {}
+
+    /// Called when acquiring a connection failed because the connection acquisition timeout
+    /// (see [`DriverConfig::with_connection_acquisition_timeout()`]) elapsed while waiting for
+    /// room in the pool.
+    ///
+    /// `address` is [`None`] if the driver was waiting for a connection to any of several servers
+    /// (routing).
+    fn on_acquisition_timed_out(&self, address: Option<&Address>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_acquisition_stats() {
+        let mut stats = AcquisitionStats::default();
+        let mut metrics = ConnectionPoolMetrics::default();
+        stats.apply_to(&mut metrics);
+        assert_eq!(metrics.mean_acquisition_time(), None);
+
+        stats.record(Duration::from_millis(10));
+        stats.record(Duration::from_millis(30));
+        stats.record(Duration::from_millis(20));
+        stats.apply_to(&mut metrics);
+
+        assert_eq!(metrics.acquisitions, 3);
+        assert_eq!(metrics.total_acquisition_time, Duration::from_millis(60));
+        assert_eq!(metrics.max_acquisition_time, Duration::from_millis(30));
+        assert_eq!(
+            metrics.mean_acquisition_time(),
+            Some(Duration::from_millis(20))
+        );
+    }
+
+    #[test]
+    fn test_round_trip_stats() {
+        let mut stats = RoundTripStats::default();
+        let mut metrics = ConnectionPoolMetrics::default();
+        stats.apply_to(&mut metrics);
+        assert_eq!(metrics.round_trip_time, None);
+
+        stats.record(Duration::from_millis(16));
+        stats.apply_to(&mut metrics);
+        assert_eq!(metrics.round_trip_time, Some(Duration::from_millis(16)));
+
+        stats.record(Duration::from_millis(32));
+        stats.apply_to(&mut metrics);
+        assert_eq!(metrics.round_trip_time, Some(Duration::from_millis(18)));
+    }
+}
@@ -15,7 +15,7 @@
 use std::collections::HashMap;
 use std::mem;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant as StdInstant};
 
 use log::{debug, warn};
 
@@ -26,6 +26,44 @@ use crate::value::ValueReceive;
 
 use thiserror::Error;
 
+// imports for docs
+#[allow(unused)]
+use crate::driver::Driver;
+
+/// A snapshot of a routing table held by the driver.
+///
+/// See [`Driver::routing_table()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RoutingTableSnapshot {
+    /// The database the routing table is for.
+    ///
+    /// [`None`] if the server didn't name the database (servers before Neo4j 4.4 don't when
+    /// asked for the home database).
+    pub database: Option<Arc<String>>,
+    /// Servers the driver fetches new routing tables from.
+    pub routers: Vec<Arc<Address>>,
+    /// Servers the driver sends read work to.
+    pub readers: Vec<Arc<Address>>,
+    /// Servers the driver sends write work to.
+    pub writers: Vec<Arc<Address>>,
+    /// How long the routing table is valid after it was fetched, as instructed by the server.
+    pub ttl: Duration,
+    /// When the routing table was fetched.
+    pub last_refresh: StdInstant,
+}
+
+impl RoutingTableSnapshot {
+    /// When the routing table expires and will be fetched again on next use.
+    ///
+    /// Returns [`None`] if the expiry is too far in the future to be represented.
+    /// Note that the driver also fetches a new routing table before it expires if it runs out of
+    /// servers for the requested mode (e.g., because all writers failed).
+    pub fn expires_at(&self) -> Option<StdInstant> {
+        self.last_refresh.checked_add(self.ttl)
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct RoutingTable {
     pub(crate) routers: Vec<Arc<Address>>,
@@ -179,6 +217,29 @@ impl RoutingTable {
         true
     }
 
+    /// Whether the table expired (TTL) at least `delay` ago.
+    pub(crate) fn is_expired_for(&self, delay: Duration) -> bool {
+        self.created.elapsed() > self.ttl.saturating_add(delay)
+    }
+
+    pub(crate) fn snapshot(&self) -> RoutingTableSnapshot {
+        RoutingTableSnapshot {
+            database: self.database.clone(),
+            routers: self.routers.clone(),
+            readers: self.readers.clone(),
+            writers: self.writers.clone(),
+            ttl: self.ttl,
+            last_refresh: self.created.raw(),
+        }
+    }
+
+    /// Forget all readers and writers so the table is fetched again on next use.
+    /// The routers are kept to fetch the new table from.
+    pub(crate) fn invalidate(&mut self) {
+        self.readers.clear();
+        self.writers.clear();
+    }
+
     pub(crate) fn deactivate(&mut self, addr: &Address) {
         self.routers = mem::take(&mut self.routers)
             .into_iter()
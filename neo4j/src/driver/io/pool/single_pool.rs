@@ -14,21 +14,31 @@
 
 use std::borrow::Cow;
 use std::collections::{HashSet, VecDeque};
+#[cfg(not(unix))]
+use std::io;
+use std::mem;
 use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
-use std::time::Duration;
+use std::thread;
+use std::time::{Duration, Instant as StdInstant};
 
-use log::{Level, info, log_enabled};
+use log::{Level, debug, info, log_enabled, warn};
 use parking_lot::lock_api::MutexGuard;
 use parking_lot::{Condvar, Mutex, RawMutex};
 
 use super::super::bolt::message_parameters::{HelloParameters, ReauthParameters};
-use super::super::bolt::{self, AuthResetHandle, OnServerErrorCb, TcpBolt, TcpRW};
+use super::super::bolt::{
+    self, AuthResetHandle, BufTcpStream, OnServerErrorCb, SocketProvider, TcpBolt, TcpRW,
+};
 use super::super::pool::ssr_tracker::SsrTracker;
 use super::PoolConfig;
+use super::metrics::{
+    AcquisitionStats, ConnectionPoolMetrics, ConnectionPoolObserver, RoundTripStats,
+};
 use crate::address_::Address;
 use crate::driver::config::AuthConfig;
 use crate::driver::config::auth::{AuthToken, auth_managers};
+use crate::driver::config::connector::Connector;
 use crate::error_::{Neo4jError, Result};
 use crate::time::Instant;
 
@@ -49,6 +59,15 @@ struct InnerPoolSyncedData {
     reservations: usize,
     borrowed: usize,
     borrowed_auth_reset: HashSet<AuthResetHandle>,
+    pending: usize,
+    // subset of `reservations` opened to reach `min_idle_connections`
+    filling: usize,
+    // a background thread is filling the pool => no need to spawn another one
+    filler_running: bool,
+    // the routing pool dropped this pool => don't open connections just to keep it filled
+    retired: bool,
+    acquisition_stats: AcquisitionStats,
+    round_trip_stats: RoundTripStats,
 }
 
 impl InnerPool {
@@ -62,6 +81,12 @@ impl InnerPool {
             reservations: 0,
             borrowed: 0,
             borrowed_auth_reset,
+            pending: 0,
+            filling: 0,
+            filler_running: false,
+            retired: false,
+            acquisition_stats: Default::default(),
+            round_trip_stats: Default::default(),
         });
         Self {
             address,
@@ -81,14 +106,23 @@ impl InnerPool {
         let mut sync = self.synced.lock();
         sync.reservations -= 1;
         let connection = connection?;
+        sync.round_trip_stats.record(connection.handshake_rtt());
         sync.borrowed += 1;
         assert!(
             sync.borrowed_auth_reset
                 .insert(connection.auth_reset_handler())
         );
+        drop(sync);
+        self.notify_observer(|observer| observer.on_connection_created(&self.address));
         Ok(connection)
     }
 
+    fn notify_observer(&self, notify: impl FnOnce(&dyn ConnectionPoolObserver)) {
+        if let Some(observer) = &self.config.observer {
+            notify(observer.as_ref())
+        }
+    }
+
     fn open_new(
         &self,
         deadline: Option<Instant>,
@@ -108,6 +142,9 @@ impl InnerPool {
         let address = Arc::clone(&self.address);
         let mut connection = self.open_socket(address, deadline)?;
         connection.set_telemetry_enabled(self.config.telemetry);
+        if let Some(housekeeping) = &self.config.housekeeping {
+            connection.set_lifetime_jitter(housekeeping.draw_lifetime_jitter());
+        }
 
         connection.hello(HelloParameters::new(
             &self.config.user_agent,
@@ -130,6 +167,12 @@ impl InnerPool {
     }
 
     fn open_socket(&self, address: Arc<Address>, deadline: Option<Instant>) -> Result<TcpBolt> {
+        if let Some(connector) = &self.config.connector {
+            return self.open_custom_socket(connector.as_ref(), address, deadline);
+        }
+        if address.unix_socket_path().is_some() {
+            return self.open_unix_socket(address, deadline);
+        }
         let mut last_err = None;
         for address in address.fully_resolve(self.config.resolver.as_deref())? {
             last_err = match address {
@@ -158,13 +201,80 @@ impl InnerPool {
         last_err.expect("resolve_address_fully returned empty iterator")
     }
 
-    #[cfg(feature = "_internal_testkit_backend")]
+    fn open_custom_socket(
+        &self,
+        connector: &dyn Connector,
+        address: Arc<Address>,
+        deadline: Option<Instant>,
+    ) -> Result<TcpBolt> {
+        // the connector is in charge of how to reach the address => no DNS resolution
+        self.open_unresolved_socket(|| bolt::CustomConnector(connector), address, deadline)
+    }
+
+    #[cfg(unix)]
+    fn open_unix_socket(
+        &self,
+        address: Arc<Address>,
+        deadline: Option<Instant>,
+    ) -> Result<TcpBolt> {
+        self.open_unresolved_socket(|| bolt::UnixConnector, address, deadline)
+    }
+
+    #[cfg(not(unix))]
+    fn open_unix_socket(
+        &self,
+        address: Arc<Address>,
+        _deadline: Option<Instant>,
+    ) -> Result<TcpBolt> {
+        Err(Neo4jError::connect_error(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("Unix domain sockets are not supported on this platform: {address}"),
+        )))
+    }
+
+    fn open_unresolved_socket<S: SocketProvider<BuffRW = BufTcpStream>>(
+        &self,
+        socket_provider: impl Fn() -> S,
+        address: Arc<Address>,
+        deadline: Option<Instant>,
+    ) -> Result<TcpBolt> {
+        let mut last_err = None;
+        for address in address.custom_resolve(self.config.resolver.as_deref())? {
+            match bolt::open(
+                socket_provider(),
+                address,
+                deadline,
+                self.config.connection_timeout,
+                self.config.keep_alive,
+                self.config.tls_config.clone(),
+            ) {
+                Ok(connection) => return Ok(connection),
+                Err(err) => {
+                    info!("failed to open connection: {err}");
+                    last_err = Some(err);
+                }
+            }
+        }
+        Err(last_err.expect("custom_resolve returned empty iterator"))
+    }
+
     pub(crate) fn address(&self) -> &Arc<Address> {
         &self.address
     }
 }
 
-#[derive(Debug)]
+impl Drop for InnerPool {
+    fn drop(&mut self) {
+        let idle = mem::take(&mut self.synced.get_mut().raw_pool);
+        for mut connection in idle {
+            connection.close();
+            self.ssr_tracker.remove_connection(&connection);
+            self.notify_observer(|observer| observer.on_connection_closed(&self.address));
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub(crate) struct SimplePool(Arc<InnerPool>);
 
 impl SimplePool {
@@ -189,8 +299,16 @@ impl SimplePool {
                 if self.has_room(&synced) {
                     synced.reservations += 1;
                     break;
-                } else {
-                    self.wait_for_room(deadline, &mut synced)?;
+                }
+                synced.pending += 1;
+                let res = self.wait_for_room(deadline, &mut synced);
+                synced.pending -= 1;
+                if let Err(err) = res {
+                    drop(synced);
+                    self.notify_observer(|observer| {
+                        observer.on_acquisition_timed_out(Some(&self.address))
+                    });
+                    return Err(err);
                 }
             }
         }
@@ -237,9 +355,10 @@ impl SimplePool {
         Some(UnpreparedSinglePooledBolt::new(None, Arc::clone(&self.0)))
     }
 
-    pub(crate) fn in_use(&self) -> usize {
-        let synced = self.synced.lock();
-        synced.borrowed + synced.reservations
+    /// Mark an acquisition as waiting for this pool until the returned guard is dropped.
+    pub(crate) fn pending_acquisition(&self) -> PendingAcquisition {
+        self.synced.lock().pending += 1;
+        PendingAcquisition(Arc::clone(&self.0))
     }
 
     pub(crate) fn reset_all_auth(&self) {
@@ -260,16 +379,74 @@ impl SimplePool {
     fn acquire_existing(&self, synced: &mut InnerPoolSyncedData) -> Option<PoolElement> {
         let connection = synced.raw_pool.pop_front();
         if let Some(connection) = connection.as_ref() {
-            synced.borrowed += 1;
-            assert!(
-                synced
-                    .borrowed_auth_reset
-                    .insert(connection.auth_reset_handler())
-            );
+            Self::mark_borrowed(synced, connection);
         }
         connection
     }
 
+    fn mark_borrowed(synced: &mut InnerPoolSyncedData, connection: &PoolElement) {
+        synced.borrowed += 1;
+        assert!(
+            synced
+                .borrowed_auth_reset
+                .insert(connection.auth_reset_handler())
+        );
+    }
+
+    /// Close idle connections past their (jittered) lifetime and liveness-check idle connections
+    /// that have been idle for longer than `idle_time_before_connection_test`.
+    ///
+    /// The connections being checked are borrowed from the pool for the time being, so
+    /// acquisitions can proceed concurrently.
+    pub(crate) fn sweep(&self) {
+        let idle_time_before_connection_test = self.config.idle_time_before_connection_test;
+        let max_lifetime = self.config.max_connection_lifetime;
+        let mut expired = Vec::new();
+        let mut to_check = Vec::new();
+        {
+            let mut synced = self.synced.lock();
+            let idle = mem::replace(
+                &mut synced.raw_pool,
+                VecDeque::with_capacity(self.config.max_connection_pool_size),
+            );
+            for connection in idle {
+                if max_lifetime.is_some_and(|max_lifetime| {
+                    connection
+                        .is_older_than(max_lifetime.saturating_sub(connection.lifetime_jitter()))
+                }) {
+                    Self::mark_borrowed(&mut synced, &connection);
+                    expired.push(connection);
+                } else if idle_time_before_connection_test
+                    .is_some_and(|idle_time| connection.is_idle_for(idle_time))
+                {
+                    Self::mark_borrowed(&mut synced, &connection);
+                    to_check.push(connection);
+                } else {
+                    synced.raw_pool.push_back(connection);
+                }
+            }
+        }
+        for mut connection in expired {
+            connection.debug_log(|| String::from("connection reached max lifetime"));
+            connection.close();
+            Self::release(&self.0, connection);
+        }
+        if let Some(idle_time) = idle_time_before_connection_test {
+            let deadline = self.config.connection_acquisition_deadline();
+            for mut connection in to_check {
+                if let Err(err) = UnpreparedSinglePooledBolt::liveness_check(
+                    &mut connection,
+                    idle_time,
+                    deadline,
+                    None,
+                ) {
+                    connection.debug_log(|| format!("liveness check failed: {err}"));
+                }
+                Self::release(&self.0, connection);
+            }
+        }
+    }
+
     fn release(inner_pool: &Arc<InnerPool>, mut connection: PoolElement) {
         if connection.needs_reset() {
             let res = connection
@@ -290,19 +467,129 @@ impl SimplePool {
             inner_pool.made_room_condition.notify_one();
             drop(lock);
             inner_pool.ssr_tracker.remove_connection(&connection);
+            inner_pool
+                .notify_observer(|observer| observer.on_connection_closed(&inner_pool.address));
+            // replace the connection if the pool dropped below `min_idle_connections`
+            SimplePool(Arc::clone(inner_pool)).fill_in_background();
         } else {
             lock.raw_pool.push_back(connection);
             inner_pool.made_room_condition.notify_one();
         }
     }
 
-    #[cfg(feature = "_internal_testkit_backend")]
+    /// Open new connections until the pool holds at least `min_idle_connections` idle ones or is
+    /// full.
+    pub(crate) fn fill(&self, deadline: Option<Instant>) -> Result<()> {
+        loop {
+            {
+                let mut synced = self.synced.lock();
+                loop {
+                    if synced.retired
+                        || synced.raw_pool.len() >= self.config.min_idle_connections
+                        || !self.has_room(&synced)
+                    {
+                        return Ok(());
+                    }
+                    if self.needs_filling(&synced) {
+                        break;
+                    }
+                    // other threads are opening the missing connections => wait for them
+                    self.wait_for_room(deadline, &mut synced)?;
+                }
+                synced.reservations += 1;
+                synced.filling += 1;
+            }
+            let connection = self.open_new(deadline, SessionAuth::None);
+            let mut synced = self.synced.lock();
+            synced.reservations -= 1;
+            synced.filling -= 1;
+            // wake up fellow fillers as well as acquisitions waiting for room
+            self.made_room_condition.notify_all();
+            let connection = connection?;
+            synced.round_trip_stats.record(connection.handshake_rtt());
+            synced.raw_pool.push_back(connection);
+            drop(synced);
+            self.notify_observer(|observer| observer.on_connection_created(&self.address));
+        }
+    }
+
+    /// Like [`SimplePool::fill()`], but on a separate thread.
+    ///
+    /// Does nothing if the pool doesn't need filling or another thread is already filling it.
+    pub(crate) fn fill_in_background(&self) {
+        {
+            let mut synced = self.synced.lock();
+            if synced.filler_running || !self.needs_filling(&synced) {
+                return;
+            }
+            synced.filler_running = true;
+        }
+        let pool = self.clone();
+        let spawned = thread::Builder::new()
+            .name(String::from("neo4j-pool-filler"))
+            .spawn(move || {
+                debug!("filling connection pool for {}", pool.address);
+                let deadline = pool.config.connection_acquisition_deadline();
+                loop {
+                    let res = pool.fill(deadline);
+                    let mut synced = pool.synced.lock();
+                    if let Err(err) = res {
+                        synced.filler_running = false;
+                        drop(synced);
+                        info!("failed to fill connection pool for {}: {err}", pool.address);
+                        return;
+                    }
+                    // connections closed since `fill` returned didn't spawn a filler of their own
+                    if !pool.needs_filling(&synced) {
+                        synced.filler_running = false;
+                        return;
+                    }
+                }
+            });
+        if let Err(err) = spawned {
+            self.synced.lock().filler_running = false;
+            warn!("failed to spawn thread to fill connection pool: {err}");
+        }
+    }
+
+    /// Mark the pool as no longer used by the driver.
+    ///
+    /// Stops (background) filling; idle connections are closed once the last reference is
+    /// dropped.
+    pub(crate) fn retire(&self) {
+        let mut synced = self.synced.lock();
+        synced.retired = true;
+        // wake up fillers waiting for room so they can give up
+        self.made_room_condition.notify_all();
+    }
+
+    fn needs_filling(&self, synced: &InnerPoolSyncedData) -> bool {
+        !synced.retired
+            && synced.raw_pool.len() + synced.filling < self.config.min_idle_connections
+            && self.has_room(synced)
+    }
+
     pub(crate) fn get_metrics(&self) -> ConnectionPoolMetrics {
         let lock = self.synced.lock();
-        ConnectionPoolMetrics {
-            in_use: lock.borrowed + lock.reservations,
+        let mut metrics = ConnectionPoolMetrics {
+            in_use: lock.borrowed,
             idle: lock.raw_pool.len(),
-        }
+            creating: lock.reservations,
+            pending_acquisitions: lock.pending,
+            ..Default::default()
+        };
+        lock.acquisition_stats.apply_to(&mut metrics);
+        lock.round_trip_stats.apply_to(&mut metrics);
+        metrics
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct PendingAcquisition(Arc<InnerPool>);
+
+impl Drop for PendingAcquisition {
+    fn drop(&mut self) {
+        self.0.synced.lock().pending -= 1;
     }
 }
 
@@ -328,6 +615,7 @@ impl UnpreparedSinglePooledBolt {
     pub(crate) fn prepare(
         mut self,
         deadline: Option<Instant>,
+        acquisition_start: StdInstant,
         idle_time_before_connection_test: Option<Duration>,
         session_auth: SessionAuth,
         on_server_error: OnServerErrorCb<TcpRW>,
@@ -337,7 +625,11 @@ impl UnpreparedSinglePooledBolt {
         match bolt {
             None => {
                 let connection = self.pool.acquire_new(deadline, session_auth)?;
-                Ok(Some(SinglePooledBolt::new(connection, pool)))
+                Ok(Some(SinglePooledBolt::new(
+                    connection,
+                    pool,
+                    acquisition_start,
+                )))
             }
             Some(mut connection) => {
                 // room for health check etc. (return None on failed health check)
@@ -365,10 +657,11 @@ impl UnpreparedSinglePooledBolt {
                     }
                 }
                 match self.reauth(&mut connection, session_auth) {
-                    Ok(Some(())) => Ok(Some(SinglePooledBolt {
+                    Ok(Some(())) => Ok(Some(SinglePooledBolt::new(
+                        connection,
                         pool,
-                        bolt: Some(connection),
-                    })),
+                        acquisition_start,
+                    ))),
                     Ok(None) => {
                         SimplePool::release(&self.pool, connection);
                         Ok(None)
@@ -460,7 +753,15 @@ pub(crate) struct SinglePooledBolt {
 }
 
 impl SinglePooledBolt {
-    fn new(bolt: PoolElement, pool: Arc<InnerPool>) -> Self {
+    fn new(bolt: PoolElement, pool: Arc<InnerPool>, acquisition_start: StdInstant) -> Self {
+        let acquisition_time = acquisition_start.elapsed();
+        pool.synced
+            .lock()
+            .acquisition_stats
+            .record(acquisition_time);
+        pool.notify_observer(|observer| {
+            observer.on_connection_acquired(&pool.address, acquisition_time)
+        });
         Self {
             pool,
             bolt: Some(bolt),
@@ -474,6 +775,8 @@ impl Drop for SinglePooledBolt {
             .bolt
             .take()
             .expect("bolt option should be Some from init to drop");
+        self.pool
+            .notify_observer(|observer| observer.on_connection_released(&self.pool.address));
         SimplePool::release(&self.pool, bolt);
     }
 }
@@ -496,9 +799,108 @@ impl DerefMut for SinglePooledBolt {
     }
 }
 
-#[cfg(feature = "_internal_testkit_backend")]
-#[derive(Debug, Copy, Clone)]
-pub struct ConnectionPoolMetrics {
-    pub in_use: usize,
-    pub idle: usize,
+#[cfg(test)]
+mod tests {
+    use std::io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::driver::config::connector::Transport;
+    use crate::driver::config::load_balancing::LeastConnected;
+
+    /// Counts connection attempts and fails each of them once the gate is opened.
+    #[derive(Debug, Default)]
+    struct GatedConnector {
+        attempts: AtomicUsize,
+        open: Mutex<bool>,
+        opened: Condvar,
+    }
+
+    impl GatedConnector {
+        fn open_gate(&self) {
+            *self.open.lock() = true;
+            self.opened.notify_all();
+        }
+    }
+
+    impl Connector for GatedConnector {
+        fn connect(
+            &self,
+            _address: &Address,
+            _timeout: Option<Duration>,
+        ) -> IoResult<Box<dyn Transport>> {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            let mut open = self.open.lock();
+            while !*open {
+                self.opened.wait(&mut open);
+            }
+            Err(IoError::from(IoErrorKind::ConnectionRefused))
+        }
+    }
+
+    fn pool(connector: Arc<GatedConnector>) -> SimplePool {
+        let config = PoolConfig {
+            routing_context: None,
+            tls_config: None,
+            user_agent: String::from("test"),
+            auth: AuthConfig::Static(Default::default()),
+            max_connection_lifetime: None,
+            idle_time_before_connection_test: None,
+            max_connection_pool_size: 10,
+            min_idle_connections: 1,
+            connection_timeout: None,
+            keep_alive: None,
+            connection_acquisition_timeout: None,
+            resolver: None,
+            notification_filters: Default::default(),
+            telemetry: false,
+            observer: None,
+            connector: Some(connector),
+            load_balancing_strategy: Arc::new(LeastConnected),
+            circuit_breaker: None,
+            housekeeping: None,
+        };
+        SimplePool::new(
+            Arc::new(Address::from(("localhost", 7687))),
+            Arc::new(config),
+            Arc::new(SsrTracker::new()),
+        )
+    }
+
+    fn wait_for_filler(pool: &SimplePool) {
+        let deadline = StdInstant::now() + Duration::from_secs(5);
+        while pool.synced.lock().filler_running {
+            assert!(StdInstant::now() < deadline, "filler didn't finish");
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_fill_in_background_spawns_one_filler() {
+        let connector = Arc::new(GatedConnector::default());
+        let pool = pool(Arc::clone(&connector));
+
+        for _ in 0..3 {
+            pool.fill_in_background();
+        }
+        assert!(pool.synced.lock().filler_running);
+        connector.open_gate();
+        wait_for_filler(&pool);
+
+        assert_eq!(connector.attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_retired_pool_is_not_filled() {
+        let connector = Arc::new(GatedConnector::default());
+        connector.open_gate();
+        let pool = pool(Arc::clone(&connector));
+
+        pool.retire();
+        pool.fill_in_background();
+        assert!(!pool.synced.lock().filler_running);
+        pool.fill(None).unwrap();
+
+        assert_eq!(connector.attempts.load(Ordering::SeqCst), 0);
+    }
 }
@@ -0,0 +1,261 @@
+// Copyright Rouven Bauer
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::eager_result::EagerResult;
+use super::session::SessionConfig;
+use super::transaction::Transaction;
+use super::{Driver, RoutingControl};
+use crate::error_::Result;
+use crate::value::ValueSend;
+
+// imports for docs
+#[allow(unused)]
+use crate::retry::{ExponentialBackoff, RetryPolicy};
+
+/// A query together with the options [`QueryExecutor::execute()`] runs it with.
+///
+/// By default, the query has no parameters, runs against the home database, and is routed to a
+/// writer ([`RoutingControl::Write`]).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    text: String,
+    parameters: HashMap<String, ValueSend>,
+    database: Option<Arc<String>>,
+    routing_control: RoutingControl,
+}
+
+impl Query {
+    /// Create a new query with the given Cypher text and default options.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            parameters: Default::default(),
+            database: None,
+            routing_control: RoutingControl::Write,
+        }
+    }
+
+    /// Configure the query's parameters.
+    #[inline]
+    pub fn with_parameters(mut self, parameters: HashMap<String, ValueSend>) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
+    /// Run the query against the given database.
+    ///
+    /// Always specify the database when possible, to allow the driver to work more efficiently.
+    #[inline]
+    pub fn with_database(mut self, database: Arc<String>) -> Self {
+        self.database = Some(database);
+        self
+    }
+
+    /// Run the query against the user's home database.
+    ///
+    /// This is the *default*.
+    #[inline]
+    pub fn with_default_database(mut self) -> Self {
+        self.database = None;
+        self
+    }
+
+    /// Choose whether the query should be routed to a reader or a writer.
+    #[inline]
+    pub fn with_routing_control(mut self, routing_control: RoutingControl) -> Self {
+        self.routing_control = routing_control;
+        self
+    }
+
+    /// The Cypher text of the query.
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The parameters the query is run with.
+    ///
+    /// See [`Query::with_parameters()`].
+    #[inline]
+    pub fn parameters(&self) -> &HashMap<String, ValueSend> {
+        &self.parameters
+    }
+
+    /// The database the query is run against or [`None`] for the user's home database.
+    ///
+    /// See [`Query::with_database()`] and [`Query::with_default_database()`].
+    #[inline]
+    pub fn database(&self) -> Option<&Arc<String>> {
+        self.database.as_ref()
+    }
+
+    /// Whether the query is routed to a reader or a writer.
+    ///
+    /// See [`Query::with_routing_control()`].
+    #[inline]
+    pub fn routing_control(&self) -> RoutingControl {
+        self.routing_control
+    }
+}
+
+/// The common operations of running work against the DBMS.
+///
+/// Application code that is generic over this trait (instead of taking a [`Driver`]) can be unit
+/// tested with a fake implementation (e.g., `neo4j::testing::FakeQueryExecutor` behind the
+/// `testing` feature).
+///
+/// Implementations run the work once.
+/// To retry work on transient failures, wrap calls with a [`RetryPolicy`].
+///
+/// # Example
+/// ```
+/// use std::collections::HashMap;
+/// use std::sync::Arc;
+///
+/// use neo4j::driver::{Query, QueryExecutor};
+/// use neo4j::retry::{ExponentialBackoff, RetryPolicy};
+/// use neo4j::{value_map, ValueReceive};
+///
+/// fn count_people(executor: &impl QueryExecutor) -> i64 {
+///     let query = Query::new("MATCH (p:Person {name: $name}) RETURN count(p) AS n")
+///         .with_parameters(value_map!({"name": "Alice"}))
+///         .with_database(Arc::new(String::from("neo4j")));
+///     let result = ExponentialBackoff::new()
+///         .execute(|| executor.execute(query.clone()))
+///         .unwrap();
+///     result.into_scalar().unwrap().try_into().unwrap()
+/// }
+///
+/// # let driver = doc_test_utils::get_driver();
+/// assert!(count_people(&driver) >= 0);
+///
+/// let created = driver
+///     .execute_write(Some(Arc::new(String::from("neo4j"))), |tx| {
+///         tx.run("CREATE (p:Person {name: $name})", value_map!({"name": "Bob"}))?;
+///         tx.run("RETURN 1 AS n", HashMap::new())
+///     })
+///     .unwrap();
+/// assert_eq!(created.into_scalar().unwrap(), ValueReceive::Integer(1));
+/// ```
+pub trait QueryExecutor {
+    /// Run a single query in its own transaction and return its result.
+    fn execute(&self, query: Query) -> Result<EagerResult>;
+
+    /// Run `work` in a transaction.
+    ///
+    /// The transaction is committed if `work` returns `Ok` and rolled back otherwise.
+    /// Committing happens right after `work` returns, before this method returns.
+    /// So once this method returns `Ok`, the work has been committed.
+    /// If committing fails, the error is returned instead of `work`'s result.
+    /// If `database` is `None`, the user's home database is used.
+    fn execute_transaction<R>(
+        &self,
+        database: Option<Arc<String>>,
+        routing_control: RoutingControl,
+        work: impl FnOnce(&mut dyn TransactionExecutor) -> Result<R>,
+    ) -> Result<R>;
+
+    /// Run `work` in a transaction routed to a reader.
+    ///
+    /// See [`QueryExecutor::execute_transaction()`].
+    fn execute_read<R>(
+        &self,
+        database: Option<Arc<String>>,
+        work: impl FnOnce(&mut dyn TransactionExecutor) -> Result<R>,
+    ) -> Result<R> {
+        self.execute_transaction(database, RoutingControl::Read, work)
+    }
+
+    /// Run `work` in a transaction routed to a writer.
+    ///
+    /// See [`QueryExecutor::execute_transaction()`].
+    fn execute_write<R>(
+        &self,
+        database: Option<Arc<String>>,
+        work: impl FnOnce(&mut dyn TransactionExecutor) -> Result<R>,
+    ) -> Result<R> {
+        self.execute_transaction(database, RoutingControl::Write, work)
+    }
+}
+
+/// A transaction as seen by the work passed to [`QueryExecutor::execute_transaction()`].
+pub trait TransactionExecutor {
+    /// Run a query inside the transaction and return its result.
+    ///
+    /// The result is fetched completely before this method returns.
+    /// It only becomes durable once the transaction is committed, i.e., when
+    /// [`QueryExecutor::execute_transaction()`] returns `Ok`.
+    fn run(&mut self, query: &str, parameters: HashMap<String, ValueSend>) -> Result<EagerResult>;
+}
+
+/// Queries run through this implementation are
+/// [causally chained](crate#causal-consistency) with each other and with
+/// [`Driver::execute_query()`] calls using the default bookmark manager.
+impl QueryExecutor for Driver {
+    fn execute(&self, query: Query) -> Result<EagerResult> {
+        let Query {
+            text,
+            parameters,
+            database,
+            routing_control,
+        } = query;
+        let builder = self
+            .execute_query(text)
+            .with_parameters(parameters)
+            .with_routing_control(routing_control);
+        match database {
+            Some(database) => builder.with_database(database).run(),
+            None => builder.run(),
+        }
+    }
+
+    fn execute_transaction<R>(
+        &self,
+        database: Option<Arc<String>>,
+        routing_control: RoutingControl,
+        work: impl FnOnce(&mut dyn TransactionExecutor) -> Result<R>,
+    ) -> Result<R> {
+        let config =
+            SessionConfig::new().with_bookmark_manager(self.execute_query_bookmark_manager());
+        let config = match database {
+            Some(database) => config.with_database(database),
+            None => config,
+        };
+        let mut session = self.session(config);
+        session
+            .transaction()
+            .with_routing_control(routing_control)
+            .run(|tx| {
+                let res = work(&mut DriverTransactionExecutor { tx: &tx })?;
+                tx.commit()?;
+                Ok(res)
+            })
+    }
+}
+
+struct DriverTransactionExecutor<'driver, 'tx, 'a> {
+    tx: &'a Transaction<'driver, 'tx>,
+}
+
+impl TransactionExecutor for DriverTransactionExecutor<'_, '_, '_> {
+    fn run(&mut self, query: &str, parameters: HashMap<String, ValueSend>) -> Result<EagerResult> {
+        let mut stream = self.tx.query(query).with_parameters(parameters).run()?;
+        Ok(stream
+            .try_as_eager_result()?
+            .expect("the stream has not been consumed before"))
+    }
+}
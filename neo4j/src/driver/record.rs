@@ -17,7 +17,18 @@ use std::ops::Deref;
 use std::sync::Arc;
 
 use super::io::bolt::BoltRecordFields;
-use crate::value::ValueReceive;
+#[cfg(feature = "serde_json_1")]
+use crate::value::jolt::{JoltError, write_ndjson_line};
+#[cfg(feature = "serde_1")]
+use crate::value::{DeserializeError, from_record};
+use crate::value::{
+    FromValueReceive, FromValueReceiveError, FromValueReceivePathElement, ValueReceive,
+    impl_tuple_all,
+};
+
+// imports for docs
+#[allow(unused)]
+use super::EagerResult;
 
 /// A record is a collection of key-value pairs that represent a single row of a query result.
 #[derive(Debug)]
@@ -33,6 +44,35 @@ impl Record {
         }
     }
 
+    /// Assemble a record from key-value pairs, e.g., as a test fixture.
+    ///
+    /// The order of the entries is preserved.
+    /// Keys are expected to be unique, just like the keys of records received from the server.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use neo4j::driver::Record;
+    /// use neo4j::ValueReceive;
+    ///
+    /// let record = Record::from_entries([
+    ///     (Arc::new(String::from("name")), ValueReceive::String(String::from("Alice"))),
+    ///     (Arc::new(String::from("age")), ValueReceive::Integer(42)),
+    /// ]);
+    ///
+    /// assert_eq!(record.get::<String>("name").unwrap(), "Alice");
+    /// assert_eq!(record.get_index::<i64>(1).unwrap(), 42);
+    /// ```
+    pub fn from_entries(entries: impl IntoIterator<Item = (Arc<String>, ValueReceive)>) -> Self {
+        Self {
+            entries: entries
+                .into_iter()
+                .map(|(key, value)| (key, Some(value)))
+                .collect(),
+        }
+    }
+
     /// Iterate over the keys of the record.
     /// The order of the keys corresponds to the order of the values.
     ///
@@ -208,4 +248,210 @@ impl Record {
             .filter(|(k, _)| k.deref() == key)
             .find_map(|(_, v)| v.take())
     }
+
+    /// Get the value for the given key converted into `T`.
+    ///
+    /// The value is cloned.
+    /// Use [`Record::take_value()`] and [`FromValueReceive::from_value_receive()`] to avoid that.
+    ///
+    /// # Errors
+    /// A [`FromValueReceiveError`] if the key does not exist or the value cannot be converted.
+    /// The error's path starts with the key.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// # let driver = doc_test_utils::get_driver();
+    /// let result = driver
+    ///     .execute_query("RETURN 'Alice' AS name, 42 AS age, null AS nick")
+    ///     .with_database(Arc::new(String::from("neo4j")))
+    ///     .run()
+    ///     .unwrap();
+    ///
+    /// let record = result.into_single().unwrap();
+    /// assert_eq!(record.get::<String>("name").unwrap(), "Alice");
+    /// assert_eq!(record.get::<u8>("age").unwrap(), 42);
+    /// assert_eq!(record.get::<Option<String>>("nick").unwrap(), None);
+    /// assert_eq!(
+    ///     record.get::<String>("age").unwrap_err().to_string(),
+    ///     "at `age`: expected string, found integer"
+    /// );
+    /// assert_eq!(
+    ///     record.get::<String>("four").unwrap_err().to_string(),
+    ///     "at `four`: no such key"
+    /// );
+    /// ```
+    pub fn get<T: FromValueReceive>(&self, key: &str) -> Result<T, FromValueReceiveError> {
+        let path = || FromValueReceivePathElement::Key(String::from(key));
+        let value = self
+            .value(key)
+            .ok_or_else(|| FromValueReceiveError::new("no such key").prepend_path(path()))?;
+        T::from_value_receive(value.clone()).map_err(|e| e.prepend_path(path()))
+    }
+
+    /// Get the value at the given index converted into `T`.
+    ///
+    /// The order of the values corresponds to the order of the keys.
+    /// Values removed by [`Record::take_value()`] still occupy their index.
+    /// Just like [`Record::get()`], the value is cloned.
+    ///
+    /// # Errors
+    /// A [`FromValueReceiveError`] if there is no value at the index or the value cannot be
+    /// converted.
+    /// The error's path starts with the index.
+    pub fn get_index<T: FromValueReceive>(&self, index: usize) -> Result<T, FromValueReceiveError> {
+        let path = || FromValueReceivePathElement::Index(index);
+        let value = self
+            .entries
+            .get(index)
+            .and_then(|(_, value)| value.as_ref())
+            .ok_or_else(|| FromValueReceiveError::new("no value at index").prepend_path(path()))?;
+        T::from_value_receive(value.clone()).map_err(|e| e.prepend_path(path()))
+    }
+
+    /// Deserialize the record into any type implementing [`serde::Deserialize`].
+    ///
+    /// The record behaves like a map from keys to values.
+    /// When deserializing into a sequence (e.g., a tuple), the values are used in order.
+    /// See [`ValueReceive::to()`] for how the values themselves are deserialized.
+    ///
+    /// Requires the `serde_1` feature.
+    ///
+    /// # Errors
+    /// A [`DeserializeError`] carrying the path to the offending value.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize, PartialEq)]
+    /// struct Person {
+    ///     name: String,
+    ///     age: u8,
+    /// }
+    ///
+    /// # let driver = doc_test_utils::get_driver();
+    /// let result = driver
+    ///     .execute_query("RETURN 'Alice' AS name, 42 AS age")
+    ///     .with_database(Arc::new(String::from("neo4j")))
+    ///     .run()
+    ///     .unwrap();
+    ///
+    /// let record = result.into_single().unwrap();
+    /// assert_eq!(
+    ///     record.to::<Person>().unwrap(),
+    ///     Person {
+    ///         name: "Alice".into(),
+    ///         age: 42
+    ///     }
+    /// );
+    /// assert_eq!(
+    ///     record.to::<(String, u8)>().unwrap(),
+    ///     (String::from("Alice"), 42)
+    /// );
+    /// ```
+    #[cfg(feature = "serde_1")]
+    pub fn to<'de, T: serde::Deserialize<'de>>(&'de self) -> Result<T, DeserializeError> {
+        from_record(self)
+    }
+
+    /// Encode the record as a single line of newline-delimited JSON (NDJSON).
+    ///
+    /// The line is a JSON object mapping the record's keys (in order) to their values encoded as
+    /// [Jolt](crate::value::jolt) and is terminated by `\n`.
+    ///
+    /// Requires the `serde_json_1` feature.
+    ///
+    /// # Errors
+    /// A [`JoltError`] if a value cannot be encoded (see [`ValueReceive::to_jolt()`]).
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use neo4j::driver::Record;
+    /// use neo4j::ValueReceive;
+    ///
+    /// let record = Record::from_entries([
+    ///     (Arc::new(String::from("name")), ValueReceive::String(String::from("Alice"))),
+    ///     (Arc::new(String::from("age")), ValueReceive::Integer(42)),
+    /// ]);
+    /// assert_eq!(
+    ///     record.to_ndjson().unwrap(),
+    ///     concat!(r#"{"name":{"U":"Alice"},"age":{"Z":"42"}}"#, "\n")
+    /// );
+    /// ```
+    #[cfg(feature = "serde_json_1")]
+    pub fn to_ndjson(&self) -> Result<String, JoltError> {
+        let mut ndjson = String::new();
+        write_ndjson_line(self, &mut ndjson)?;
+        Ok(ndjson)
+    }
+
+    #[cfg(any(feature = "serde_1", feature = "serde_json_1"))]
+    pub(crate) fn entries_ref(&self) -> impl Iterator<Item = (&str, &ValueReceive)> {
+        self.entries
+            .iter()
+            .filter_map(|(k, v)| v.as_ref().map(|v| (k.as_str(), v)))
+    }
 }
+
+/// Conversion of a [`Record`] into a Rust type.
+///
+/// This is what powers [`EagerResult::into_rows()`].
+///
+/// Implementations are provided for [`Record`] itself (no conversion) and tuples of up to 12
+/// elements of types implementing [`FromValueReceive`].
+/// Tuples are filled with the record's values in order and require the record to have exactly
+/// as many values as the tuple has elements.
+pub trait FromRecord: Sized {
+    /// Try to convert the record into `Self`.
+    ///
+    /// # Errors
+    /// A [`FromValueReceiveError`] whose path starts with the offending column's key.
+    fn from_record(record: Record) -> Result<Self, FromValueReceiveError>;
+}
+
+impl FromRecord for Record {
+    #[inline]
+    fn from_record(record: Record) -> Result<Self, FromValueReceiveError> {
+        Ok(record)
+    }
+}
+
+fn convert_entry<T: FromValueReceive>(
+    key: Arc<String>,
+    value: Option<ValueReceive>,
+) -> Result<T, FromValueReceiveError> {
+    let result = match value {
+        None => Err(FromValueReceiveError::new("value has been taken")),
+        Some(value) => T::from_value_receive(value),
+    };
+    result.map_err(|e| e.prepend_path(FromValueReceivePathElement::Key(String::from(&*key))))
+}
+
+macro_rules! impl_tuple {
+    ( $len:literal => $($idx:tt $ty:ident),+ ) => {
+        impl<$($ty: FromValueReceive),+> FromRecord for ($($ty,)+) {
+            fn from_record(record: Record) -> Result<Self, FromValueReceiveError> {
+                if record.entries.len() != $len {
+                    return Err(FromValueReceiveError::new(format!(
+                        "expected record with {} values, found {}",
+                        $len,
+                        record.entries.len(),
+                    )));
+                }
+                let mut entries = record.entries.into_iter();
+                Ok(($({
+                    let (key, value) = entries.next().expect("length checked above");
+                    convert_entry::<$ty>(key, value)?
+                },)+))
+            }
+        }
+    };
+}
+
+impl_tuple_all!(impl_tuple);
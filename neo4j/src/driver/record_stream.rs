@@ -13,21 +13,27 @@
 // limitations under the License.
 
 use std::borrow::Borrow;
-use std::cell::RefCell;
 use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::iter::FusedIterator;
 use std::mem;
 use std::ops::Deref;
-use std::rc::Rc;
 use std::result;
 use std::sync::{Arc, Weak};
 
 use atomic_refcell::AtomicRefCell;
 use duplicate::duplicate_item;
+use parking_lot::Mutex;
 use thiserror::Error;
 
+#[cfg(feature = "arrow_57")]
+use arrow_array_57::RecordBatch;
+#[cfg(feature = "arrow_57")]
+use arrow_schema_57::SchemaRef;
+
 use super::Record;
+#[cfg(feature = "arrow_57")]
+use super::config::DEFAULT_FETCH_SIZE;
 use super::io::bolt::message_parameters::{DiscardParameters, PullParameters, RunParameters};
 use super::io::bolt::{BoltMeta, BoltRecordFields, ResponseCallbacks};
 use super::summary::Summary;
@@ -35,10 +41,16 @@ use crate::driver::eager_result::EagerResult;
 use crate::driver::io::PooledBolt;
 use crate::error_::{Neo4jError, Result, ServerError};
 use crate::value::ValueReceive;
+#[cfg(feature = "arrow_57")]
+use crate::value::arrow::{ArrowBatchError, ArrowConverter};
+
+// imports for docs
+#[allow(unused)]
+use super::session::SessionConfig;
 
 #[derive(Debug)]
 pub struct RecordStream<'driver> {
-    connection: Rc<RefCell<PooledBolt<'driver>>>,
+    connection: Arc<Mutex<PooledBolt<'driver>>>,
     fetch_size: i64,
     auto_commit: bool,
     listener: Arc<AtomicRefCell<RecordListener>>,
@@ -48,13 +60,13 @@ type BoltMetaCb = Box<dyn FnMut(&mut BoltMeta) + Send + Sync + 'static>;
 
 impl<'driver> RecordStream<'driver> {
     pub(crate) fn new(
-        connection: Rc<RefCell<PooledBolt<'driver>>>,
+        connection: Arc<Mutex<PooledBolt<'driver>>>,
         fetch_size: i64,
         auto_commit: bool,
         error_propagator: Option<SharedErrorPropagator>,
     ) -> Self {
         let listener = Arc::new(AtomicRefCell::new(RecordListener::new(
-            &(*connection).borrow(),
+            &connection.lock(),
             error_propagator.clone(),
         )));
         if let Some(error_propagator) = error_propagator {
@@ -93,9 +105,9 @@ impl<'driver> RecordStream<'driver> {
             Ok(())
         });
 
-        let mut res = self.connection.borrow_mut().run(parameters, callbacks);
+        let mut res = self.connection.lock().run(parameters, callbacks);
         if self.auto_commit {
-            res = res.and_then(|_| self.connection.borrow_mut().write_all(None));
+            res = res.and_then(|_| self.connection.lock().write_all(None));
             if let Err(e) = res {
                 let mut listener = self.listener.borrow_mut();
                 listener.state = RecordListenerState::Done;
@@ -106,7 +118,7 @@ impl<'driver> RecordStream<'driver> {
 
         if let Err(e) = res.and_then(|_| {
             // read until only response(s) to PULL is/are left
-            let mut connection = self.connection.borrow_mut();
+            let mut connection = self.connection.lock();
             let mut res = Ok(());
             while res.is_ok() && connection.expected_reply_len() > 1 {
                 res = connection.read_one(None);
@@ -149,7 +161,7 @@ impl<'driver> RecordStream<'driver> {
                 _ => {}
             }
         }
-        let mut connection_borrow = self.connection.borrow_mut();
+        let mut connection_borrow = self.connection.lock();
         if let Err(err) = connection_borrow.read_all(None) {
             self.listener.borrow_mut().state = RecordListenerState::Error(self.failed_commit(err));
         } else {
@@ -255,6 +267,62 @@ impl<'driver> RecordStream<'driver> {
         }))
     }
 
+    /// Stream the remaining records as Arrow [`RecordBatch`]es.
+    ///
+    /// Each batch holds (at most) as many records as are fetched from the server at once (see
+    /// [`SessionConfig::with_fetch_size()`]), so that large results never need to be held in
+    /// memory at once.
+    /// Unless provided with [`ArrowRecordBatches::with_schema()`], the schema is inferred from the
+    /// records (see [`crate::value::arrow`] for how values are mapped).
+    /// Columns (or struct fields) that only held nulls so far are widened to the type of the
+    /// first non-null values of a later batch, so the schema of later batches may differ.
+    /// Values conflicting with the types of earlier batches fail the conversion.
+    ///
+    /// Requires the `arrow_57` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use neo4j::session::SessionConfig;
+    ///
+    /// # let driver = doc_test_utils::get_driver();
+    /// let mut session = driver.session(
+    ///     SessionConfig::new()
+    ///         .with_database(Arc::new(String::from("neo4j")))
+    ///         .with_fetch_size(1000)
+    ///         .unwrap(),
+    /// );
+    /// let rows = session
+    ///     .transaction()
+    ///     .run(|tx| {
+    ///         let mut stream = tx
+    ///             .query("UNWIND range(1, 2500) AS x RETURN x, toString(x) AS s")
+    ///             .run()?;
+    ///         let mut rows = Vec::new();
+    ///         for batch in stream.arrow_batches() {
+    ///             let batch = batch.unwrap();
+    ///             assert_eq!(batch.num_columns(), 2);
+    ///             rows.push(batch.num_rows());
+    ///         }
+    ///         Ok(rows)
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(rows, vec![1000, 1000, 500]);
+    /// ```
+    #[cfg(feature = "arrow_57")]
+    pub fn arrow_batches(&mut self) -> ArrowRecordBatches<'_, 'driver> {
+        let chunk_size = match usize::try_from(self.fetch_size) {
+            Ok(fetch_size) if fetch_size > 0 => fetch_size,
+            _ => DEFAULT_FETCH_SIZE as usize,
+        };
+        ArrowRecordBatches {
+            converter: ArrowConverter::new(self.keys(), None),
+            stream: self,
+            chunk_size,
+        }
+    }
+
     pub(crate) fn into_bookmark(self) -> Option<String> {
         Arc::try_unwrap(self.listener)
             .unwrap()
@@ -278,10 +346,10 @@ impl<'driver> RecordStream<'driver> {
     fn pull(&mut self, flush: bool) -> Result<()> {
         let callbacks = self.pull_callbacks();
         self.connection
-            .borrow_mut()
+            .lock()
             .pull(PullParameters::new(self.fetch_size, self.qid()), callbacks)?;
         if flush {
-            self.connection.borrow_mut().write_all(None)?;
+            self.connection.lock().write_all(None)?;
         }
         Ok(())
     }
@@ -289,10 +357,10 @@ impl<'driver> RecordStream<'driver> {
     fn discard(&mut self, flush: bool) -> Result<()> {
         let callbacks = self.discard_callbacks();
         self.connection
-            .borrow_mut()
+            .lock()
             .discard(DiscardParameters::new(-1, self.qid()), callbacks)?;
         if flush {
-            self.connection.borrow_mut().write_all(None)?;
+            self.connection.lock().write_all(None)?;
         }
         Ok(())
     }
@@ -380,8 +448,8 @@ impl Iterator for RecordStream<'_> {
             if matches!(
                 AtomicRefCell::borrow(&*self.listener).state,
                 RecordListenerState::Streaming | RecordListenerState::Discarding
-            ) && RefCell::borrow(&self.connection).expects_reply()
-                && let Err(err) = self.connection.borrow_mut().read_one(None)
+            ) && self.connection.lock().expects_reply()
+                && let Err(err) = self.connection.lock().read_one(None)
             {
                 self.listener
                     .borrow_mut()
@@ -665,6 +733,52 @@ impl ErrorPropagator {
     }
 }
 
+/// Iterator over the records of a [`RecordStream`] as Arrow [`RecordBatch`]es.
+///
+/// See [`RecordStream::arrow_batches()`].
+///
+/// Requires the `arrow_57` feature.
+#[cfg(feature = "arrow_57")]
+#[derive(Debug)]
+pub struct ArrowRecordBatches<'stream, 'driver> {
+    stream: &'stream mut RecordStream<'driver>,
+    converter: ArrowConverter,
+    chunk_size: usize,
+}
+
+#[cfg(feature = "arrow_57")]
+impl ArrowRecordBatches<'_, '_> {
+    /// Convert the records according to `schema` instead of inferring it from the records.
+    ///
+    /// The schema must have one field per key of the stream (in the same order).
+    /// Map and property keys that don't appear in the schema are dropped.
+    pub fn with_schema(mut self, schema: SchemaRef) -> Self {
+        self.converter = ArrowConverter::new(self.stream.keys(), Some(schema));
+        self
+    }
+}
+
+#[cfg(feature = "arrow_57")]
+impl Iterator for ArrowRecordBatches<'_, '_> {
+    type Item = result::Result<RecordBatch, ArrowBatchError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let records = match self
+            .stream
+            .by_ref()
+            .take(self.chunk_size)
+            .collect::<Result<Vec<_>>>()
+        {
+            Ok(records) => records,
+            Err(err) => return Some(Err(err.into())),
+        };
+        if records.is_empty() {
+            return None;
+        }
+        Some(self.converter.convert(&records).map_err(Into::into))
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum GetSingleRecordError {
     #[error("no records were found")]
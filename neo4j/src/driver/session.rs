@@ -18,16 +18,16 @@ pub(crate) mod retry;
 
 use atomic_refcell::AtomicRefCell;
 use std::borrow::Borrow;
-use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 use std::marker::PhantomData;
 use std::ops::Deref;
-use std::rc::Rc;
 use std::result::Result as StdResult;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, OnceLock};
 
 use log::{debug, info};
+use parking_lot::Mutex;
 
 use super::config::auth::AuthToken;
 use super::home_db_cache::{HomeDbCache, HomeDbCacheKey};
@@ -35,9 +35,9 @@ use super::io::bolt::message_parameters::{
     BeginParameters, RunParameters, TelemetryAPI, TelemetryParameters,
 };
 use super::io::bolt::{BoltMeta, ResponseCallbacks};
-use super::io::{AcquireConfig, Pool, PooledBolt, UpdateRtArgs, UpdateRtDb};
+use super::io::{AcquireConfig, PoolRef, PooledBolt, UpdateRtArgs, UpdateRtDb};
 use super::record_stream::{ErrorPropagator, RecordStream, SharedErrorPropagator};
-use super::transaction::{Transaction, TransactionTimeout};
+use super::transaction::{Transaction, TransactionTimeout, UnmanagedTransaction};
 use super::{EagerResult, ReducedDriverConfig, RoutingControl};
 use crate::driver::io::SessionAuth;
 use crate::error_::{Neo4jError, Result};
@@ -52,6 +52,8 @@ use retry::RetryPolicy;
 // imports for docs
 #[allow(unused)]
 use super::Driver;
+#[allow(unused)]
+use super::transaction::OwnedTransaction;
 
 /// A session is a container for a series of transactions.
 ///
@@ -72,25 +74,26 @@ use super::Driver;
 ///    The only guarantee given is that the transaction has been successfully committed once all
 ///    results have been consumed.
 ///
-/// See also [`Driver::session()`].
+/// See also [`Driver::session()`] and [`Driver::owned_session()`].
 #[derive(Debug)]
 pub struct Session<'driver> {
     config: InternalSessionConfig,
-    pool: &'driver Pool,
+    pool: PoolRef<'driver>,
     home_db_cache: Arc<HomeDbCache>,
-    driver_config: &'driver ReducedDriverConfig,
+    driver_config: ReducedDriverConfig,
     target_db: Arc<AtomicRefCell<SessionTargetDb>>,
     home_db_cache_key: OnceLock<HomeDbCacheKey>,
-    session_bookmarks: SessionBookmarks,
+    session_bookmarks: Arc<Mutex<SessionBookmarks>>,
+    unmanaged_tx_open: Arc<AtomicBool>,
     current_acquisition_deadline: Option<Instant>,
 }
 
 impl<'driver> Session<'driver> {
     pub(super) fn new(
         config: InternalSessionConfig,
-        pool: &'driver Pool,
+        pool: PoolRef<'driver>,
         home_db_cache: Arc<HomeDbCache>,
-        driver_config: &'driver ReducedDriverConfig,
+        driver_config: ReducedDriverConfig,
     ) -> Self {
         let bookmarks = config.config.bookmarks.clone();
         let manager = config.config.as_ref().bookmark_manager.clone();
@@ -104,7 +107,8 @@ impl<'driver> Session<'driver> {
             driver_config,
             target_db,
             home_db_cache_key: Default::default(),
-            session_bookmarks: SessionBookmarks::new(bookmarks, manager),
+            session_bookmarks: Arc::new(Mutex::new(SessionBookmarks::new(bookmarks, manager))),
+            unmanaged_tx_open: Default::default(),
             current_acquisition_deadline: None,
         }
     }
@@ -148,24 +152,35 @@ impl<'driver> Session<'driver> {
         &'session mut self,
         builder: AutoCommitBuilder<'driver, 'session, Q, KP, P, KM, M, FRes>,
     ) -> Result<R> {
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "neo4j.auto_commit",
+            db.system.name = "neo4j",
+            db.namespace = tracing::field::Empty,
+            neo4j.mode = ?builder.mode,
+        )
+        .entered();
+        self.check_no_unmanaged_transaction()?;
         let mut connection = self.acquire_connection(builder.mode)?;
         connection.telemetry(
             TelemetryParameters::new(TelemetryAPI::AutoCommit),
             ResponseCallbacks::new(),
         )?;
         let mut record_stream = RecordStream::new(
-            Rc::new(RefCell::new(connection)),
+            Arc::new(Mutex::new(connection)),
             self.fetch_size(),
             true,
             None,
         );
         let target_db = AtomicRefCell::borrow(&self.target_db).as_db();
+        #[cfg(feature = "tracing")]
+        span.record("db.namespace", target_db.as_ref().map(|db| db.as_str()));
         let res = record_stream
             .run(
                 RunParameters::new_auto_commit_run(
                     builder.query.as_ref(),
                     Some(builder.param.borrow()),
-                    Some(&*self.session_bookmarks.get_bookmarks_for_work()?),
+                    Some(&*self.session_bookmarks.lock().get_bookmarks_for_work()?),
                     builder.timeout.raw(),
                     Some(builder.meta.borrow()),
                     builder.mode.as_protocol_str(),
@@ -193,7 +208,7 @@ impl<'driver> Session<'driver> {
         };
         let bookmark = record_stream.into_bookmark();
         if let Some(bookmark) = bookmark {
-            self.session_bookmarks.update_bookmarks(bookmark)?;
+            self.session_bookmarks.lock().update_bookmarks(bookmark)?;
         }
         res
     }
@@ -207,6 +222,26 @@ impl<'driver> Session<'driver> {
         TransactionBuilder::new(self)
     }
 
+    /// Begin a transaction that is committed or rolled back explicitly.
+    ///
+    /// This is a shorthand for `session.transaction().begin()`.
+    /// Use [`Session::transaction()`] and [`TransactionBuilder::begin()`] to configure the
+    /// transaction before beginning it.
+    ///
+    /// The returned [`UnmanagedTransaction`] doesn't borrow the session.
+    /// Hence, it can be stored and passed around until it's time to decide whether to commit or
+    /// roll back.
+    /// For an [`OwnedSession`], it doesn't borrow the driver either ([`OwnedTransaction`]).
+    /// However, until the transaction is committed, rolled back, or dropped, the session cannot
+    /// be used for any other work.
+    /// Trying to do so fails with [`Neo4jError::InvalidUsage`].
+    ///
+    /// Prefer [`Session::transaction()`] with [`TransactionBuilder::run_with_retry()`] where
+    /// possible as this way, the driver can retry the transaction on intermittent errors.
+    pub fn begin_transaction(&mut self) -> Result<UnmanagedTransaction<'driver>> {
+        self.transaction().begin()
+    }
+
     fn transaction_run<
         'session,
         KM: Borrow<str> + Debug,
@@ -218,6 +253,51 @@ impl<'driver> Session<'driver> {
         builder: &TransactionBuilder<'driver, 'session, KM, M>,
         receiver: FTx,
     ) -> Result<R> {
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "neo4j.transaction",
+            db.system.name = "neo4j",
+            db.namespace = tracing::field::Empty,
+            neo4j.mode = ?builder.mode,
+        )
+        .entered();
+        let mut tx = self.begin_inner_transaction(builder)?;
+        #[cfg(feature = "tracing")]
+        span.record(
+            "db.namespace",
+            AtomicRefCell::borrow(&self.target_db)
+                .as_db()
+                .as_ref()
+                .map(|db| db.as_str()),
+        );
+        let res = receiver(Transaction::new(&mut tx));
+        let res = match res {
+            Ok(_) => {
+                tx.close()?;
+                res
+            }
+            Err(_) => {
+                if let Err(e) = tx.close() {
+                    info!(
+                        "while propagating user code error: \
+                        ignored tx.close() error in transaction_run: {e}"
+                    )
+                }
+                res
+            }
+        };
+        let bookmark = tx.into_bookmark();
+        if let Some(bookmark) = bookmark {
+            self.session_bookmarks.lock().update_bookmarks(bookmark)?;
+        }
+        res
+    }
+
+    fn begin_inner_transaction<KM: Borrow<str> + Debug, M: Borrow<HashMap<KM, ValueSend>>>(
+        &mut self,
+        builder: &TransactionBuilder<'driver, '_, KM, M>,
+    ) -> Result<InnerTransaction<'driver>> {
+        self.check_no_unmanaged_transaction()?;
         let mut connection = self.acquire_connection(builder.mode)?;
         let error_propagator = SharedErrorPropagator::default();
 
@@ -238,7 +318,7 @@ impl<'driver> Session<'driver> {
         }
         let mut tx =
             InnerTransaction::new(connection, self.fetch_size(), Arc::clone(&error_propagator));
-        let bookmarks = &*self.session_bookmarks.get_bookmarks_for_work()?;
+        let bookmarks = &*self.session_bookmarks.lock().get_bookmarks_for_work()?;
         let parameters = BeginParameters::new(
             Some(bookmarks),
             builder.timeout.raw(),
@@ -266,27 +346,18 @@ impl<'driver> Session<'driver> {
                 })
                 .with_on_failure(ErrorPropagator::make_on_error_cb(error_propagator)),
         )?;
-        let res = receiver(Transaction::new(&mut tx));
-        let res = match res {
-            Ok(_) => {
-                tx.close()?;
-                res
-            }
-            Err(_) => {
-                if let Err(e) = tx.close() {
-                    info!(
-                        "while propagating user code error: \
-                        ignored tx.close() error in transaction_run: {e}"
-                    )
-                }
-                res
-            }
-        };
-        let bookmark = tx.into_bookmark();
-        if let Some(bookmark) = bookmark {
-            self.session_bookmarks.update_bookmarks(bookmark)?;
+        Ok(tx)
+    }
+
+    fn check_no_unmanaged_transaction(&self) -> Result<()> {
+        if self.unmanaged_tx_open.load(Ordering::Acquire) {
+            return Err(Neo4jError::InvalidUsage {
+                message: String::from(
+                    "session cannot be used while one of its unmanaged transactions is open",
+                ),
+            });
         }
-        res
+        Ok(())
     }
 
     fn resolve_db(&mut self) -> Result<()> {
@@ -323,7 +394,7 @@ impl<'driver> Session<'driver> {
         self.pool
             .resolve_home_db(UpdateRtArgs {
                 db: None,
-                bookmarks: Some(&*self.session_bookmarks.get_bookmarks_for_work()?),
+                bookmarks: Some(&*self.session_bookmarks.lock().get_bookmarks_for_work()?),
                 imp_user: self
                     .config
                     .config
@@ -405,7 +476,7 @@ impl<'driver> Session<'driver> {
     ) -> Result<PooledBolt<'driver>> {
         self.current_acquisition_deadline = self.pool.config.connection_acquisition_deadline();
         self.resolve_db()?;
-        let bookmarks = self.session_bookmarks.get_bookmarks_for_work()?;
+        let bookmarks = self.session_bookmarks.lock().get_bookmarks_for_work()?;
         let target = AtomicRefCell::borrow(&self.target_db).target.clone();
         let connection = self.no_resolve_acquire_connection(
             mode,
@@ -474,6 +545,7 @@ impl<'driver> Session<'driver> {
                 },
                 Neo4jError::Disconnect { .. }
                 | Neo4jError::InvalidConfig { .. }
+                | Neo4jError::InvalidUsage { .. }
                 | Neo4jError::Timeout { .. }
                 | Neo4jError::UserCallback { .. }
                 | Neo4jError::ProtocolError { .. } => Err(err),
@@ -529,7 +601,7 @@ impl<'driver> Session<'driver> {
     /// ```
     #[inline]
     pub fn last_bookmarks(&self) -> Arc<Bookmarks> {
-        self.session_bookmarks.get_current_bookmarks()
+        self.session_bookmarks.lock().get_current_bookmarks()
     }
 
     #[inline]
@@ -550,6 +622,14 @@ impl<'driver> Session<'driver> {
     }
 }
 
+/// A [`Session`] that doesn't borrow the [`Driver`] it was created from.
+///
+/// It shares the driver's connection pool and keeps it alive for as long as it exists.
+/// So it can be stored in structs, returned from functions, or sent to other threads.
+///
+/// See [`Driver::owned_session()`].
+pub type OwnedSession = Session<'static>;
+
 /// Builder type to prepare an auto-commit transaction.
 ///
 /// Use [`Session::auto_commit()`] for creating one and call [`AutoCommitBuilder::run()`]
@@ -1142,6 +1222,30 @@ impl<'driver, 'session, KM: Borrow<str> + Debug, M: Borrow<HashMap<KM, ValueSend
         let session = self.session.take().unwrap();
         retry_policy.execute(|| session.transaction_run(&self, &mut receiver))
     }
+
+    /// Begin the transaction and return it instead of running a `receiver` on it.
+    ///
+    /// The returned [`UnmanagedTransaction`] doesn't borrow the session and must be committed
+    /// explicitly.
+    /// Else, it will be rolled back when dropped.
+    ///
+    /// # Errors
+    /// Besides the usual errors when beginning a transaction, a [`Neo4jError::InvalidUsage`] is
+    /// returned if another [`UnmanagedTransaction`] of the same session is still open.
+    ///
+    /// See also [`Session::begin_transaction()`].
+    pub fn begin(mut self) -> Result<UnmanagedTransaction<'driver>> {
+        self.api
+            .borrow_mut()
+            .get_or_insert(TelemetryAPI::UnmanagedTx);
+        let session = self.session.take().unwrap();
+        let tx = session.begin_inner_transaction(&self)?;
+        Ok(UnmanagedTransaction::new(
+            tx,
+            Arc::clone(&session.session_bookmarks),
+            Arc::clone(&session.unmanaged_tx_open),
+        ))
+    }
 }
 
 impl<KM, M: Debug> Debug for TransactionBuilder<'_, '_, KM, M> {
@@ -1162,7 +1266,7 @@ impl<KM, M: Debug> Debug for TransactionBuilder<'_, '_, KM, M> {
 }
 
 #[derive(Debug)]
-enum SessionBookmarks {
+pub(crate) enum SessionBookmarks {
     Unmanaged {
         bookmarks: Arc<Bookmarks>,
     },
@@ -1231,7 +1335,7 @@ impl SessionBookmarks {
         }
     }
 
-    fn update_bookmarks(&mut self, bookmark: String) -> Result<()> {
+    pub(crate) fn update_bookmarks(&mut self, bookmark: String) -> Result<()> {
         match self {
             SessionBookmarks::Unmanaged { bookmarks } => {
                 *bookmarks = Arc::new(Bookmarks::from_raw([bookmark]));
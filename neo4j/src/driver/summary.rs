@@ -71,6 +71,31 @@ impl Summary {
         }
     }
 
+    /// Start building a `Summary`, e.g., as a test fixture.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use neo4j::summary::{Counters, Summary, SummaryQueryType};
+    ///
+    /// let mut counters = Counters::default();
+    /// counters.nodes_created = 1;
+    /// counters.contains_updates = true;
+    /// let summary = Summary::builder()
+    ///     .with_counters(counters)
+    ///     .with_query_type(SummaryQueryType::Write)
+    ///     .with_database("neo4j")
+    ///     .with_result_available_after(Duration::from_millis(2))
+    ///     .build();
+    ///
+    /// assert_eq!(summary.counters.nodes_created, 1);
+    /// assert_eq!(summary.database.as_deref(), Some("neo4j"));
+    /// ```
+    pub fn builder() -> SummaryBuilder {
+        SummaryBuilder::default()
+    }
+
     pub(crate) fn load_run_meta(&mut self, meta: &mut BoltMeta, had_key: bool) -> Result<()> {
         self.result_available_after = meta
             .remove("t_first")
@@ -125,6 +150,105 @@ impl Summary {
     }
 }
 
+/// Builder for [`Summary`]s.
+///
+/// See [`Summary::builder()`].
+/// Unless configured otherwise, all optional fields are [`None`], the counters are all zero, and
+/// there are no notifications or GQL status objects.
+/// The default [`ServerInfo`] points to `localhost:7687` with an empty server agent and protocol
+/// version `0.0`.
+/// Fields not covered by the builder can be set on the built [`Summary`] directly.
+#[derive(Debug, Clone)]
+pub struct SummaryBuilder {
+    result_available_after: Option<Duration>,
+    result_consumed_after: Option<Duration>,
+    counters: Counters,
+    query_type: Option<SummaryQueryType>,
+    database: Option<String>,
+    server_info: ServerInfo,
+}
+
+impl Default for SummaryBuilder {
+    fn default() -> Self {
+        Self {
+            result_available_after: None,
+            result_consumed_after: None,
+            counters: Default::default(),
+            query_type: None,
+            database: None,
+            server_info: ServerInfo {
+                address: Arc::new(Address::from(("localhost", 7687))),
+                server_agent: Default::default(),
+                protocol_version: (0, 0),
+            },
+        }
+    }
+}
+
+impl SummaryBuilder {
+    #[inline]
+    pub fn with_result_available_after(mut self, result_available_after: Duration) -> Self {
+        self.result_available_after = Some(result_available_after);
+        self
+    }
+
+    #[inline]
+    pub fn with_result_consumed_after(mut self, result_consumed_after: Duration) -> Self {
+        self.result_consumed_after = Some(result_consumed_after);
+        self
+    }
+
+    #[inline]
+    pub fn with_counters(mut self, counters: Counters) -> Self {
+        self.counters = counters;
+        self
+    }
+
+    #[inline]
+    pub fn with_query_type(mut self, query_type: SummaryQueryType) -> Self {
+        self.query_type = Some(query_type);
+        self
+    }
+
+    /// Set the name of the database the query was executed against.
+    #[inline]
+    pub fn with_database(mut self, database: impl Into<String>) -> Self {
+        self.database = Some(database.into());
+        self
+    }
+
+    #[inline]
+    pub fn with_server_info(mut self, server_info: ServerInfo) -> Self {
+        self.server_info = server_info;
+        self
+    }
+
+    pub fn build(self) -> Summary {
+        let Self {
+            result_available_after,
+            result_consumed_after,
+            counters,
+            query_type,
+            database,
+            server_info,
+        } = self;
+        Summary {
+            result_available_after,
+            result_consumed_after,
+            counters,
+            notifications: Default::default(),
+            gql_status_objects: Default::default(),
+            profile: None,
+            plan: None,
+            query_type,
+            database,
+            server_info,
+            had_record: false,
+            had_key: false,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 #[non_exhaustive]
 pub struct Counters {
@@ -145,6 +269,23 @@ pub struct Counters {
 }
 
 impl Counters {
+    pub(crate) fn add(&mut self, other: &Counters) {
+        self.nodes_created += other.nodes_created;
+        self.nodes_deleted += other.nodes_deleted;
+        self.relationships_created += other.relationships_created;
+        self.relationships_deleted += other.relationships_deleted;
+        self.properties_set += other.properties_set;
+        self.labels_added += other.labels_added;
+        self.labels_removed += other.labels_removed;
+        self.indexes_added += other.indexes_added;
+        self.indexes_removed += other.indexes_removed;
+        self.constraints_added += other.constraints_added;
+        self.constraints_removed += other.constraints_removed;
+        self.system_updates += other.system_updates;
+        self.contains_updates |= other.contains_updates;
+        self.contains_system_updates |= other.contains_system_updates;
+    }
+
     fn load_meta(meta: &mut BoltMeta) -> Result<Self> {
         let Some(meta) = meta.remove("stats") else {
             return Ok(Counters {
@@ -13,31 +13,40 @@
 // limitations under the License.
 
 use std::borrow::Borrow;
-use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 use std::marker::PhantomData;
+use std::mem;
 use std::ops::Deref;
-use std::rc::Rc;
 use std::result;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use atomic_refcell::AtomicRefCell;
+use log::info;
+use parking_lot::Mutex;
 
 use super::Record;
 use super::eager_result::EagerResult;
 use super::io::PooledBolt;
 use super::io::bolt::ResponseCallbacks;
 use super::io::bolt::message_parameters::{BeginParameters, RunParameters};
+#[cfg(feature = "arrow_57")]
+use super::record_stream::ArrowRecordBatches;
 use super::record_stream::{GetSingleRecordError, RecordStream, SharedErrorPropagator};
+use super::session::SessionBookmarks;
 use crate::error_::{Neo4jError, Result};
 use crate::summary::Summary;
 use crate::value::{ValueReceive, ValueSend};
 
+// imports for docs
+#[allow(unused)]
+use super::session::{OwnedSession, Session, TransactionBuilder};
+
 #[derive(Debug)]
 pub struct Transaction<'driver, 'tx> {
     inner_tx: &'tx mut InnerTransaction<'driver>,
-    drop_result: RefCell<Result<()>>,
+    drop_result: Mutex<Result<()>>,
 }
 
 /// A transaction that can be used to execute queries.
@@ -49,7 +58,7 @@ impl<'driver, 'tx> Transaction<'driver, 'tx> {
     pub(crate) fn new(inner: &'tx mut InnerTransaction<'driver>) -> Self {
         Self {
             inner_tx: inner,
-            drop_result: RefCell::new(Ok(())),
+            drop_result: Mutex::new(Ok(())),
         }
     }
 
@@ -60,19 +69,13 @@ impl<'driver, 'tx> Transaction<'driver, 'tx> {
         &'tx self,
         query: Q,
     ) -> TransactionQueryBuilder<'driver, 'tx, Q, DefaultKey, DefaultParameters> {
-        TransactionQueryBuilder::new(self, query)
-    }
-
-    fn run<Q: AsRef<str>, K: Borrow<str> + Debug, M: Borrow<HashMap<K, ValueSend>>>(
-        &'tx self,
-        builder: TransactionQueryBuilder<'driver, 'tx, Q, K, M>,
-    ) -> Result<TransactionRecordStream<'driver, 'tx>> {
-        let query = builder.query.as_ref();
-        let parameters = builder.parameters.borrow();
-        Ok(TransactionRecordStream(
-            self.inner_tx.run(query, parameters)?,
-            self,
-        ))
+        TransactionQueryBuilder::new(
+            TransactionRef {
+                inner_tx: self.inner_tx,
+                drop_result: &self.drop_result,
+            },
+            query,
+        )
     }
 
     /// Commit the transaction.
@@ -97,6 +100,184 @@ impl<'driver, 'tx> Transaction<'driver, 'tx> {
     }
 }
 
+/// A transaction that is controlled explicitly instead of through a closure.
+///
+/// Get one through [`Session::begin_transaction()`] or [`TransactionBuilder::begin()`].
+/// Unlike [`Transaction`], it doesn't borrow the session it was started from.
+/// So it can be stored and passed around, for example, to commit or roll back based on outside
+/// events.
+/// Bookmarks received when committing are handed back to the session.
+/// While the transaction is open, the session cannot be used for other work.
+///
+/// Besides that, it behaves just like [`Transaction`]:
+/// dropping it without calling [`UnmanagedTransaction::commit()`] or
+/// [`UnmanagedTransaction::rollback()`] will roll it back, swallowing potential errors.
+///
+/// **NOTE:**  
+/// Once any associated function of the transaction or any [`TransactionRecordStream`]
+/// spawned from it returns an error, the transaction is closed.
+/// Use [`UnmanagedTransaction::is_open()`] to check for this.
+///
+/// # Example
+/// ```
+/// use neo4j::transaction::UnmanagedTransaction;
+///
+/// fn create_node(transaction: &UnmanagedTransaction) -> neo4j::Result<()> {
+///     transaction.query("CREATE (n:Node)").run()?.consume()?;
+///     Ok(())
+/// }
+///
+/// # let driver = doc_test_utils::get_driver();
+/// # let mut session = doc_test_utils::get_session(&driver);
+/// let transaction = session.begin_transaction().unwrap();
+/// create_node(&transaction).unwrap();
+/// assert!(transaction.is_open());
+/// // decide later whether to commit or roll back
+/// transaction.commit().unwrap();
+/// ```
+///
+/// See also [`OwnedTransaction`].
+#[derive(Debug)]
+pub struct UnmanagedTransaction<'driver> {
+    inner_tx: InnerTransaction<'driver>,
+    drop_result: Mutex<Result<()>>,
+    session_bookmarks: Arc<Mutex<SessionBookmarks>>,
+    session_tx_open: Arc<AtomicBool>,
+}
+
+/// An [`UnmanagedTransaction`] that doesn't borrow the driver either.
+///
+/// Get one through [`OwnedSession::begin_transaction()`].
+/// It can be stored in structs, returned from functions, or sent to other threads.
+///
+/// # Example
+/// ```no_run
+/// use std::sync::Arc;
+///
+/// use neo4j::driver::Driver;
+/// use neo4j::session::{OwnedSession, SessionConfig};
+/// use neo4j::transaction::OwnedTransaction;
+///
+/// struct Job {
+///     session: OwnedSession,
+///     transaction: Option<OwnedTransaction>,
+/// }
+///
+/// fn start_job(driver: &Driver) -> neo4j::Result<Job> {
+///     let config = SessionConfig::new().with_database(Arc::new(String::from("neo4j")));
+///     let mut session = driver.owned_session(config);
+///     let transaction = session.begin_transaction()?;
+///     transaction.query("CREATE (n:Job)").run()?.consume()?;
+///     Ok(Job {
+///         session,
+///         transaction: Some(transaction),
+///     })
+/// }
+///
+/// # let driver = doc_test_utils::get_driver();
+/// let mut job = start_job(&driver).unwrap();
+/// // ...
+/// job.transaction.take().unwrap().commit().unwrap();
+/// let _bookmarks = job.session.last_bookmarks();
+/// ```
+pub type OwnedTransaction = UnmanagedTransaction<'static>;
+
+impl<'driver> UnmanagedTransaction<'driver> {
+    pub(crate) fn new(
+        inner_tx: InnerTransaction<'driver>,
+        session_bookmarks: Arc<Mutex<SessionBookmarks>>,
+        session_tx_open: Arc<AtomicBool>,
+    ) -> Self {
+        session_tx_open.store(true, Ordering::Release);
+        Self {
+            inner_tx,
+            drop_result: Mutex::new(Ok(())),
+            session_bookmarks,
+            session_tx_open,
+        }
+    }
+
+    /// Prepare a query to be executed.
+    ///
+    /// Use the returned [`TransactionQueryBuilder`] to add parameters and run the query.
+    pub fn query<Q: AsRef<str>>(
+        &self,
+        query: Q,
+    ) -> TransactionQueryBuilder<'driver, '_, Q, DefaultKey, DefaultParameters> {
+        TransactionQueryBuilder::new(
+            TransactionRef {
+                inner_tx: &self.inner_tx,
+                drop_result: &self.drop_result,
+            },
+            query,
+        )
+    }
+
+    /// Prepare a query that takes ownership of the transaction.
+    ///
+    /// Unlike [`UnmanagedTransaction::query()`], the [`UnmanagedRecordStream`] returned by
+    /// [`UnmanagedQueryBuilder::run()`] doesn't borrow the transaction.
+    /// For an [`OwnedTransaction`], this makes it an [`OwnedRecordStream`] that can be sent to
+    /// other threads.
+    /// Once done with the stream, get the transaction back with
+    /// [`UnmanagedRecordStream::into_transaction()`] or end it right away with
+    /// [`UnmanagedRecordStream::commit()`] or [`UnmanagedRecordStream::rollback()`].
+    pub fn into_query<Q: AsRef<str>>(
+        self,
+        query: Q,
+    ) -> UnmanagedQueryBuilder<'driver, Q, DefaultKey, DefaultParameters> {
+        UnmanagedQueryBuilder {
+            tx: self,
+            query,
+            _k: PhantomData,
+            parameters: Default::default(),
+        }
+    }
+
+    /// Check whether the transaction can still be used.
+    ///
+    /// Returns `false` once the transaction failed (e.g., because a query errored or the
+    /// connection broke).
+    /// Committing or rolling back such a transaction is not possible anymore.
+    pub fn is_open(&self) -> bool {
+        self.drop_result.lock().is_ok() && self.inner_tx.is_open()
+    }
+
+    /// Commit the transaction.
+    pub fn commit(mut self) -> Result<()> {
+        mem::replace(self.drop_result.get_mut(), Ok(()))?;
+        self.inner_tx.commit()?;
+        match self.inner_tx.take_bookmark() {
+            Some(bookmark) => self.session_bookmarks.lock().update_bookmarks(bookmark),
+            None => Ok(()),
+        }
+    }
+
+    /// Rollback the transaction.
+    ///
+    /// This is the default behavior when the transaction is dropped.
+    /// However, when dropping the transaction, potential errors will be swallowed.
+    pub fn rollback(mut self) -> Result<()> {
+        match mem::replace(self.drop_result.get_mut(), Ok(())) {
+            Ok(_) => self.inner_tx.rollback(),
+            Err(_) => {
+                // Nothing to do here.
+                // The transaction already failed and doesn't need to be rolled back.
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Drop for UnmanagedTransaction<'_> {
+    fn drop(&mut self) {
+        if let Err(err) = self.inner_tx.close() {
+            info!("ignored tx.close() error while dropping UnmanagedTransaction: {err}");
+        }
+        self.session_tx_open.store(false, Ordering::Release);
+    }
+}
+
 /// A result cursor as returned by [`TransactionQueryBuilder::run()`].
 ///
 /// It implements [`Iterator`] and can be used to iterate over the [`Record`]s.
@@ -106,22 +287,71 @@ impl<'driver, 'tx> Transaction<'driver, 'tx> {
 /// While calling [`drop(stream)`](drop) works fine for this purpose, it will swallow any
 /// outstanding errors.
 /// Therefore, it is recommended to use [`TransactionRecordStream::consume()`] instead.
+///
+/// Like [`Transaction`] and [`RecordStream`], the stream is [`Send`] and [`Sync`].
+/// So it can, for example, be handed to a producer thread that streams records into a channel.
+///
+/// # Example
+/// ```
+/// use std::sync::mpsc;
+/// use std::thread;
+///
+/// # doc_test_utils::with_transaction(|transaction| {
+/// let stream = transaction
+///     .query("UNWIND range(1, 3) AS x RETURN x")
+///     .run()?;
+/// let (sender, receiver) = mpsc::channel();
+/// thread::scope(|scope| {
+///     scope.spawn(move || {
+///         for record in stream {
+///             sender.send(record).unwrap();
+///         }
+///     });
+///     assert_eq!(receiver.iter().count(), 3);
+/// });
+/// # Ok(())
+/// # });
+/// ```
 #[derive(Debug)]
 pub struct TransactionRecordStream<'driver, 'tx>(
     RecordStream<'driver>,
-    &'tx Transaction<'driver, 'tx>,
+    TransactionRef<'driver, 'tx>,
 );
 
 impl Drop for TransactionRecordStream<'_, '_> {
     fn drop(&mut self) {
-        if let Err(err) = self.0.consume()
-            && self.1.drop_result.borrow().is_ok()
-        {
-            let _ = self.1.drop_result.replace(Err(err));
+        if let Err(err) = self.0.consume() {
+            let mut drop_result = self.1.drop_result.lock();
+            if drop_result.is_ok() {
+                *drop_result = Err(err);
+            }
         }
     }
 }
 
+/// The parts of a transaction that queries and their record streams need access to.
+///
+/// Shared by [`Transaction`] and [`UnmanagedTransaction`].
+#[derive(Debug, Clone, Copy)]
+struct TransactionRef<'driver, 'tx> {
+    inner_tx: &'tx InnerTransaction<'driver>,
+    drop_result: &'tx Mutex<Result<()>>,
+}
+
+impl<'driver, 'tx> TransactionRef<'driver, 'tx> {
+    fn run<Q: AsRef<str>, K: Borrow<str> + Debug, M: Borrow<HashMap<K, ValueSend>>>(
+        self,
+        builder: TransactionQueryBuilder<'driver, 'tx, Q, K, M>,
+    ) -> Result<TransactionRecordStream<'driver, 'tx>> {
+        let query = builder.query.as_ref();
+        let parameters = builder.parameters.borrow();
+        Ok(TransactionRecordStream(
+            self.inner_tx.run(query, parameters)?,
+            self,
+        ))
+    }
+}
+
 impl<'driver> TransactionRecordStream<'driver, '_> {
     /// see [`RecordStream::consume()`] (except that this consumes `self`)
     pub fn consume(mut self) -> Result<Option<Summary>> {
@@ -139,6 +369,11 @@ impl<'driver> TransactionRecordStream<'driver, '_> {
     pub fn try_as_eager_result(&mut self) -> Result<Option<EagerResult>> {
         self.0.try_as_eager_result()
     }
+    /// see [`RecordStream::arrow_batches`]
+    #[cfg(feature = "arrow_57")]
+    pub fn arrow_batches(&mut self) -> ArrowRecordBatches<'_, 'driver> {
+        self.0.arrow_batches()
+    }
 
     pub(crate) fn raw_stream_mut(&mut self) -> &mut RecordStream<'driver> {
         &mut self.0
@@ -153,9 +388,138 @@ impl Iterator for TransactionRecordStream<'_, '_> {
     }
 }
 
+/// A result cursor that owns the transaction it was spawned from.
+///
+/// Get one through [`UnmanagedTransaction::into_query()`].
+/// It implements [`Iterator`] and can be used to iterate over the [`Record`]s.
+///
+/// Dropping the stream consumes it (swallowing potential errors) and rolls back the transaction.
+/// Use [`UnmanagedRecordStream::into_transaction()`], [`UnmanagedRecordStream::commit()`], or
+/// [`UnmanagedRecordStream::rollback()`] to end it explicitly instead.
+///
+/// See also [`OwnedRecordStream`].
+#[derive(Debug)]
+pub struct UnmanagedRecordStream<'driver> {
+    stream: RecordStream<'driver>,
+    // Only `None` while the stream is being turned back into its transaction.
+    transaction: Option<UnmanagedTransaction<'driver>>,
+}
+
+/// An [`UnmanagedRecordStream`] that doesn't borrow the driver either.
+///
+/// Get one by running a query through [`OwnedTransaction::into_query()`].
+/// It is [`Send`] and `'static`, so it can be handed to a producer thread that streams records
+/// into a channel.
+///
+/// # Example
+/// ```no_run
+/// use std::sync::mpsc;
+/// use std::thread;
+///
+/// use neo4j::session::SessionConfig;
+///
+/// # let driver = doc_test_utils::get_driver();
+/// let mut session = driver.owned_session(SessionConfig::new());
+/// let mut stream = session
+///     .begin_transaction()
+///     .unwrap()
+///     .into_query("UNWIND range(1, 3) AS x RETURN x")
+///     .run()
+///     .unwrap();
+/// let (sender, receiver) = mpsc::channel();
+/// let producer = thread::spawn(move || {
+///     for record in &mut stream {
+///         sender.send(record?).unwrap();
+///     }
+///     stream.commit()
+/// });
+/// assert_eq!(receiver.iter().count(), 3);
+/// producer.join().unwrap().unwrap();
+/// ```
+pub type OwnedRecordStream = UnmanagedRecordStream<'static>;
+
+impl<'driver> UnmanagedRecordStream<'driver> {
+    /// see [`RecordStream::consume()`]
+    pub fn consume(&mut self) -> Result<Option<Summary>> {
+        self.stream.consume()
+    }
+    /// see [`RecordStream::keys`]
+    pub fn keys(&self) -> Vec<Arc<String>> {
+        self.stream.keys()
+    }
+    /// see [`RecordStream::single`]
+    pub fn single(&mut self) -> result::Result<Result<Record>, GetSingleRecordError> {
+        self.stream.single()
+    }
+    /// see [`RecordStream::try_as_eager_result`]
+    pub fn try_as_eager_result(&mut self) -> Result<Option<EagerResult>> {
+        self.stream.try_as_eager_result()
+    }
+    /// see [`RecordStream::arrow_batches`]
+    #[cfg(feature = "arrow_57")]
+    pub fn arrow_batches(&mut self) -> ArrowRecordBatches<'_, 'driver> {
+        self.stream.arrow_batches()
+    }
+
+    /// Consume the rest of the stream and hand back the transaction.
+    ///
+    /// If consuming the stream fails, the error is returned and the transaction is closed.
+    pub fn into_transaction(self) -> Result<UnmanagedTransaction<'driver>> {
+        let mut transaction = self.finish();
+        mem::replace(transaction.drop_result.get_mut(), Ok(()))?;
+        Ok(transaction)
+    }
+
+    /// Consume the rest of the stream and commit the transaction.
+    pub fn commit(self) -> Result<()> {
+        self.into_transaction()?.commit()
+    }
+
+    /// Consume the rest of the stream and roll back the transaction.
+    ///
+    /// See [`UnmanagedTransaction::rollback()`].
+    pub fn rollback(self) -> Result<()> {
+        self.finish().rollback()
+    }
+
+    fn finish(mut self) -> UnmanagedTransaction<'driver> {
+        let res = self.stream.consume();
+        let transaction = self
+            .transaction
+            .take()
+            .expect("transaction is only taken when finishing the stream");
+        if let Err(err) = res {
+            let mut drop_result = transaction.drop_result.lock();
+            if drop_result.is_ok() {
+                *drop_result = Err(err);
+            }
+        }
+        transaction
+    }
+}
+
+impl Drop for UnmanagedRecordStream<'_> {
+    fn drop(&mut self) {
+        if self.transaction.is_none() {
+            return;
+        }
+        if let Err(err) = self.stream.consume() {
+            info!("ignored stream.consume() error while dropping UnmanagedRecordStream: {err}");
+        }
+    }
+}
+
+impl Iterator for UnmanagedRecordStream<'_> {
+    type Item = Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.stream.next()
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct InnerTransaction<'driver> {
-    connection: Rc<RefCell<PooledBolt<'driver>>>,
+    connection: Arc<Mutex<PooledBolt<'driver>>>,
     bookmark: Arc<AtomicRefCell<Option<String>>>,
     error_propagator: SharedErrorPropagator,
     fetch_size: i64,
@@ -169,7 +533,7 @@ impl<'driver> InnerTransaction<'driver> {
         error_propagator: SharedErrorPropagator,
     ) -> Self {
         Self {
-            connection: Rc::new(RefCell::new(connection)),
+            connection: Arc::new(Mutex::new(connection)),
             bookmark: Default::default(),
             error_propagator,
             fetch_size,
@@ -183,7 +547,7 @@ impl<'driver> InnerTransaction<'driver> {
         eager: bool,
         callbacks: ResponseCallbacks,
     ) -> Result<()> {
-        let mut cx = self.connection.borrow_mut();
+        let mut cx = self.connection.lock();
         cx.begin(parameters, callbacks)?;
         if eager {
             cx.write_all(None)?;
@@ -196,7 +560,7 @@ impl<'driver> InnerTransaction<'driver> {
     pub(crate) fn commit(&mut self) -> Result<()> {
         self.closed = true;
         self.check_error()?;
-        let mut cx = self.connection.borrow_mut();
+        let mut cx = self.connection.lock();
         let bookmark = Arc::clone(&self.bookmark);
         cx.write_all(None)?;
         cx.read_all(None)?;
@@ -216,14 +580,18 @@ impl<'driver> InnerTransaction<'driver> {
             // transaction already failed, nothing to rollback
             return Ok(());
         }
-        let mut cx = self.connection.borrow_mut();
+        let mut cx = self.connection.lock();
         cx.rollback()?;
         cx.write_all(None)?;
         cx.read_all(None)
     }
 
+    pub(crate) fn is_open(&self) -> bool {
+        !self.closed && self.check_error().is_ok() && !self.connection.lock().closed()
+    }
+
     pub(crate) fn close(&mut self) -> Result<()> {
-        if self.check_error().is_err() || self.connection.borrow_mut().closed() {
+        if self.check_error().is_err() || self.connection.lock().closed() {
             self.closed = true;
         }
         if !self.closed {
@@ -233,6 +601,10 @@ impl<'driver> InnerTransaction<'driver> {
     }
 
     pub(crate) fn into_bookmark(self) -> Option<String> {
+        self.take_bookmark()
+    }
+
+    pub(crate) fn take_bookmark(&self) -> Option<String> {
         self.bookmark.borrow_mut().take()
     }
 
@@ -241,7 +613,7 @@ impl<'driver> InnerTransaction<'driver> {
         query: &str,
         parameters: &HashMap<K, ValueSend>,
     ) -> Result<RecordStream<'driver>> {
-        let cx = Rc::clone(&self.connection);
+        let cx = Arc::clone(&self.connection);
 
         let mut record_stream = RecordStream::new(
             cx,
@@ -276,7 +648,7 @@ pub struct TransactionQueryBuilder<
     K: Borrow<str> + Debug,
     M: Borrow<HashMap<K, ValueSend>>,
 > {
-    tx: &'tx Transaction<'driver, 'tx>,
+    tx: TransactionRef<'driver, 'tx>,
     query: Q,
     _k: PhantomData<K>,
     parameters: M,
@@ -288,7 +660,7 @@ type DefaultParameters = HashMap<DefaultKey, ValueSend>;
 impl<'driver, 'tx, Q: AsRef<str>>
     TransactionQueryBuilder<'driver, 'tx, Q, DefaultKey, DefaultParameters>
 {
-    fn new(tx: &'tx Transaction<'driver, 'tx>, query: Q) -> Self {
+    fn new(tx: TransactionRef<'driver, 'tx>, query: Q) -> Self {
         Self {
             tx,
             query,
@@ -377,6 +749,92 @@ impl<Q: AsRef<str>, K: Borrow<str> + Debug, M: Borrow<HashMap<K, ValueSend>>> De
     }
 }
 
+/// A builder for queries that take ownership of an [`UnmanagedTransaction`].
+///
+/// See [`UnmanagedTransaction::into_query()`].
+pub struct UnmanagedQueryBuilder<
+    'driver,
+    Q: AsRef<str>,
+    K: Borrow<str> + Debug,
+    M: Borrow<HashMap<K, ValueSend>>,
+> {
+    tx: UnmanagedTransaction<'driver>,
+    query: Q,
+    _k: PhantomData<K>,
+    parameters: M,
+}
+
+impl<'driver, Q: AsRef<str>, K: Borrow<str> + Debug, M: Borrow<HashMap<K, ValueSend>>>
+    UnmanagedQueryBuilder<'driver, Q, K, M>
+{
+    /// Configure query parameters.
+    ///
+    /// See [`TransactionQueryBuilder::with_parameters()`].
+    pub fn with_parameters<K_: Borrow<str> + Debug, M_: Borrow<HashMap<K_, ValueSend>>>(
+        self,
+        parameters: M_,
+    ) -> UnmanagedQueryBuilder<'driver, Q, K_, M_> {
+        let Self {
+            tx,
+            query,
+            _k: _,
+            parameters: _,
+        } = self;
+        UnmanagedQueryBuilder {
+            tx,
+            query,
+            _k: PhantomData,
+            parameters,
+        }
+    }
+
+    /// Configure the query to not use any parameters.
+    ///
+    /// This is the *default*.
+    pub fn without_parameters(
+        self,
+    ) -> UnmanagedQueryBuilder<'driver, Q, DefaultKey, DefaultParameters> {
+        let Self {
+            tx,
+            query,
+            _k: _,
+            parameters: _,
+        } = self;
+        UnmanagedQueryBuilder {
+            tx,
+            query,
+            _k: PhantomData,
+            parameters: Default::default(),
+        }
+    }
+
+    /// Run the query as configured.
+    ///
+    /// If running the query fails, the error is returned and the transaction is closed.
+    pub fn run(self) -> Result<UnmanagedRecordStream<'driver>> {
+        let stream = self
+            .tx
+            .inner_tx
+            .run(self.query.as_ref(), self.parameters.borrow())?;
+        Ok(UnmanagedRecordStream {
+            stream,
+            transaction: Some(self.tx),
+        })
+    }
+}
+
+impl<Q: AsRef<str>, K: Borrow<str> + Debug, M: Borrow<HashMap<K, ValueSend>>> Debug
+    for UnmanagedQueryBuilder<'_, Q, K, M>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UnmanagedQueryBuilder")
+            .field("tx", &self.tx)
+            .field("query", &self.query.as_ref())
+            .field("parameters", self.parameters.borrow())
+            .finish()
+    }
+}
+
 /// Controls after how long a transaction should be killed by the server.
 ///
 /// Choices:
@@ -468,3 +926,115 @@ impl InternalTransactionTimeout {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_streams_and_transactions_are_send_and_sync() {
+        assert_send_sync::<RecordStream<'static>>();
+        assert_send_sync::<Transaction<'static, 'static>>();
+        assert_send_sync::<TransactionRecordStream<'static, 'static>>();
+    }
+
+    #[test]
+    fn test_owned_session_and_transaction_are_static_send_and_sync() {
+        fn assert_owned<T: Send + Sync + 'static>() {}
+
+        assert_owned::<OwnedSession>();
+        assert_owned::<OwnedTransaction>();
+    }
+
+    #[test]
+    fn test_unmanaged_transaction_is_send_and_sync() {
+        assert_send_sync::<UnmanagedTransaction<'static>>();
+    }
+
+    #[test]
+    fn test_owned_record_stream_is_static_send_and_sync() {
+        fn assert_owned<T: Send + Sync + 'static>() {}
+
+        assert_owned::<OwnedRecordStream>();
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_owned_record_stream_is_consumed_on_another_thread() {
+        use std::thread;
+
+        use crate::session::SessionConfig;
+        use crate::testing::StubServer;
+        use crate::testing::test_support::{direct_driver, query_script};
+
+        let server = StubServer::start(query_script("RETURN 1 AS n")).unwrap();
+        let driver = direct_driver(&server);
+        let mut session = driver.owned_session(SessionConfig::new());
+
+        let stream = session
+            .begin_transaction()
+            .unwrap()
+            .into_query("RETURN 1 AS n")
+            .run()
+            .unwrap();
+        let consumer = thread::spawn(move || {
+            let mut stream = stream;
+            let values = (&mut stream)
+                .map(|record| record.map(|mut record| record.take_value("n")))
+                .collect::<Result<Vec<_>>>()?;
+            stream.commit()?;
+            Ok::<_, Neo4jError>(values)
+        });
+        let values = consumer.join().unwrap().unwrap();
+        assert_eq!(values, vec![Some(ValueReceive::Integer(1))]);
+
+        drop(session);
+        drop(driver);
+        server.finish().unwrap();
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_session_is_blocked_while_unmanaged_transaction_is_open() {
+        use crate::session::SessionConfig;
+        use crate::testing::test_support::{direct_driver, hello_script};
+        use crate::testing::{ClientMessage, ServerMessage, StubServer};
+        use crate::value_map;
+
+        let script = hello_script()
+            .client(ClientMessage::begin())
+            .server(ServerMessage::success(value_map!()))
+            .client(ClientMessage::rollback())
+            .server(ServerMessage::success(value_map!()))
+            .client(ClientMessage::begin())
+            .server(ServerMessage::success(value_map!()))
+            .client(ClientMessage::rollback())
+            .server(ServerMessage::success(value_map!()));
+        let server = StubServer::start(script).unwrap();
+        let driver = direct_driver(&server);
+        let mut session = driver.session(SessionConfig::new());
+
+        let transaction = session.begin_transaction().unwrap();
+        assert!(matches!(
+            session.begin_transaction(),
+            Err(Neo4jError::InvalidUsage { .. })
+        ));
+        assert!(matches!(
+            session.auto_commit("RETURN 1 AS n").run(),
+            Err(Neo4jError::InvalidUsage { .. })
+        ));
+        assert!(matches!(
+            session.transaction().run(|_| Ok(())),
+            Err(Neo4jError::InvalidUsage { .. })
+        ));
+        transaction.rollback().unwrap();
+
+        // once the transaction is closed, the session can be used again
+        session.begin_transaction().unwrap().rollback().unwrap();
+        drop(session);
+        drop(driver);
+        server.finish().unwrap();
+    }
+}
@@ -33,7 +33,7 @@ use crate::driver::auth::AuthManager;
 #[allow(unused)]
 use crate::driver::session::bookmarks::BookmarkManager;
 #[allow(unused)]
-use crate::driver::{DriverConfig, ExecuteQueryBuilder};
+use crate::driver::{DriverConfig, ExecuteQueryBuilder, QueryExecutor};
 #[allow(unused)]
 use crate::session::SessionConfig;
 #[allow(unused)]
@@ -102,6 +102,15 @@ pub enum Neo4jError {
         // backtrace: Backtrace,
     },
 
+    /// Used when the driver's API is used in a way that's not supported and that cannot be
+    /// prevented at compile time.
+    /// For example:
+    ///  * Sending work to an async session while a result stream or transaction of that session
+    ///    is still open.
+    #[error("invalid usage: {message}")]
+    #[non_exhaustive]
+    InvalidUsage { message: String },
+
     /// Used when:
     ///  * the server returns an error.
     #[error("{error}")]
@@ -279,18 +288,27 @@ impl ServerError {
         }
     }
 
-    pub(crate) fn from_meta(mut meta: BoltMeta) -> Self {
-        let code = match meta.remove("code") {
-            Some(ValueReceive::String(code)) => code,
-            _ => UNKNOWN_NEO4J_CODE.into(),
-        };
-        let message = match meta.remove("message") {
-            Some(ValueReceive::String(message)) => message,
-            _ => UNKNOWN_NEO4J_MESSAGE.into(),
-        };
+    /// Create a server error with the given Neo4j status code and message.
+    ///
+    /// The driver never produces errors this way.
+    /// This is useful for letting test doubles (e.g., of [`QueryExecutor`]) fail like the DBMS
+    /// would.
+    ///
+    /// # Example
+    /// ```
+    /// use neo4j::error::ServerError;
+    /// use neo4j::Neo4jError;
+    ///
+    /// let error = ServerError::new("Neo.TransientError.General.Unknown", "oh no!");
+    /// assert_eq!(error.classification(), "TransientError");
+    /// let error = Neo4jError::from(error);
+    /// assert!(error.is_retryable());
+    /// ```
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        let message = message.into();
         let gql_status_description = format!("{UNKNOWN_GQL_STATUS_DESCRIPTION}. {message}");
         Self {
-            code: Self::map_legacy_codes(code),
+            code: Self::map_legacy_codes(code.into()),
             message,
             gql_status: String::from(UNKNOWN_GQL_STATUS),
             gql_status_description,
@@ -302,6 +320,18 @@ impl ServerError {
         }
     }
 
+    pub(crate) fn from_meta(mut meta: BoltMeta) -> Self {
+        let code = match meta.remove("code") {
+            Some(ValueReceive::String(code)) => code,
+            _ => UNKNOWN_NEO4J_CODE.into(),
+        };
+        let message = match meta.remove("message") {
+            Some(ValueReceive::String(message)) => message,
+            _ => UNKNOWN_NEO4J_MESSAGE.into(),
+        };
+        Self::new(code, message)
+    }
+
     pub(crate) fn from_meta_gql(mut meta: BoltMeta) -> Self {
         let code = match meta.remove("neo4j_code") {
             Some(ValueReceive::String(code)) => code,
@@ -421,6 +451,14 @@ impl ServerError {
     }
 }
 
+impl From<ServerError> for Neo4jError {
+    fn from(error: ServerError) -> Self {
+        Neo4jError::ServerError {
+            error: Box::new(error),
+        }
+    }
+}
+
 impl Display for ServerError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -195,21 +195,50 @@
 //!
 //! # Crate Features
 //! This crate supports the following features:
+//! - `arrow_57`: Enables exporting results as Apache Arrow (`arrow-array` crate version 57)
+//!   `RecordBatch`es (`EagerResult::to_arrow()`, `RecordStream::arrow_batches()`).
 //! - `chrono_0_4`: Enables conversion between temporal driver types and `chrono` crate version 0.4
 //!   types.
 //! - `chrono_tz_0_9`: Enables conversion between temporal driver types and `chrono` 0.4 types with
 //!   `chrono-tz` 0.9 timezone types.
 //! - `chrono_tz_0_10`: Enables conversion between temporal driver types and `chrono` 0.4 types with
 //!   `chrono-tz` 0.10 timezone types.
+//! - `derive`: Enables derive macros (`#[derive(FromRecord)]`, `#[derive(FromNode)]`, and
+//!   `#[derive(ToParameters)]`) for mapping records and nodes to structs and structs to query
+//!   parameters.
+//! - `rustls_0_23`: Enables APIs exposing `rustls` crate version 0.23 types
+//!   (e.g., `ConnectionConfig::with_tls_config()`).
+//! - `serde_1`: Enables `serde` (version 1) integration:
+//!   deserializing records and values into user types (e.g., `Record::to()`,
+//!   `EagerResult::records_as()`) and serializing user types into values and query parameters
+//!   (`neo4j::value::to_value()`, `neo4j::value::to_parameters()`).
+//! - `serde_json_1`: Enables encoding and decoding values as Jolt, Neo4j's typed JSON format,
+//!   using `serde_json` crate version 1 types (`ValueReceive::to_jolt()`,
+//!   `ValueReceive::from_jolt()`, see `neo4j::value::jolt`), and exporting records as
+//!   newline-delimited JSON (`Record::to_ndjson()`, `EagerResult::to_ndjson()`).
+//! - `testing`: Enables `neo4j::testing`, an in-process Bolt stub server for testing code that uses
+//!   the driver without a running DBMS.
+//! - `tracing`: Emits [`tracing`](https://docs.rs/tracing) spans (at `INFO` level) for
+//!   `execute_query`, session transactions, connection acquisition, routing table fetches, and
+//!   the Bolt messages `RUN`, `PULL`, `DISCARD`, `BEGIN`, `COMMIT`, and `ROUTE`.
+//!   Spans carry structured fields such as the database (`db.namespace`), the server address
+//!   (`server.address`), the Bolt connection id (`neo4j.bolt_id`), the query type
+//!   (`neo4j.query_type`), and the number of received records (`neo4j.records`).
 //! - The crate has further feature flags starting with `_internal_...`.
 //!   Do **NOT** us them. APIs exposed by these features don't come with any semver guarantees,
 //!   support, or documentation.
 
+// allows the derive macros' `::neo4j::...` paths to resolve in this crate's own tests
+#[cfg(all(test, feature = "derive"))]
+extern crate self as neo4j;
+
 mod address_;
 pub mod driver;
 mod error_;
 mod macros;
 mod sync;
+#[cfg(feature = "testing")]
+pub mod testing;
 #[cfg(feature = "_internal_testkit_backend")]
 pub mod time;
 #[cfg(not(feature = "_internal_testkit_backend"))]
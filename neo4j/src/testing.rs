@@ -0,0 +1,995 @@
+// Copyright Rouven Bauer
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Utilities for testing code that uses the driver without a running DBMS.
+//!
+//! Application code that is generic over [`QueryExecutor`] can be tested with a
+//! [`FakeQueryExecutor`], which records all queries and replies with configured results without
+//! any networking involved.
+//!
+//! To test the driver's behavior on the wire, a [`StubServer`] is an in-process Bolt server that
+//! plays a [`StubScript`]: it expects the client to send certain messages ([`ClientMessage`]) and
+//! answers with canned responses ([`ServerMessage`]).
+//! Any deviation from the script fails the test when the server is [finished](StubServer::finish)
+//! or dropped.
+//!
+//! Each stub server serves exactly one connection.
+//! To test routing, let one stub server reply to `ROUTE` with a routing table
+//! ([`ServerMessage::routing_table()`]) pointing to the addresses of other stub servers.
+//!
+//! # Example
+//! ```
+//! use std::sync::Arc;
+//!
+//! use neo4j::driver::{ConnectionConfig, Driver, DriverConfig};
+//! use neo4j::testing::{ClientMessage, ServerMessage, StubScript, StubServer};
+//! use neo4j::{value_map, ValueSend};
+//!
+//! let server = StubServer::start(
+//!     StubScript::new(5, 0)
+//!         .client(ClientMessage::hello())
+//!         .server(ServerMessage::success(value_map!({"server": "Neo4j/5.0.0"})))
+//!         .client(ClientMessage::begin())
+//!         .server(ServerMessage::success(value_map!()))
+//!         .client(ClientMessage::run("RETURN 1 AS n"))
+//!         .client(ClientMessage::pull())
+//!         .server(ServerMessage::success(value_map!({"fields": ["n"]})))
+//!         .server(ServerMessage::record(vec![ValueSend::Integer(1)]))
+//!         .server(ServerMessage::success(value_map!({"type": "r"})))
+//!         .client(ClientMessage::commit())
+//!         .server(ServerMessage::success(value_map!({"bookmark": "bm"}))),
+//! )
+//! .unwrap();
+//!
+//! let driver = Driver::new(
+//!     ConnectionConfig::new(server.address()).with_routing(false).unwrap(),
+//!     DriverConfig::new(),
+//! );
+//! let n: i64 = driver
+//!     .execute_query("RETURN 1 AS n")
+//!     .with_database(Arc::new(String::from("neo4j")))
+//!     .run()
+//!     .unwrap()
+//!     .into_scalar()
+//!     .unwrap()
+//!     .try_into()
+//!     .unwrap();
+//! assert_eq!(n, 1);
+//!
+//! // closing the driver ends the connection
+//! drop(driver);
+//! server.finish().unwrap();
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::result;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use log::debug;
+use parking_lot::Mutex;
+use thiserror::Error;
+
+use crate::address_::Address;
+use crate::driver::io::bolt::StubConnection;
+use crate::driver::{EagerResult, Query, QueryExecutor, RoutingControl, TransactionExecutor};
+use crate::error_::{Neo4jError, Result};
+use crate::value::{ValueReceive, ValueSend};
+
+// imports for docs
+#[allow(unused)]
+use crate::driver::ConnectionConfig;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+const TAG_HELLO: u8 = 0x01;
+const TAG_GOODBYE: u8 = 0x02;
+const TAG_RESET: u8 = 0x0F;
+const TAG_RUN: u8 = 0x10;
+const TAG_BEGIN: u8 = 0x11;
+const TAG_COMMIT: u8 = 0x12;
+const TAG_ROLLBACK: u8 = 0x13;
+const TAG_DISCARD: u8 = 0x2F;
+const TAG_PULL: u8 = 0x3F;
+const TAG_TELEMETRY: u8 = 0x54;
+const TAG_ROUTE: u8 = 0x66;
+const TAG_LOGON: u8 = 0x6A;
+const TAG_LOGOFF: u8 = 0x6B;
+const TAG_SUCCESS: u8 = 0x70;
+const TAG_RECORD: u8 = 0x71;
+const TAG_IGNORED: u8 = 0x7E;
+const TAG_FAILURE: u8 = 0x7F;
+
+fn client_message_name(tag: u8) -> Option<&'static str> {
+    Some(match tag {
+        TAG_HELLO => "HELLO",
+        TAG_GOODBYE => "GOODBYE",
+        TAG_RESET => "RESET",
+        TAG_RUN => "RUN",
+        TAG_BEGIN => "BEGIN",
+        TAG_COMMIT => "COMMIT",
+        TAG_ROLLBACK => "ROLLBACK",
+        TAG_DISCARD => "DISCARD",
+        TAG_PULL => "PULL",
+        TAG_TELEMETRY => "TELEMETRY",
+        TAG_ROUTE => "ROUTE",
+        TAG_LOGON => "LOGON",
+        TAG_LOGOFF => "LOGOFF",
+        _ => return None,
+    })
+}
+
+fn server_message_name(tag: u8) -> Option<&'static str> {
+    Some(match tag {
+        TAG_SUCCESS => "SUCCESS",
+        TAG_RECORD => "RECORD",
+        TAG_IGNORED => "IGNORED",
+        TAG_FAILURE => "FAILURE",
+        _ => return None,
+    })
+}
+
+fn fmt_tag(f: &mut Formatter<'_>, tag: u8, name: Option<&str>) -> std::fmt::Result {
+    match name {
+        Some(name) => write!(f, "{name}"),
+        None => write!(f, "<{tag:#04X}>"),
+    }
+}
+
+/// A script of messages a [`StubServer`] expects to receive and sends in return.
+///
+/// Steps are played in order.
+/// After the last step, the server waits for the client to close the connection.
+/// The only message the client may send after the script ended is `GOODBYE`.
+#[derive(Debug, Clone)]
+pub struct StubScript {
+    version: (u8, u8),
+    steps: Vec<ScriptStep>,
+    timeout: Duration,
+}
+
+#[derive(Debug, Clone)]
+enum ScriptStep {
+    Client(ClientMessage),
+    Server(ServerMessage),
+    Close,
+}
+
+impl StubScript {
+    /// Create an empty script for a server that speaks Bolt `major.minor`.
+    ///
+    /// # Panics
+    /// Panics if the driver doesn't support the given protocol version.
+    pub fn new(major: u8, minor: u8) -> Self {
+        assert!(
+            crate::driver::io::bolt::is_supported_version(major, minor),
+            "unsupported bolt version {major}.{minor}"
+        );
+        Self {
+            version: (major, minor),
+            steps: Vec::new(),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Expect the client to send the given message next.
+    pub fn client(mut self, message: ClientMessage) -> Self {
+        self.steps.push(ScriptStep::Client(message));
+        self
+    }
+
+    /// Send the given message to the client.
+    pub fn server(mut self, message: ServerMessage) -> Self {
+        self.steps.push(ScriptStep::Server(message));
+        self
+    }
+
+    /// Close the connection (server side).
+    ///
+    /// This ends the script.
+    /// Any steps added afterward will not be played.
+    pub fn close(mut self) -> Self {
+        self.steps.push(ScriptStep::Close);
+        self
+    }
+
+    /// Configure how long the server waits for the client (to connect, to send the next message,
+    /// or to close the connection after the script ended).
+    ///
+    /// Default: 10 seconds.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// A message the [`StubServer`] expects to receive from the client.
+///
+/// By default, any fields are accepted.
+/// Use [`ClientMessage::with_field()`] or [`ClientMessage::with_fields()`] to also check the
+/// message's fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientMessage {
+    tag: u8,
+    fields: Vec<Option<ValueSend>>,
+    exact: bool,
+}
+
+impl ClientMessage {
+    /// Expect a message with the given tag (signature byte).
+    pub fn new(tag: u8) -> Self {
+        Self {
+            tag,
+            fields: Vec::new(),
+            exact: false,
+        }
+    }
+
+    /// Expect a `HELLO` message.
+    pub fn hello() -> Self {
+        Self::new(TAG_HELLO)
+    }
+
+    /// Expect a `LOGON` message (Bolt 5.1+).
+    pub fn logon() -> Self {
+        Self::new(TAG_LOGON)
+    }
+
+    /// Expect a `LOGOFF` message (Bolt 5.1+).
+    pub fn logoff() -> Self {
+        Self::new(TAG_LOGOFF)
+    }
+
+    /// Expect a `GOODBYE` message.
+    pub fn goodbye() -> Self {
+        Self::new(TAG_GOODBYE)
+    }
+
+    /// Expect a `RESET` message.
+    pub fn reset() -> Self {
+        Self::new(TAG_RESET)
+    }
+
+    /// Expect a `RUN` message with the given query.
+    pub fn run(query: &str) -> Self {
+        Self::new(TAG_RUN).with_field(0, query)
+    }
+
+    /// Expect a `BEGIN` message.
+    pub fn begin() -> Self {
+        Self::new(TAG_BEGIN)
+    }
+
+    /// Expect a `COMMIT` message.
+    pub fn commit() -> Self {
+        Self::new(TAG_COMMIT)
+    }
+
+    /// Expect a `ROLLBACK` message.
+    pub fn rollback() -> Self {
+        Self::new(TAG_ROLLBACK)
+    }
+
+    /// Expect a `PULL` message.
+    pub fn pull() -> Self {
+        Self::new(TAG_PULL)
+    }
+
+    /// Expect a `DISCARD` message.
+    pub fn discard() -> Self {
+        Self::new(TAG_DISCARD)
+    }
+
+    /// Expect a `ROUTE` message.
+    pub fn route() -> Self {
+        Self::new(TAG_ROUTE)
+    }
+
+    /// Expect a `TELEMETRY` message (Bolt 5.4+).
+    pub fn telemetry() -> Self {
+        Self::new(TAG_TELEMETRY)
+    }
+
+    /// Expect the field at `index` to be equal to `value`.
+    ///
+    /// Other fields remain unchecked.
+    pub fn with_field(mut self, index: usize, value: impl Into<ValueSend>) -> Self {
+        if self.fields.len() <= index {
+            self.fields.resize(index + 1, None);
+        }
+        self.fields[index] = Some(value.into());
+        self
+    }
+
+    /// Expect the message to have exactly the given fields.
+    pub fn with_fields(mut self, fields: impl IntoIterator<Item = ValueSend>) -> Self {
+        self.fields = fields.into_iter().map(Some).collect();
+        self.exact = true;
+        self
+    }
+
+    fn matches(&self, tag: u8, fields: &[ValueReceive]) -> bool {
+        if tag != self.tag {
+            return false;
+        }
+        if self.exact && fields.len() != self.fields.len() {
+            return false;
+        }
+        self.fields.iter().enumerate().all(|(i, expected)| {
+            let Some(expected) = expected else {
+                return true;
+            };
+            fields
+                .get(i)
+                .and_then(|field| ValueSend::try_from(field.clone()).ok())
+                .is_some_and(|field| &field == expected)
+        })
+    }
+}
+
+impl Display for ClientMessage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_tag(f, self.tag, client_message_name(self.tag))?;
+        for field in &self.fields {
+            match field {
+                Some(field) => write!(f, " {field:?}")?,
+                None => write!(f, " *")?,
+            }
+        }
+        if !self.exact {
+            write!(f, " ...")?;
+        }
+        Ok(())
+    }
+}
+
+struct ReceivedMessage<'a> {
+    tag: u8,
+    fields: &'a [ValueReceive],
+}
+
+impl Display for ReceivedMessage<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_tag(f, self.tag, client_message_name(self.tag))?;
+        for field in self.fields {
+            write!(f, " {field:?}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A message the [`StubServer`] sends to the client.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerMessage {
+    tag: u8,
+    fields: Vec<ValueSend>,
+}
+
+impl ServerMessage {
+    /// A message with the given tag (signature byte) and fields.
+    pub fn new(tag: u8, fields: Vec<ValueSend>) -> Self {
+        Self { tag, fields }
+    }
+
+    /// A `SUCCESS` message with the given metadata.
+    pub fn success(meta: HashMap<String, ValueSend>) -> Self {
+        Self::new(TAG_SUCCESS, vec![ValueSend::Map(meta)])
+    }
+
+    /// A `RECORD` message with the given values.
+    pub fn record(values: Vec<ValueSend>) -> Self {
+        Self::new(TAG_RECORD, vec![ValueSend::List(values)])
+    }
+
+    /// A `FAILURE` message with the given metadata.
+    ///
+    /// Before Bolt 5.7, the metadata should contain `"code"` and `"message"`.
+    /// Since Bolt 5.7, it should contain (at least) `"neo4j_code"`, `"message"`, `"gql_status"`,
+    /// and `"description"`.
+    pub fn failure(meta: HashMap<String, ValueSend>) -> Self {
+        Self::new(TAG_FAILURE, vec![ValueSend::Map(meta)])
+    }
+
+    /// An `IGNORED` message.
+    pub fn ignored() -> Self {
+        Self::new(TAG_IGNORED, vec![])
+    }
+
+    /// A `SUCCESS` message in reply to `ROUTE` carrying a routing table.
+    ///
+    /// `ttl` is the time to live in seconds.
+    pub fn routing_table(
+        ttl: i64,
+        routers: &[Address],
+        readers: &[Address],
+        writers: &[Address],
+    ) -> Self {
+        let servers = [("ROUTE", routers), ("READ", readers), ("WRITE", writers)]
+            .into_iter()
+            .map(|(role, addresses)| {
+                ValueSend::Map(HashMap::from([
+                    (String::from("role"), ValueSend::from(role)),
+                    (
+                        String::from("addresses"),
+                        ValueSend::List(
+                            addresses
+                                .iter()
+                                .map(|address| ValueSend::String(address.to_string()))
+                                .collect(),
+                        ),
+                    ),
+                ]))
+            })
+            .collect();
+        let rt = HashMap::from([
+            (String::from("ttl"), ValueSend::Integer(ttl)),
+            (String::from("servers"), ValueSend::List(servers)),
+        ]);
+        Self::success(HashMap::from([(String::from("rt"), ValueSend::Map(rt))]))
+    }
+}
+
+impl Display for ServerMessage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_tag(f, self.tag, server_message_name(self.tag))?;
+        for field in &self.fields {
+            write!(f, " {field:?}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Error returned by [`StubServer::finish()`] when the client didn't behave as scripted.
+#[derive(Debug, Error)]
+#[error("stub server {address}: {message}")]
+pub struct StubScriptError {
+    address: Address,
+    message: String,
+}
+
+impl StubScriptError {
+    /// Description of the deviation from the script.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// An in-process Bolt server playing a [`StubScript`] on a background thread.
+///
+/// Point a [`ConnectionConfig`] at [`StubServer::address()`] to connect to it.
+///
+/// When dropped, the server waits for the script to finish and panics if the client deviated from
+/// it (unless the thread is already panicking).
+/// Use [`StubServer::finish()`] to inspect the outcome instead.
+/// Either way, make sure to close the driver first, so that the connection gets closed.
+#[derive(Debug)]
+pub struct StubServer {
+    address: Address,
+    handle: Option<JoinHandle<result::Result<(), String>>>,
+}
+
+impl StubServer {
+    /// Start a server on a random port on `127.0.0.1` playing the given script.
+    pub fn start(script: StubScript) -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0))?;
+        let address = Address::from(listener.local_addr()?);
+        listener.set_nonblocking(true)?;
+        let handle = thread::Builder::new()
+            .name(format!("neo4j-stub-server-{address}"))
+            .spawn(move || serve(listener, script))?;
+        Ok(Self {
+            address,
+            handle: Some(handle),
+        })
+    }
+
+    /// The address the server is listening on.
+    pub fn address(&self) -> Address {
+        self.address.clone()
+    }
+
+    /// Wait for the script to finish.
+    ///
+    /// Returns an error if the client deviated from the script or if it timed out.
+    pub fn finish(mut self) -> result::Result<(), StubScriptError> {
+        self.join()
+    }
+
+    fn join(&mut self) -> result::Result<(), StubScriptError> {
+        let Some(handle) = self.handle.take() else {
+            return Ok(());
+        };
+        let res = match handle.join() {
+            Ok(res) => res,
+            Err(_) => Err(String::from("server thread panicked")),
+        };
+        res.map_err(|message| StubScriptError {
+            address: self.address.clone(),
+            message,
+        })
+    }
+}
+
+impl Drop for StubServer {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            return;
+        }
+        if let Err(err) = self.join() {
+            panic!("{err}");
+        }
+    }
+}
+
+fn serve(listener: TcpListener, script: StubScript) -> result::Result<(), String> {
+    let deadline = Instant::now() + script.timeout;
+    let stream = loop {
+        match listener.accept() {
+            Ok((stream, _)) => break stream,
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                if Instant::now() >= deadline {
+                    return Err(String::from("timed out waiting for client to connect"));
+                }
+                thread::sleep(ACCEPT_POLL_INTERVAL);
+            }
+            Err(err) => return Err(format!("failed to accept connection: {err}")),
+        }
+    };
+    drop(listener);
+    let setup = stream
+        .set_nonblocking(false)
+        .and_then(|_| stream.set_read_timeout(Some(script.timeout)));
+    if let Err(err) = setup {
+        return Err(format!("failed to configure connection: {err}"));
+    }
+    let mut connection = StubConnection::accept(stream, script.version)
+        .map_err(|err| format!("handshake failed: {err}"))?;
+    let res = play(&mut connection, script);
+    let _ = connection.into_inner().shutdown(Shutdown::Both);
+    res
+}
+
+fn play(
+    connection: &mut StubConnection<TcpStream>,
+    script: StubScript,
+) -> result::Result<(), String> {
+    for step in script.steps {
+        match step {
+            ScriptStep::Client(expected) => {
+                let message = connection
+                    .read_message()
+                    .map_err(|err| format!("failed to read {expected}: {err}"))?;
+                let Some((tag, fields)) = message else {
+                    return Err(format!("client closed connection, expected {expected}"));
+                };
+                let received = ReceivedMessage {
+                    tag,
+                    fields: &fields,
+                };
+                debug!("STUB C: {received}");
+                if !expected.matches(tag, &fields) {
+                    return Err(format!("expected {expected}, received {received}"));
+                }
+            }
+            ScriptStep::Server(message) => {
+                debug!("STUB S: {message}");
+                connection
+                    .write_message(message.tag, &message.fields)
+                    .map_err(|err| format!("failed to send {message}: {err}"))?;
+            }
+            ScriptStep::Close => {
+                debug!("STUB S: <CLOSE>");
+                return Ok(());
+            }
+        }
+    }
+    loop {
+        let message = connection.read_message().map_err(|err| {
+            format!("script ended, failed waiting for client to close connection: {err}")
+        })?;
+        match message {
+            None => return Ok(()),
+            Some((TAG_GOODBYE, _)) => debug!("STUB C: GOODBYE"),
+            Some((tag, fields)) => {
+                return Err(format!(
+                    "script ended, received {}",
+                    ReceivedMessage {
+                        tag,
+                        fields: &fields
+                    }
+                ));
+            }
+        }
+    }
+}
+
+/// An in-memory [`QueryExecutor`] for unit tests.
+///
+/// Every query, whether executed on its own or inside a transaction, is recorded (see
+/// [`FakeQueryExecutor::queries()`]) and answered with the next configured response in the order
+/// they were configured.
+/// Queries run inside a transaction are recorded with the transaction's database and routing
+/// control.
+///
+/// # Panics
+/// Running a query when there are no more configured responses panics.
+///
+/// # Example
+/// ```
+/// use neo4j::driver::{Query, QueryExecutor};
+/// use neo4j::error::ServerError;
+/// use neo4j::testing::FakeQueryExecutor;
+/// use neo4j::value_map;
+///
+/// fn create_person(executor: &impl QueryExecutor, name: &str) -> neo4j::Result<()> {
+///     executor
+///         .execute(Query::new("CREATE (:Person {name: $name})")
+///             .with_parameters(value_map!({"name": name})))
+///         .map(drop)
+/// }
+///
+/// let fake = FakeQueryExecutor::new().with_error(
+///     ServerError::new(
+///         "Neo.ClientError.Schema.ConstraintValidationFailed",
+///         "Node already exists",
+///     )
+///     .into(),
+/// );
+///
+/// assert!(create_person(&fake, "Alice").is_err());
+/// assert_eq!(
+///     fake.queries(),
+///     vec![Query::new("CREATE (:Person {name: $name})")
+///         .with_parameters(value_map!({"name": "Alice"}))],
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct FakeQueryExecutor {
+    state: Mutex<FakeQueryExecutorState>,
+}
+
+#[derive(Debug, Default)]
+struct FakeQueryExecutorState {
+    responses: VecDeque<Result<EagerResult>>,
+    queries: Vec<Query>,
+}
+
+impl FakeQueryExecutor {
+    /// Create a fake without any configured responses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reply to the next unanswered query with `result`.
+    pub fn with_result(self, result: EagerResult) -> Self {
+        self.state.lock().responses.push_back(Ok(result));
+        self
+    }
+
+    /// Fail the next unanswered query with `error`.
+    pub fn with_error(self, error: Neo4jError) -> Self {
+        self.state.lock().responses.push_back(Err(error));
+        self
+    }
+
+    /// All queries run so far (in order).
+    pub fn queries(&self) -> Vec<Query> {
+        self.state.lock().queries.clone()
+    }
+
+    fn respond(&self, query: Query) -> Result<EagerResult> {
+        let mut state = self.state.lock();
+        let response = state.responses.pop_front().unwrap_or_else(|| {
+            panic!(
+                "FakeQueryExecutor has no response configured for query {:?}",
+                query.text()
+            )
+        });
+        state.queries.push(query);
+        response
+    }
+}
+
+impl QueryExecutor for FakeQueryExecutor {
+    fn execute(&self, query: Query) -> Result<EagerResult> {
+        self.respond(query)
+    }
+
+    fn execute_transaction<R>(
+        &self,
+        database: Option<Arc<String>>,
+        routing_control: RoutingControl,
+        work: impl FnOnce(&mut dyn TransactionExecutor) -> Result<R>,
+    ) -> Result<R> {
+        work(&mut FakeTransaction {
+            executor: self,
+            database,
+            routing_control,
+        })
+    }
+}
+
+struct FakeTransaction<'a> {
+    executor: &'a FakeQueryExecutor,
+    database: Option<Arc<String>>,
+    routing_control: RoutingControl,
+}
+
+impl TransactionExecutor for FakeTransaction<'_> {
+    fn run(&mut self, query: &str, parameters: HashMap<String, ValueSend>) -> Result<EagerResult> {
+        let query = Query::new(query)
+            .with_parameters(parameters)
+            .with_routing_control(self.routing_control);
+        let query = match &self.database {
+            Some(database) => query.with_database(Arc::clone(database)),
+            None => query,
+        };
+        self.executor.respond(query)
+    }
+}
+
+/// Stub server helpers shared by the driver's own tests.
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+    use crate::driver::{ConnectionConfig, Driver, DriverConfig};
+    use crate::value_map;
+
+    /// A Bolt 5.0 handshake with a successful `HELLO`.
+    pub(crate) fn hello_script() -> StubScript {
+        StubScript::new(5, 0)
+            .client(ClientMessage::hello())
+            .server(ServerMessage::success(
+                value_map!({"server": "Neo4j/5.0.0"}),
+            ))
+    }
+
+    /// [`hello_script()`] followed by a transaction running `query`, which returns a single record
+    /// with a single field `n`.
+    pub(crate) fn query_script(query: &str) -> StubScript {
+        hello_script()
+            .client(ClientMessage::begin())
+            .server(ServerMessage::success(value_map!()))
+            .client(ClientMessage::run(query))
+            .client(ClientMessage::pull())
+            .server(ServerMessage::success(value_map!({"fields": ["n"]})))
+            .server(ServerMessage::record(vec![ValueSend::Integer(1)]))
+            .server(ServerMessage::success(value_map!({"type": "r"})))
+            .client(ClientMessage::commit())
+            .server(ServerMessage::success(value_map!()))
+    }
+
+    /// A driver connecting to `server` without routing.
+    pub(crate) fn direct_driver(server: &StubServer) -> Driver {
+        Driver::new(
+            ConnectionConfig::new(server.address())
+                .with_routing(false)
+                .unwrap(),
+            DriverConfig::new(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::{direct_driver, hello_script, query_script};
+    use super::*;
+    use crate::driver::Record;
+    use crate::driver::{ConnectionConfig, Driver, DriverConfig};
+    use crate::error::ServerError;
+    use crate::summary::Summary;
+    use crate::value_map;
+
+    fn run_query(driver: &Driver, query: &str, mode: RoutingControl) -> crate::Result<usize> {
+        driver
+            .execute_query(query)
+            .with_database(Arc::new(String::from("neo4j")))
+            .with_routing_control(mode)
+            .run()
+            .map(|result| result.records.len())
+    }
+
+    #[test]
+    fn test_routing() {
+        let reader = StubServer::start(query_script("RETURN 1 AS n")).unwrap();
+        let router = StubServer::start(hello_script().client(ClientMessage::route()).server(
+            ServerMessage::routing_table(300, &[reader.address()], &[reader.address()], &[]),
+        ))
+        .unwrap();
+        let driver = Driver::new(ConnectionConfig::new(router.address()), DriverConfig::new());
+
+        assert_eq!(
+            run_query(&driver, "RETURN 1 AS n", RoutingControl::Read).unwrap(),
+            1
+        );
+
+        drop(driver);
+        router.finish().unwrap();
+        reader.finish().unwrap();
+    }
+
+    #[test]
+    fn test_mismatch() {
+        let server = StubServer::start(query_script("RETURN 1 AS n")).unwrap();
+        let driver = direct_driver(&server);
+
+        assert!(run_query(&driver, "RETURN 2 AS n", RoutingControl::Write).is_err());
+
+        drop(driver);
+        let err = server.finish().unwrap_err();
+        assert!(
+            err.message().starts_with(
+                r#"expected RUN String("RETURN 1 AS n") ..., received RUN String("RETURN 2 AS n")"#
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_missing_message() {
+        let server = StubServer::start(hello_script().client(ClientMessage::reset())).unwrap();
+        let driver = direct_driver(&server);
+
+        driver.verify_connectivity().unwrap();
+
+        drop(driver);
+        let err = server.finish().unwrap_err();
+        assert_eq!(err.message(), "expected RESET ..., received GOODBYE");
+    }
+
+    #[test]
+    fn test_message_after_script_ended() {
+        let server = StubServer::start(hello_script()).unwrap();
+        let driver = direct_driver(&server);
+
+        assert!(run_query(&driver, "RETURN 1 AS n", RoutingControl::Write).is_err());
+
+        drop(driver);
+        let err = server.finish().unwrap_err();
+        assert!(
+            err.message().starts_with("script ended, received BEGIN"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_server_close() {
+        let server = StubServer::start(query_script("RETURN 1 AS n").close()).unwrap();
+        let driver = direct_driver(&server);
+
+        run_query(&driver, "RETURN 1 AS n", RoutingControl::Write).unwrap();
+
+        drop(driver);
+        server.finish().unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "timed out waiting for client to connect")]
+    fn test_drop_panics_on_failure() {
+        let server =
+            StubServer::start(hello_script().with_timeout(Duration::from_millis(1))).unwrap();
+        drop(server);
+    }
+
+    #[rstest::rstest]
+    #[case(ClientMessage::run("RETURN 1"), r#"RUN String("RETURN 1") ..."#)]
+    #[case(ClientMessage::pull().with_field(1, 2), "PULL * Integer(2) ...")]
+    #[case(ClientMessage::commit().with_fields([]), "COMMIT")]
+    #[case(ClientMessage::new(0x42), "<0x42> ...")]
+    fn test_client_message_display(#[case] message: ClientMessage, #[case] expected: &str) {
+        assert_eq!(message.to_string(), expected);
+    }
+
+    #[test]
+    fn test_driver_query_executor() {
+        let database = Arc::new(String::from("neo4j"));
+        let server = StubServer::start(
+            query_script("RETURN 1 AS n")
+                .client(ClientMessage::begin())
+                .server(ServerMessage::success(value_map!()))
+                .client(ClientMessage::run("RETURN $x AS n").with_field(1, value_map!({"x": 2})))
+                .client(ClientMessage::pull())
+                .server(ServerMessage::success(value_map!({"fields": ["n"]})))
+                .server(ServerMessage::record(vec![ValueSend::Integer(2)]))
+                .server(ServerMessage::success(value_map!()))
+                .client(ClientMessage::commit())
+                .server(ServerMessage::success(value_map!())),
+        )
+        .unwrap();
+        let driver = direct_driver(&server);
+
+        let result = driver
+            .execute(Query::new("RETURN 1 AS n").with_database(Arc::clone(&database)))
+            .unwrap();
+        assert_eq!(result.into_scalar().unwrap(), ValueReceive::Integer(1));
+        let result = driver
+            .execute_write(Some(database), |tx| {
+                tx.run("RETURN $x AS n", value_map!({"x": 2}))
+            })
+            .unwrap();
+        assert_eq!(result.into_scalar().unwrap(), ValueReceive::Integer(2));
+
+        drop(driver);
+        server.finish().unwrap();
+    }
+
+    #[test]
+    fn test_fake_query_executor_returns_results_in_order() {
+        let key = Arc::new(String::from("n"));
+        let result = |n| {
+            EagerResult::new(
+                vec![Arc::clone(&key)],
+                vec![Record::from_entries([(
+                    Arc::clone(&key),
+                    ValueReceive::Integer(n),
+                )])],
+                Summary::builder().build(),
+            )
+        };
+        let fake = FakeQueryExecutor::new()
+            .with_result(result(1))
+            .with_result(result(2));
+
+        let first = fake.execute(Query::new("RETURN 1 AS n")).unwrap();
+        let second = fake.execute(Query::new("RETURN 2 AS n")).unwrap();
+
+        assert_eq!(first.into_scalar().unwrap(), ValueReceive::Integer(1));
+        assert_eq!(second.into_scalar().unwrap(), ValueReceive::Integer(2));
+        assert_eq!(
+            fake.queries(),
+            vec![Query::new("RETURN 1 AS n"), Query::new("RETURN 2 AS n")]
+        );
+    }
+
+    #[test]
+    fn test_fake_query_executor_records_transaction_queries() {
+        let database = Arc::new(String::from("db"));
+        let fake = FakeQueryExecutor::new()
+            .with_error(ServerError::new("Neo.ClientError.Statement.SyntaxError", "1").into())
+            .with_error(ServerError::new("Neo.ClientError.Statement.SyntaxError", "2").into());
+
+        let res = fake.execute_read(Some(Arc::clone(&database)), |tx| {
+            assert!(tx.run("RETURN 1", value_map!()).is_err());
+            tx.run("RETURN $x", value_map!({"x": 1}))
+        });
+
+        let Err(Neo4jError::ServerError { error }) = res else {
+            panic!("expected server error, got {res:?}");
+        };
+        assert_eq!(error.message(), "2");
+        assert_eq!(
+            fake.queries(),
+            vec![
+                Query::new("RETURN 1")
+                    .with_database(Arc::clone(&database))
+                    .with_routing_control(RoutingControl::Read),
+                Query::new("RETURN $x")
+                    .with_parameters(value_map!({"x": 1}))
+                    .with_database(Arc::clone(&database))
+                    .with_routing_control(RoutingControl::Read),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "no response configured for query \"RETURN 1\"")]
+    fn test_fake_query_executor_without_response() {
+        let _ = FakeQueryExecutor::new().execute(Query::new("RETURN 1"));
+    }
+}
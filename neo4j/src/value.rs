@@ -12,18 +12,41 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "arrow_57")]
+pub mod arrow;
+#[cfg(feature = "serde_1")]
+mod de;
+mod from_value_receive;
 pub mod graph;
+#[cfg(feature = "serde_json_1")]
+pub mod jolt;
+#[cfg(feature = "serde_1")]
+mod ser;
 pub mod spatial;
 pub mod time;
+mod to_parameters;
 pub mod unsupported_type;
+mod value_path;
 mod value_receive;
 mod value_send;
 pub mod vector;
-// mod de;
-// mod ser;
 
 use thiserror::Error;
 
+#[cfg(feature = "serde_1")]
+pub use de::{DeserializeError, DeserializePathElement};
+#[cfg(feature = "serde_1")]
+pub(crate) use de::{from_properties, from_record, from_value};
+pub(crate) use from_value_receive::impl_tuple_all;
+pub use from_value_receive::{
+    FromValueReceive, FromValueReceiveError, FromValueReceivePathElement,
+};
+#[cfg(feature = "derive")]
+pub use neo4j_derive::{FromNode, ToParameters};
+#[cfg(feature = "serde_1")]
+pub use ser::{SerializeError, to_parameters, to_value};
+pub use to_parameters::ToParameters;
+pub use value_path::ValuePathElement;
 pub(crate) use value_receive::BrokenValueInner;
 pub use value_receive::{BrokenValue, ValueReceive};
 pub use value_send::ValueSend;
@@ -0,0 +1,1093 @@
+// Copyright Rouven Bauer
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversion of records into Arrow [`RecordBatch`]es.
+//!
+//! Each record key becomes a nullable column.
+//! The column types are inferred from the values:
+//!
+//! | Neo4j type                       | Arrow type                                            |
+//! |----------------------------------|-------------------------------------------------------|
+//! | `null` (only)                    | `Null`                                                |
+//! | `Boolean`                        | `Boolean`                                             |
+//! | `Integer`                        | `Int64`                                               |
+//! | `Float`                          | `Float64`                                             |
+//! | `Bytes`                          | `Binary`                                              |
+//! | `String`                         | `Utf8`                                                |
+//! | `List`                           | `List` of the (combined) element type                 |
+//! | `Map`                            | `Struct` with a field per key (see below)             |
+//! | `Date`                           | `Date32`                                              |
+//! | `LocalTime`                      | `Time64(Nanosecond)`                                  |
+//! | `Time`                           | `Struct {time: Time64(Nanosecond), utc_offset: Int64}` |
+//! | `LocalDateTime`                  | `Timestamp(Nanosecond, None)`                         |
+//! | `DateTime`, `DateTimeFixed`      | `Timestamp(Nanosecond, "UTC")` (the zone is dropped)  |
+//! | `Duration`                       | `Interval(MonthDayNano)`                              |
+//! | `Vector`                         | `FixedSizeList` of the vector's element type          |
+//! | `Point` (Cartesian)              | `Struct {srid, x, y[, z]}`                            |
+//! | `Point` (WGS-84)                 | `Struct {srid, longitude, latitude[, altitude]}`      |
+//! | `Node`                           | `Struct {element_id, labels, properties}`             |
+//! | `Relationship`                   | `Struct {element_id, type, start_node_element_id, end_node_element_id, properties}` |
+//! | `Path`                           | `Struct {nodes, relationships}` in traversal order    |
+//!
+//! Maps and properties become structs with the union of all keys found in the column.
+//! Values of the same column (or list, or struct field) must have the same type, `null` being
+//! compatible with every type.
+//! Temporal values outside the range of the Arrow type (e.g., timestamps beyond the year 2262)
+//! cannot be converted.
+
+use std::sync::Arc;
+
+use arrow_array_57::types::{Float32Type, Float64Type, Int8Type, Int16Type, Int32Type, Int64Type};
+use arrow_array_57::{
+    ArrayRef, ArrowPrimitiveType, BinaryArray, BooleanArray, Date32Array, FixedSizeListArray,
+    Float64Array, Int64Array, IntervalMonthDayNanoArray, ListArray, NullArray, PrimitiveArray,
+    RecordBatch, RecordBatchOptions, StringArray, StructArray, Time64NanosecondArray,
+    TimestampNanosecondArray,
+};
+use arrow_buffer_57::{IntervalMonthDayNano, NullBuffer, OffsetBuffer};
+use arrow_schema_57::{DataType, Field, Fields, IntervalUnit, Schema, SchemaRef, TimeUnit};
+use thiserror::Error;
+
+use super::ValueReceive;
+use super::graph::{Node, Path, RelationshipDirection};
+use super::value_path::{ValuePathElement, ValuePathError};
+use super::vector::Vector;
+use crate::driver::Record;
+use crate::error_::Neo4jError;
+
+/// Error returned when records cannot be converted into an Arrow [`RecordBatch`].
+///
+/// Requires the `arrow_57` feature.
+#[derive(Debug, Clone, Error)]
+#[error("{inner}")]
+pub struct ArrowConversionError {
+    inner: ValuePathError,
+}
+
+impl ArrowConversionError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            inner: ValuePathError::new(message),
+        }
+    }
+
+    fn prepend_path(self, element: &str) -> Self {
+        Self {
+            inner: self
+                .inner
+                .prepend_path(ValuePathElement::Key(String::from(element))),
+        }
+    }
+
+    /// The path (column, then struct fields) to the values that failed to convert.
+    ///
+    /// The path is empty if the error isn't specific to a column.
+    pub fn path(&self) -> &[ValuePathElement] {
+        self.inner.path()
+    }
+
+    /// The reason the conversion failed (without the path).
+    pub fn message(&self) -> &str {
+        self.inner.message()
+    }
+}
+
+/// Error returned when streaming records as Arrow [`RecordBatch`]es.
+///
+/// Requires the `arrow_57` feature.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ArrowBatchError {
+    /// Fetching the records failed.
+    #[error(transparent)]
+    Neo4jError(#[from] Neo4jError),
+    /// Converting the records failed.
+    #[error(transparent)]
+    Conversion(#[from] ArrowConversionError),
+}
+
+type ConversionResult<T> = Result<T, ArrowConversionError>;
+
+/// Converts chunks of records into record batches.
+///
+/// Unless provided up front, the schema is inferred from the records.
+/// Later chunks widen the inferred schema where earlier ones only held nulls.
+#[derive(Debug)]
+pub(crate) struct ArrowConverter {
+    keys: Vec<Arc<String>>,
+    schema: Option<SchemaRef>,
+    inferred: bool,
+}
+
+impl ArrowConverter {
+    pub(crate) fn new(keys: Vec<Arc<String>>, schema: Option<SchemaRef>) -> Self {
+        Self {
+            keys,
+            schema,
+            inferred: false,
+        }
+    }
+
+    pub(crate) fn convert(&mut self, records: &[Record]) -> ConversionResult<RecordBatch> {
+        let columns = self
+            .keys
+            .iter()
+            .map(|key| {
+                records
+                    .iter()
+                    .map(|record| record.value(key).map(Cell::new).unwrap_or(Ok(Cell::Null)))
+                    .collect::<ConversionResult<Vec<_>>>()
+                    .map_err(|e| e.prepend_path(key))
+            })
+            .collect::<ConversionResult<Vec<_>>>()?;
+        let schema = match &self.schema {
+            Some(schema) if !self.inferred => Arc::clone(schema),
+            previous => {
+                let fields = self
+                    .keys
+                    .iter()
+                    .zip(&columns)
+                    .enumerate()
+                    .map(|(i, (key, cells))| {
+                        // widens what earlier chunks only knew as null
+                        let previous = previous
+                            .as_ref()
+                            .map(|schema| schema.field(i).data_type().clone())
+                            .unwrap_or(DataType::Null);
+                        infer_all(cells)
+                            .and_then(|data_type| merge(previous, data_type))
+                            .map(|data_type| Field::new(key.as_str(), data_type, true))
+                            .map_err(|e| e.prepend_path(key))
+                    })
+                    .collect::<ConversionResult<Vec<_>>>()?;
+                let schema = Schema::new(fields);
+                let schema = match previous {
+                    Some(previous) if **previous == schema => Arc::clone(previous),
+                    _ => Arc::new(schema),
+                };
+                self.schema = Some(Arc::clone(&schema));
+                self.inferred = true;
+                schema
+            }
+        };
+        if schema.fields().len() != columns.len() {
+            return Err(ArrowConversionError::new(format!(
+                "schema has {} fields, but records have {} keys",
+                schema.fields().len(),
+                columns.len()
+            )));
+        }
+        let arrays = schema
+            .fields()
+            .iter()
+            .zip(&columns)
+            .map(|(field, cells)| {
+                let cells = cells.iter().collect::<Vec<_>>();
+                build(&cells, field.data_type()).map_err(|e| e.prepend_path(field.name()))
+            })
+            .collect::<ConversionResult<Vec<_>>>()?;
+        RecordBatch::try_new_with_options(
+            schema,
+            arrays,
+            &RecordBatchOptions::new().with_row_count(Some(records.len())),
+        )
+        .map_err(|e| ArrowConversionError::new(e.to_string()))
+    }
+}
+
+/// A value in a shape that maps directly onto Arrow types.
+#[derive(Debug)]
+enum Cell<'a> {
+    Null,
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    Bytes(&'a [u8]),
+    String(&'a str),
+    List(Vec<Cell<'a>>),
+    Struct(Vec<(&'a str, Cell<'a>)>),
+    Date(i32),
+    LocalTime(i64),
+    LocalDateTime(i64),
+    UtcDateTime(i64),
+    Duration(IntervalMonthDayNano),
+    Vector(&'a Vector),
+}
+
+impl<'a> Cell<'a> {
+    fn new(value: &'a ValueReceive) -> ConversionResult<Self> {
+        Ok(match value {
+            ValueReceive::Null => Self::Null,
+            ValueReceive::Boolean(v) => Self::Boolean(*v),
+            ValueReceive::Integer(v) => Self::Integer(*v),
+            ValueReceive::Float(v) => Self::Float(*v),
+            ValueReceive::Bytes(v) => Self::Bytes(v),
+            ValueReceive::String(v) => Self::String(v),
+            ValueReceive::List(v) => Self::List(v.iter().map(Self::new).collect::<Result<_, _>>()?),
+            ValueReceive::Map(v) => {
+                let mut entries = v
+                    .iter()
+                    .map(|(k, v)| Ok((k.as_str(), Self::new(v)?)))
+                    .collect::<ConversionResult<Vec<_>>>()?;
+                entries.sort_by_key(|(k, _)| *k);
+                Self::Struct(entries)
+            }
+            ValueReceive::Cartesian2D(v) => Self::Struct(vec![
+                ("srid", Self::Integer(v.srid())),
+                ("x", Self::Float(v.x())),
+                ("y", Self::Float(v.y())),
+            ]),
+            ValueReceive::Cartesian3D(v) => Self::Struct(vec![
+                ("srid", Self::Integer(v.srid())),
+                ("x", Self::Float(v.x())),
+                ("y", Self::Float(v.y())),
+                ("z", Self::Float(v.z())),
+            ]),
+            ValueReceive::WGS84_2D(v) => Self::Struct(vec![
+                ("srid", Self::Integer(v.srid())),
+                ("longitude", Self::Float(v.longitude())),
+                ("latitude", Self::Float(v.latitude())),
+            ]),
+            ValueReceive::WGS84_3D(v) => Self::Struct(vec![
+                ("srid", Self::Integer(v.srid())),
+                ("longitude", Self::Float(v.longitude())),
+                ("latitude", Self::Float(v.latitude())),
+                ("altitude", Self::Float(v.altitude())),
+            ]),
+            ValueReceive::Duration(v) => {
+                let months = i32::try_from(v.months())
+                    .map_err(|_| ArrowConversionError::new("duration months out of range"))?;
+                let days = i32::try_from(v.days())
+                    .map_err(|_| ArrowConversionError::new("duration days out of range"))?;
+                let nanos = timestamp_nanos(v.seconds(), v.nanoseconds().into())
+                    .ok_or_else(|| ArrowConversionError::new("duration seconds out of range"))?;
+                Self::Duration(IntervalMonthDayNano::new(months, days, nanos))
+            }
+            ValueReceive::LocalTime(v) => {
+                Self::LocalTime(local_time_nanos(v.nanos_since_midnight()))
+            }
+            ValueReceive::Time(v) => Self::Struct(vec![
+                (
+                    "time",
+                    Self::LocalTime(local_time_nanos(v.nanos_since_midnight())),
+                ),
+                ("utc_offset", Self::Integer(v.utc_offset().into())),
+            ]),
+            ValueReceive::Date(v) => Self::Date(
+                i32::try_from(v.ordinal())
+                    .map_err(|_| ArrowConversionError::new("date out of range"))?,
+            ),
+            ValueReceive::LocalDateTime(v) => {
+                let (secs, nanos) = v.timestamp();
+                Self::LocalDateTime(
+                    timestamp_nanos(secs, nanos.into())
+                        .ok_or_else(|| ArrowConversionError::new("local date time out of range"))?,
+                )
+            }
+            ValueReceive::DateTime(v) => {
+                let (secs, nanos) = v.utc_timestamp();
+                Self::UtcDateTime(
+                    timestamp_nanos(secs, nanos.into())
+                        .ok_or_else(|| ArrowConversionError::new("date time out of range"))?,
+                )
+            }
+            ValueReceive::DateTimeFixed(v) => {
+                let (secs, nanos) = v.utc_timestamp();
+                Self::UtcDateTime(
+                    timestamp_nanos(secs, nanos.into())
+                        .ok_or_else(|| ArrowConversionError::new("date time out of range"))?,
+                )
+            }
+            ValueReceive::Vector(v) => Self::Vector(v),
+            ValueReceive::Node(v) => Self::node(v)?,
+            ValueReceive::Relationship(v) => Self::Struct(vec![
+                ("element_id", Self::String(&v.element_id)),
+                ("type", Self::String(&v.type_)),
+                (
+                    "start_node_element_id",
+                    Self::String(&v.start_node_element_id),
+                ),
+                ("end_node_element_id", Self::String(&v.end_node_element_id)),
+                ("properties", Self::properties(v.properties.iter())?),
+            ]),
+            ValueReceive::Path(v) => Self::path(v)?,
+            ValueReceive::UnsupportedType(v) => {
+                return Err(ArrowConversionError::new(format!(
+                    "cannot convert unsupported type {v:?}"
+                )));
+            }
+            ValueReceive::BrokenValue(v) => {
+                return Err(ArrowConversionError::new(format!(
+                    "cannot convert broken value: {}",
+                    v.reason()
+                )));
+            }
+        })
+    }
+
+    fn node(node: &'a Node) -> ConversionResult<Self> {
+        Ok(Self::Struct(vec![
+            ("element_id", Self::String(&node.element_id)),
+            (
+                "labels",
+                Self::List(node.labels.iter().map(|l| Self::String(l)).collect()),
+            ),
+            ("properties", Self::properties(node.properties.iter())?),
+        ]))
+    }
+
+    fn properties(
+        properties: impl Iterator<Item = (&'a String, &'a ValueReceive)>,
+    ) -> ConversionResult<Self> {
+        let mut entries = properties
+            .map(|(k, v)| {
+                Self::new(v)
+                    .map(|v| (k.as_str(), v))
+                    .map_err(|e| e.prepend_path(k))
+            })
+            .collect::<ConversionResult<Vec<_>>>()
+            .map_err(|e| e.prepend_path("properties"))?;
+        entries.sort_by_key(|(k, _)| *k);
+        Ok(Self::Struct(entries))
+    }
+
+    fn path(path: &'a Path) -> ConversionResult<Self> {
+        path.verify_invariants()
+            .map_err(|e| ArrowConversionError::new(format!("invalid path: {e}")))?;
+        let (start, hops) = path.traverse();
+        let mut nodes = Vec::with_capacity(hops.len() + 1);
+        let mut relationships = Vec::with_capacity(hops.len());
+        nodes.push(Self::node(start)?);
+        let mut previous = start;
+        for (direction, relationship, next) in hops {
+            let (start, end) = match direction {
+                RelationshipDirection::To => (previous, next),
+                RelationshipDirection::From => (next, previous),
+            };
+            relationships.push(Self::Struct(vec![
+                ("element_id", Self::String(&relationship.element_id)),
+                ("type", Self::String(&relationship.type_)),
+                ("start_node_element_id", Self::String(&start.element_id)),
+                ("end_node_element_id", Self::String(&end.element_id)),
+                (
+                    "properties",
+                    Self::properties(relationship.properties.iter())?,
+                ),
+            ]));
+            nodes.push(Self::node(next)?);
+            previous = next;
+        }
+        Ok(Self::Struct(vec![
+            ("nodes", Self::List(nodes)),
+            ("relationships", Self::List(relationships)),
+        ]))
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Boolean(_) => "boolean",
+            Self::Integer(_) => "integer",
+            Self::Float(_) => "float",
+            Self::Bytes(_) => "bytes",
+            Self::String(_) => "string",
+            Self::List(_) => "list",
+            Self::Struct(_) => "map",
+            Self::Date(_) => "date",
+            Self::LocalTime(_) => "local time",
+            Self::LocalDateTime(_) => "local date time",
+            Self::UtcDateTime(_) => "date time",
+            Self::Duration(_) => "duration",
+            Self::Vector(_) => "vector",
+        }
+    }
+}
+
+fn local_time_nanos(nanos: u64) -> i64 {
+    i64::try_from(nanos).expect("nanoseconds since midnight fit into i64")
+}
+
+fn timestamp_nanos(secs: i64, nanos: i64) -> Option<i64> {
+    secs.checked_mul(1_000_000_000)?.checked_add(nanos)
+}
+
+fn list_field(data_type: DataType) -> Arc<Field> {
+    Arc::new(Field::new_list_field(data_type, true))
+}
+
+fn infer(cell: &Cell) -> ConversionResult<DataType> {
+    Ok(match cell {
+        Cell::Null => DataType::Null,
+        Cell::Boolean(_) => DataType::Boolean,
+        Cell::Integer(_) => DataType::Int64,
+        Cell::Float(_) => DataType::Float64,
+        Cell::Bytes(_) => DataType::Binary,
+        Cell::String(_) => DataType::Utf8,
+        Cell::List(items) => DataType::List(list_field(infer_all(items)?)),
+        Cell::Struct(entries) => DataType::Struct(
+            entries
+                .iter()
+                .map(|(k, v)| {
+                    infer(v)
+                        .map(|data_type| Field::new(*k, data_type, true))
+                        .map_err(|e| e.prepend_path(k))
+                })
+                .collect::<ConversionResult<Fields>>()?,
+        ),
+        Cell::Date(_) => DataType::Date32,
+        Cell::LocalTime(_) => DataType::Time64(TimeUnit::Nanosecond),
+        Cell::LocalDateTime(_) => DataType::Timestamp(TimeUnit::Nanosecond, None),
+        Cell::UtcDateTime(_) => DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into())),
+        Cell::Duration(_) => DataType::Interval(IntervalUnit::MonthDayNano),
+        Cell::Vector(v) => {
+            let (data_type, len) = match v {
+                Vector::F64(v) => (DataType::Float64, v.len()),
+                Vector::F32(v) => (DataType::Float32, v.len()),
+                Vector::I64(v) => (DataType::Int64, v.len()),
+                Vector::I32(v) => (DataType::Int32, v.len()),
+                Vector::I16(v) => (DataType::Int16, v.len()),
+                Vector::I8(v) => (DataType::Int8, v.len()),
+            };
+            let len =
+                i32::try_from(len).map_err(|_| ArrowConversionError::new("vector too long"))?;
+            DataType::FixedSizeList(Arc::new(Field::new_list_field(data_type, false)), len)
+        }
+    })
+}
+
+fn infer_all(cells: &[Cell]) -> ConversionResult<DataType> {
+    cells.iter().try_fold(DataType::Null, |data_type, cell| {
+        merge(data_type, infer(cell)?)
+    })
+}
+
+fn merge(a: DataType, b: DataType) -> ConversionResult<DataType> {
+    Ok(match (a, b) {
+        (DataType::Null, b) => b,
+        (a, DataType::Null) => a,
+        (DataType::List(a), DataType::List(b)) => DataType::List(list_field(merge(
+            a.data_type().clone(),
+            b.data_type().clone(),
+        )?)),
+        (DataType::Struct(a), DataType::Struct(b)) => {
+            let mut fields = a.iter().map(|f| Field::clone(f)).collect::<Vec<_>>();
+            for field_b in b.iter() {
+                match fields.iter_mut().find(|f| f.name() == field_b.name()) {
+                    Some(field_a) => {
+                        let data_type =
+                            merge(field_a.data_type().clone(), field_b.data_type().clone())
+                                .map_err(|e| e.prepend_path(field_b.name()))?;
+                        field_a.set_data_type(data_type);
+                    }
+                    None => fields.push(Field::clone(field_b)),
+                }
+            }
+            DataType::Struct(fields.into())
+        }
+        (a, b) if a == b => a,
+        (a, b) => {
+            return Err(ArrowConversionError::new(format!(
+                "cannot combine values of types {a} and {b}"
+            )));
+        }
+    })
+}
+
+static NULL_CELL: Cell<'static> = Cell::Null;
+
+fn nulls(cells: &[&Cell]) -> Option<NullBuffer> {
+    let validity = cells
+        .iter()
+        .map(|cell| !matches!(cell, Cell::Null))
+        .collect::<Vec<_>>();
+    match validity.iter().all(|valid| *valid) {
+        true => None,
+        false => Some(NullBuffer::from(validity)),
+    }
+}
+
+fn mismatch(data_type: &DataType, cell: &Cell) -> ArrowConversionError {
+    ArrowConversionError::new(format!(
+        "expected value for type {data_type}, found {}",
+        cell.kind()
+    ))
+}
+
+fn values<'c, 'a: 'c, T>(
+    cells: &[&'c Cell<'a>],
+    data_type: &DataType,
+    extract: impl Fn(&'c Cell<'a>) -> Option<T>,
+) -> ConversionResult<Vec<Option<T>>> {
+    cells
+        .iter()
+        .map(|cell| match cell {
+            Cell::Null => Ok(None),
+            cell => extract(cell)
+                .map(Some)
+                .ok_or_else(|| mismatch(data_type, cell)),
+        })
+        .collect()
+}
+
+fn build(cells: &[&Cell], data_type: &DataType) -> ConversionResult<ArrayRef> {
+    Ok(match data_type {
+        DataType::Null => {
+            if let Some(cell) = cells.iter().find(|cell| !matches!(cell, Cell::Null)) {
+                return Err(mismatch(data_type, cell));
+            }
+            Arc::new(NullArray::new(cells.len()))
+        }
+        DataType::Boolean => Arc::new(BooleanArray::from(values(
+            cells,
+            data_type,
+            |cell| match cell {
+                Cell::Boolean(v) => Some(*v),
+                _ => None,
+            },
+        )?)),
+        DataType::Int64 => Arc::new(Int64Array::from(values(
+            cells,
+            data_type,
+            |cell| match cell {
+                Cell::Integer(v) => Some(*v),
+                _ => None,
+            },
+        )?)),
+        DataType::Float64 => Arc::new(Float64Array::from(values(
+            cells,
+            data_type,
+            |cell| match cell {
+                Cell::Float(v) => Some(*v),
+                _ => None,
+            },
+        )?)),
+        DataType::Binary => Arc::new(BinaryArray::from(values(
+            cells,
+            data_type,
+            |cell| match cell {
+                Cell::Bytes(v) => Some(*v),
+                _ => None,
+            },
+        )?)),
+        DataType::Utf8 => Arc::new(StringArray::from(values(
+            cells,
+            data_type,
+            |cell| match cell {
+                Cell::String(v) => Some(*v),
+                _ => None,
+            },
+        )?)),
+        DataType::Date32 => Arc::new(Date32Array::from(values(
+            cells,
+            data_type,
+            |cell| match cell {
+                Cell::Date(v) => Some(*v),
+                _ => None,
+            },
+        )?)),
+        DataType::Time64(TimeUnit::Nanosecond) => Arc::new(Time64NanosecondArray::from(values(
+            cells,
+            data_type,
+            |cell| match cell {
+                Cell::LocalTime(v) => Some(*v),
+                _ => None,
+            },
+        )?)),
+        DataType::Timestamp(TimeUnit::Nanosecond, None) => Arc::new(
+            TimestampNanosecondArray::from(values(cells, data_type, |cell| match cell {
+                Cell::LocalDateTime(v) => Some(*v),
+                _ => None,
+            })?),
+        ),
+        DataType::Timestamp(TimeUnit::Nanosecond, Some(tz)) => Arc::new(
+            TimestampNanosecondArray::from(values(cells, data_type, |cell| match cell {
+                Cell::UtcDateTime(v) => Some(*v),
+                _ => None,
+            })?)
+            .with_timezone(Arc::clone(tz)),
+        ),
+        DataType::Interval(IntervalUnit::MonthDayNano) => Arc::new(
+            IntervalMonthDayNanoArray::from(values(cells, data_type, |cell| match cell {
+                Cell::Duration(v) => Some(*v),
+                _ => None,
+            })?),
+        ),
+        DataType::List(field) => {
+            let lists = values(cells, data_type, |cell| match cell {
+                Cell::List(items) => Some(items),
+                _ => None,
+            })?;
+            let offsets = OffsetBuffer::from_lengths(
+                lists.iter().map(|items| items.map(Vec::len).unwrap_or(0)),
+            );
+            let items = lists
+                .iter()
+                .flatten()
+                .flat_map(|items| items.iter())
+                .collect::<Vec<_>>();
+            let items = build(&items, field.data_type())?;
+            Arc::new(
+                ListArray::try_new(Arc::clone(field), offsets, items, nulls(cells))
+                    .map_err(|e| ArrowConversionError::new(e.to_string()))?,
+            )
+        }
+        DataType::Struct(fields) => {
+            let entries = values(cells, data_type, |cell| match cell {
+                Cell::Struct(entries) => Some(entries),
+                _ => None,
+            })?;
+            let children = fields
+                .iter()
+                .map(|field| {
+                    let cells = entries
+                        .iter()
+                        .copied()
+                        .map(|entries| {
+                            entries
+                                .and_then(|entries| {
+                                    entries.iter().find(|(k, _)| *k == field.name())
+                                })
+                                .map(|(_, cell)| cell)
+                                .unwrap_or(&NULL_CELL)
+                        })
+                        .collect::<Vec<_>>();
+                    build(&cells, field.data_type()).map_err(|e| e.prepend_path(field.name()))
+                })
+                .collect::<ConversionResult<Vec<_>>>()?;
+            Arc::new(
+                StructArray::try_new_with_length(
+                    fields.clone(),
+                    children,
+                    nulls(cells),
+                    cells.len(),
+                )
+                .map_err(|e| ArrowConversionError::new(e.to_string()))?,
+            )
+        }
+        DataType::FixedSizeList(field, len) => {
+            let vectors = values(cells, data_type, |cell| match cell {
+                Cell::Vector(v) => Some(*v),
+                _ => None,
+            })?;
+            let items = match field.data_type() {
+                DataType::Float64 => {
+                    vector_items::<Float64Type>(&vectors, *len, data_type, Vector::as_vec_f64)?
+                }
+                DataType::Float32 => {
+                    vector_items::<Float32Type>(&vectors, *len, data_type, Vector::as_vec_f32)?
+                }
+                DataType::Int64 => {
+                    vector_items::<Int64Type>(&vectors, *len, data_type, Vector::as_vec_i64)?
+                }
+                DataType::Int32 => {
+                    vector_items::<Int32Type>(&vectors, *len, data_type, Vector::as_vec_i32)?
+                }
+                DataType::Int16 => {
+                    vector_items::<Int16Type>(&vectors, *len, data_type, Vector::as_vec_i16)?
+                }
+                DataType::Int8 => {
+                    vector_items::<Int8Type>(&vectors, *len, data_type, Vector::as_vec_i8)?
+                }
+                _ => return Err(unsupported(data_type)),
+            };
+            Arc::new(
+                FixedSizeListArray::try_new(Arc::clone(field), *len, items, nulls(cells))
+                    .map_err(|e| ArrowConversionError::new(e.to_string()))?,
+            )
+        }
+        _ => return Err(unsupported(data_type)),
+    })
+}
+
+fn unsupported(data_type: &DataType) -> ArrowConversionError {
+    ArrowConversionError::new(format!("cannot convert values into type {data_type}"))
+}
+
+fn vector_items<T: ArrowPrimitiveType>(
+    vectors: &[Option<&Vector>],
+    len: i32,
+    data_type: &DataType,
+    as_vec: impl Fn(&Vector) -> Option<&Vec<T::Native>>,
+) -> ConversionResult<ArrayRef> {
+    let len = usize::try_from(len).map_err(|_| unsupported(data_type))?;
+    let mut items = Vec::with_capacity(vectors.len() * len);
+    for vector in vectors {
+        match vector {
+            None => items.extend(std::iter::repeat_n(T::Native::default(), len)),
+            Some(vector) => {
+                let vector_items = as_vec(vector)
+                    .filter(|items| items.len() == len)
+                    .ok_or_else(|| {
+                        ArrowConversionError::new(format!(
+                            "expected value for type {data_type}, found {vector}"
+                        ))
+                    })?;
+                items.extend_from_slice(vector_items);
+            }
+        }
+    }
+    Ok(Arc::new(PrimitiveArray::<T>::from_iter_values(items)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+
+    use arrow_array_57::Array;
+    use arrow_array_57::cast::AsArray;
+    use arrow_array_57::types::{Date32Type, TimestampNanosecondType};
+    use rstest::rstest;
+
+    use crate::value::graph::UnboundRelationship;
+    use crate::value::time::{Date, DateTime, Duration, LocalDateTime};
+
+    fn records(key: &str, values: Vec<ValueReceive>) -> Vec<Record> {
+        let key = Arc::new(String::from(key));
+        values
+            .into_iter()
+            .map(|value| Record::from_entries([(Arc::clone(&key), value)]))
+            .collect()
+    }
+
+    fn convert(values: Vec<ValueReceive>) -> ConversionResult<RecordBatch> {
+        ArrowConverter::new(vec![Arc::new(String::from("x"))], None).convert(&records("x", values))
+    }
+
+    fn map(entries: &[(&str, ValueReceive)]) -> ValueReceive {
+        ValueReceive::Map(
+            entries
+                .iter()
+                .map(|(k, v)| (String::from(*k), v.clone()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_scalars_with_nulls() {
+        let batch = convert(vec![
+            ValueReceive::Integer(1),
+            ValueReceive::Null,
+            ValueReceive::Integer(3),
+        ])
+        .unwrap();
+
+        assert_eq!(batch.schema().field(0).data_type(), &DataType::Int64);
+        let column = batch.column(0).as_primitive::<Int64Type>();
+        assert_eq!(
+            column.iter().collect::<Vec<_>>(),
+            vec![Some(1), None, Some(3)]
+        );
+    }
+
+    #[test]
+    fn test_missing_values_are_null() {
+        let mut records = records("x", vec![ValueReceive::Boolean(true)]);
+        records[0].take_value("x");
+        let batch = ArrowConverter::new(vec![Arc::new(String::from("x"))], None)
+            .convert(&records)
+            .unwrap();
+
+        assert_eq!(batch.num_rows(), 1);
+        assert_eq!(batch.schema().field(0).data_type(), &DataType::Null);
+    }
+
+    #[test]
+    fn test_maps_become_structs_with_union_of_keys() {
+        let batch = convert(vec![
+            map(&[("a", ValueReceive::Integer(1))]),
+            map(&[("b", ValueReceive::String(String::from("b")))]),
+        ])
+        .unwrap();
+
+        let column = batch.column(0).as_struct();
+        assert_eq!(column.column_names(), vec!["a", "b"]);
+        let a = column
+            .column_by_name("a")
+            .unwrap()
+            .as_primitive::<Int64Type>();
+        assert_eq!(a.iter().collect::<Vec<_>>(), vec![Some(1), None]);
+        let b = column.column_by_name("b").unwrap().as_string::<i32>();
+        assert_eq!(b.iter().collect::<Vec<_>>(), vec![None, Some("b")]);
+    }
+
+    #[test]
+    fn test_lists() {
+        let batch = convert(vec![
+            ValueReceive::List(vec![ValueReceive::Float(1.0), ValueReceive::Float(2.0)]),
+            ValueReceive::List(vec![]),
+            ValueReceive::Null,
+        ])
+        .unwrap();
+
+        let column = batch.column(0).as_list::<i32>();
+        assert_eq!(column.value_offsets(), &[0, 2, 2, 2]);
+        assert!(column.is_null(2));
+        assert_eq!(
+            column.values().as_primitive::<Float64Type>().values(),
+            &[1.0, 2.0]
+        );
+    }
+
+    #[test]
+    fn test_temporal() {
+        let key = Arc::new(String::from("x"));
+        let entries = |i| {
+            let date = Date::from_ordinal(i).unwrap();
+            let local_date_time = LocalDateTime::from_timestamp(i, 5).unwrap();
+            let date_time = DateTime::from_utc_timestamp(i, 7, "Europe/Stockholm").unwrap();
+            let duration = Duration::new(1, 2, 3, 4).unwrap();
+            [
+                (Arc::clone(&key), ValueReceive::Date(date)),
+                (
+                    Arc::new(String::from("ldt")),
+                    ValueReceive::LocalDateTime(local_date_time),
+                ),
+                (
+                    Arc::new(String::from("dt")),
+                    ValueReceive::DateTime(date_time),
+                ),
+                (
+                    Arc::new(String::from("d")),
+                    ValueReceive::Duration(duration),
+                ),
+            ]
+        };
+        let records = vec![Record::from_entries(entries(10))];
+        let keys = ["x", "ldt", "dt", "d"]
+            .into_iter()
+            .map(|key| Arc::new(String::from(key)))
+            .collect();
+
+        let batch = ArrowConverter::new(keys, None).convert(&records).unwrap();
+
+        assert_eq!(batch.column(0).as_primitive::<Date32Type>().value(0), 10);
+        let ldt = batch.column(1).as_primitive::<TimestampNanosecondType>();
+        assert_eq!(ldt.timezone(), None);
+        assert_eq!(ldt.value(0), 10_000_000_005);
+        let dt = batch.column(2).as_primitive::<TimestampNanosecondType>();
+        assert_eq!(dt.timezone(), Some("UTC"));
+        assert_eq!(dt.value(0), 10_000_000_007);
+        assert_eq!(
+            batch
+                .column(3)
+                .as_primitive::<arrow_array_57::types::IntervalMonthDayNanoType>()
+                .value(0),
+            IntervalMonthDayNano::new(1, 2, 3_000_000_004)
+        );
+    }
+
+    #[test]
+    fn test_local_date_time_out_of_range() {
+        let value = LocalDateTime::from_timestamp(i64::MAX / 2, 0).unwrap();
+        let err = convert(vec![ValueReceive::LocalDateTime(value)]).unwrap_err();
+
+        assert_eq!(err.to_string(), "at `x`: local date time out of range");
+    }
+
+    #[test]
+    fn test_vectors() {
+        let batch = convert(vec![
+            ValueReceive::Vector(Vector::F32(vec![1.0, 2.0])),
+            ValueReceive::Null,
+            ValueReceive::Vector(Vector::F32(vec![3.0, 4.0])),
+        ])
+        .unwrap();
+
+        let column = batch.column(0).as_fixed_size_list();
+        assert_eq!(column.value_length(), 2);
+        assert!(column.is_null(1));
+        assert_eq!(
+            column.values().as_primitive::<Float32Type>().values(),
+            &[1.0, 2.0, 0.0, 0.0, 3.0, 4.0]
+        );
+    }
+
+    #[rstest]
+    #[case(
+        vec![ValueReceive::Integer(1), ValueReceive::String(String::from("a"))],
+        "at `x`: cannot combine values of types Int64 and Utf8"
+    )]
+    #[case(
+        vec![
+            map(&[("a", ValueReceive::Integer(1))]),
+            map(&[("a", ValueReceive::Float(1.0))]),
+        ],
+        "at `x.a`: cannot combine values of types Int64 and Float64"
+    )]
+    #[case(
+        vec![
+            ValueReceive::Vector(Vector::I8(vec![1])),
+            ValueReceive::Vector(Vector::I8(vec![1, 2])),
+        ],
+        "at `x`: cannot combine values of types FixedSizeList(1 x non-null Int8) and \
+        FixedSizeList(2 x non-null Int8)"
+    )]
+    fn test_incompatible_values(#[case] values: Vec<ValueReceive>, #[case] error: &str) {
+        assert_eq!(convert(values).unwrap_err().to_string(), error);
+    }
+
+    #[test]
+    fn test_later_batch_widens_null_columns() {
+        let mut converter = ArrowConverter::new(vec![Arc::new(String::from("x"))], None);
+        let batch = converter
+            .convert(&records("x", vec![ValueReceive::Null]))
+            .unwrap();
+        assert_eq!(batch.column(0).data_type(), &DataType::Null);
+
+        let batch = converter
+            .convert(&records(
+                "x",
+                vec![map(&[("a", ValueReceive::Null)]), ValueReceive::Null],
+            ))
+            .unwrap();
+        assert_eq!(
+            batch.column(0).data_type(),
+            &DataType::Struct(vec![Field::new("a", DataType::Null, true)].into())
+        );
+
+        let batch = converter
+            .convert(&records(
+                "x",
+                vec![map(&[
+                    ("a", ValueReceive::Integer(1)),
+                    ("b", ValueReceive::Boolean(true)),
+                ])],
+            ))
+            .unwrap();
+        assert_eq!(
+            batch.column(0).data_type(),
+            &DataType::Struct(
+                vec![
+                    Field::new("a", DataType::Int64, true),
+                    Field::new("b", DataType::Boolean, true),
+                ]
+                .into()
+            )
+        );
+
+        // batches with nulls only keep the widened type
+        let batch = converter
+            .convert(&records("x", vec![ValueReceive::Null]))
+            .unwrap();
+        assert_eq!(batch.column(0).as_struct().fields().len(), 2);
+
+        let err = converter
+            .convert(&records(
+                "x",
+                vec![map(&[("a", ValueReceive::String(String::from("1")))])],
+            ))
+            .unwrap_err();
+        assert_eq!(
+            err.path(),
+            &[
+                ValuePathElement::Key(String::from("x")),
+                ValuePathElement::Key(String::from("a")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_provided_schema() {
+        let schema = Arc::new(Schema::new(vec![Field::new("x", DataType::Int64, true)]));
+        let mut converter = ArrowConverter::new(vec![Arc::new(String::from("x"))], Some(schema));
+
+        let batch = converter
+            .convert(&records("x", vec![ValueReceive::Null]))
+            .unwrap();
+        assert_eq!(batch.column(0).data_type(), &DataType::Int64);
+        let batch = converter
+            .convert(&records("x", vec![ValueReceive::Integer(1)]))
+            .unwrap();
+        assert_eq!(batch.column(0).as_primitive::<Int64Type>().value(0), 1);
+        let err = converter
+            .convert(&records("x", vec![ValueReceive::Float(1.0)]))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "at `x`: expected value for type Int64, found float"
+        );
+    }
+
+    #[test]
+    fn test_graph_values() {
+        let alice = Node::builder()
+            .with_element_id("n1")
+            .with_label("Person")
+            .with_property("name", "Alice")
+            .build();
+        let bob = Node::builder()
+            .with_element_id("n2")
+            .with_label("Person")
+            .with_property("age", 42)
+            .build();
+        let knows = UnboundRelationship {
+            id: 0,
+            type_: String::from("KNOWS"),
+            properties: HashMap::new(),
+            element_id: String::from("r1"),
+        };
+        // (bob)<-[knows]-(alice)
+        let path =
+            Path::from_parts(vec![bob.clone(), alice.clone()], vec![knows], vec![-1, 1]).unwrap();
+        let keys = vec![Arc::new(String::from("n")), Arc::new(String::from("p"))];
+        let records = vec![
+            Record::from_entries([
+                (Arc::clone(&keys[0]), ValueReceive::Node(alice)),
+                (Arc::clone(&keys[1]), ValueReceive::Path(path)),
+            ]),
+            Record::from_entries([
+                (Arc::clone(&keys[0]), ValueReceive::Node(bob)),
+                (Arc::clone(&keys[1]), ValueReceive::Null),
+            ]),
+        ];
+
+        let batch = ArrowConverter::new(keys, None).convert(&records).unwrap();
+
+        let nodes = batch.column(0).as_struct();
+        assert_eq!(
+            nodes.column_names(),
+            vec!["element_id", "labels", "properties"]
+        );
+        let properties = nodes.column_by_name("properties").unwrap().as_struct();
+        assert_eq!(properties.column_names(), vec!["name", "age"]);
+
+        let path = batch.column(1).as_struct();
+        let relationships = path
+            .column_by_name("relationships")
+            .unwrap()
+            .as_list::<i32>()
+            .value(0);
+        let relationships = relationships.as_struct();
+        let end_node_element_ids = relationships
+            .column_by_name("end_node_element_id")
+            .unwrap()
+            .as_string::<i32>();
+        assert_eq!(end_node_element_ids.value(0), "n2");
+        let path_nodes = path
+            .column_by_name("nodes")
+            .unwrap()
+            .as_list::<i32>()
+            .value(0);
+        let path_node_ids = path_nodes
+            .as_struct()
+            .column_by_name("element_id")
+            .unwrap()
+            .as_string::<i32>()
+            .iter()
+            .collect::<Vec<_>>();
+        assert_eq!(path_node_ids, vec![Some("n2"), Some("n1")]);
+    }
+}
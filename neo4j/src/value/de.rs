@@ -1,192 +1,662 @@
-// todo: serde fun
-
-// use super::Value;
-// use serde::de::{Deserialize, Deserializer, EnumAccess, MapAccess, SeqAccess, Visitor};
-// use std::collections::HashMap;
-// use std::error::Error;
-// use std::fmt;
-//
-// impl<'de> Deserialize<'de> for Value {
-//     #[inline]
-//     fn deserialize<D>(deserializer: D) -> Result<Value, D::Error>
-//     where
-//         D: Deserializer<'de>,
-//     {
-//         struct ValueVisitor;
-//
-//         impl<'de> Visitor<'de> for ValueVisitor {
-//             type Value = Value;
-//
-//             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-//                 formatter.write_str("a Neo4j compatible types")
-//             }
-//
-//             fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
-//             where
-//                 E: Error,
-//             {
-//                 Ok(Value::Boolean(v))
-//             }
-//
-//             fn visit_i8<E>(self, v: i8) -> Result<Self::Value, E>
-//             where
-//                 E: Error,
-//             {
-//                 Ok(Value::Integer(v.into()))
-//             }
-//
-//             fn visit_i16<E>(self, v: i16) -> Result<Self::Value, E>
-//             where
-//                 E: Error,
-//             {
-//                 Ok(Value::Integer(v.into()))
-//             }
-//
-//             fn visit_i32<E>(self, v: i32) -> Result<Self::Value, E>
-//             where
-//                 E: Error,
-//             {
-//                 Ok(Value::Integer(v.into()))
-//             }
-//
-//             fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
-//             where
-//                 E: Error,
-//             {
-//                 Ok(Value::Integer(v.into()))
-//             }
-//
-//             fn visit_u8<E>(self, v: u8) -> Result<Self::Value, E>
-//             where
-//                 E: Error,
-//             {
-//                 Ok(Value::Integer(v.into()))
-//             }
-//
-//             fn visit_u16<E>(self, v: u16) -> Result<Self::Value, E>
-//             where
-//                 E: Error,
-//             {
-//                 Ok(Value::Integer(v.into()))
-//             }
-//
-//             fn visit_u32<E>(self, v: u32) -> Result<Self::Value, E>
-//             where
-//                 E: Error,
-//             {
-//                 Ok(Value::Integer(v.into()))
-//             }
-//
-//             fn visit_f32<E>(self, v: f32) -> Result<Self::Value, E>
-//             where
-//                 E: Error,
-//             {
-//                 Ok(Value::Float(v.into()))
-//             }
-//
-//             fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
-//             where
-//                 E: Error,
-//             {
-//                 Ok(Value::Float(v.into()))
-//             }
-//
-//             fn visit_char<E>(self, v: char) -> Result<Self::Value, E>
-//             where
-//                 E: Error,
-//             {
-//                 Ok(Value::String(v.into()))
-//             }
-//
-//             fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
-//             where
-//                 E: Error,
-//             {
-//                 Ok(Value::String(v.into()))
-//             }
-//
-//             fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
-//             where
-//                 E: Error,
-//             {
-//                 Ok(Value::Null)
-//             }
-//
-//             fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
-//             where
-//                 E: Error,
-//             {
-//                 Ok(Value::Null)
-//             }
-//
-//             fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
-//             where
-//                 E: Error,
-//             {
-//                 Ok(Value::Null)
-//             }
-//
-//             fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
-//             where
-//                 E: Error,
-//             {
-//                 Ok(Value::Null)
-//             }
-//
-//             fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
-//             where
-//                 E: Error,
-//             {
-//                 Ok(Value::Null)
-//             }
-//
-//             fn visit_none<E>(self) -> Result<Self::Value, E>
-//             where
-//                 E: Error,
-//             {
-//                 Ok(Value::Null)
-//             }
-//
-//             fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
-//             where
-//                 D: Deserializer<'de>,
-//             {
-//                 Deserialize::deserialize(deserializer)
-//             }
-//
-//             fn visit_unit<E>(self) -> Result<Self::Value, E>
-//             where
-//                 E: Error,
-//             {
-//                 Ok(Value::Null)
-//             }
-//
-//             fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-//             where
-//                 A: SeqAccess<'de>,
-//             {
-//                 let mut vec = Vec::new();
+// Copyright Rouven Bauer
 //
-//                 while let Some(v) = seq.next_element()? {
-//                     vec.push(v)
-//                 }
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
 //
-//                 Ok(Value::List(vec))
-//             }
+//    https://www.apache.org/licenses/LICENSE-2.0
 //
-//             fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
-//             where
-//                 A: MapAccess<'de>,
-//             {
-//                 // TODO: limit to string keys
-//                 let mut dict = HashMap::new();
-//                 while let Some((key, value)) = map.next_entry()? {
-//                     dict.insert(key, value);
-//                 }
-//                 Ok(Value::Dictionary(dict))
-//             }
-//         }
-//
-//         deserializer.deserialize_any(ValueVisitor)
-//     }
-// }
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::result::Result as StdResult;
+
+use serde::de::value::{BorrowedStrDeserializer, MapDeserializer, SeqDeserializer};
+use serde::de::{
+    self, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, Unexpected,
+    VariantAccess, Visitor,
+};
+use serde::{Deserialize, Deserializer};
+use thiserror::Error;
+
+use super::ValueReceive;
+use super::value_path::{ValuePathElement, ValuePathError};
+use super::vector::Vector;
+use crate::driver::Record;
+
+type Result<T> = StdResult<T, DeserializeError>;
+
+/// Error returned when deserializing a [`ValueReceive`] or [`Record`] into a user type fails.
+///
+/// Besides the reason of the failure, the error carries the path to the offending value.
+///
+/// # Example
+/// ```
+/// use std::collections::HashMap;
+///
+/// use neo4j::value::DeserializePathElement;
+/// use neo4j::ValueReceive;
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize)]
+/// # #[allow(dead_code)]
+/// struct Person {
+///     name: String,
+///     friends: Vec<String>,
+/// }
+///
+/// let value = ValueReceive::Map(HashMap::from([
+///     ("name".into(), ValueReceive::String("Alice".into())),
+///     (
+///         "friends".into(),
+///         ValueReceive::List(vec![
+///             ValueReceive::String("Bob".into()),
+///             ValueReceive::Integer(42),
+///         ]),
+///     ),
+/// ]));
+///
+/// let err = value.to::<Person>().unwrap_err();
+/// assert_eq!(
+///     err.path(),
+///     &[
+///         DeserializePathElement::Key("friends".into()),
+///         DeserializePathElement::Index(1),
+///     ]
+/// );
+/// assert_eq!(
+///     err.to_string(),
+///     "at `friends[1]`: invalid type: integer `42`, expected a string"
+/// );
+/// ```
+#[derive(Debug, Clone, Error)]
+#[error("{inner}")]
+pub struct DeserializeError {
+    inner: ValuePathError,
+}
+
+impl DeserializeError {
+    /// The path from the deserialized root to the value that failed to deserialize.
+    ///
+    /// The path is empty if the root itself failed to deserialize.
+    pub fn path(&self) -> &[DeserializePathElement] {
+        self.inner.path()
+    }
+
+    /// The reason the deserialization failed (without the path).
+    pub fn message(&self) -> &str {
+        self.inner.message()
+    }
+
+    pub(crate) fn prepend_path(self, element: DeserializePathElement) -> Self {
+        Self {
+            inner: self.inner.prepend_path(element),
+        }
+    }
+}
+
+impl de::Error for DeserializeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self {
+            inner: ValuePathError::new(msg.to_string()),
+        }
+    }
+}
+
+/// A single step in the path of a [`DeserializeError`].
+pub type DeserializePathElement = ValuePathElement;
+
+pub(crate) fn from_value<'de, T: Deserialize<'de>>(value: &'de ValueReceive) -> Result<T> {
+    T::deserialize(ValueDeserializer(value))
+}
+
+pub(crate) fn from_properties<'de, T: Deserialize<'de>>(
+    properties: &'de HashMap<String, ValueReceive>,
+) -> Result<T> {
+    T::deserialize(MapLikeDeserializer(PropertyIter(properties.iter())))
+}
+
+pub(crate) fn from_record<'de, T: Deserialize<'de>>(record: &'de Record) -> Result<T> {
+    T::deserialize(MapLikeDeserializer(record.entries_ref()))
+}
+
+struct ValueDeserializer<'de>(&'de ValueReceive);
+
+impl ValueDeserializer<'_> {
+    fn unexpected(&self) -> Unexpected<'_> {
+        match self.0 {
+            ValueReceive::Null => Unexpected::Unit,
+            ValueReceive::Boolean(v) => Unexpected::Bool(*v),
+            ValueReceive::Integer(v) => Unexpected::Signed(*v),
+            ValueReceive::Float(v) => Unexpected::Float(*v),
+            ValueReceive::Bytes(v) => Unexpected::Bytes(v),
+            ValueReceive::String(v) => Unexpected::Str(v),
+            ValueReceive::List(_) | ValueReceive::Vector(_) => Unexpected::Seq,
+            ValueReceive::Map(_) => Unexpected::Map,
+            value => Unexpected::Other(value.type_name()),
+        }
+    }
+}
+
+impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.0 {
+            ValueReceive::Null => visitor.visit_unit(),
+            ValueReceive::Boolean(v) => visitor.visit_bool(*v),
+            ValueReceive::Integer(v) => visitor.visit_i64(*v),
+            ValueReceive::Float(v) => visitor.visit_f64(*v),
+            ValueReceive::Bytes(v) => visitor.visit_borrowed_bytes(v),
+            ValueReceive::String(v) => visitor.visit_borrowed_str(v),
+            ValueReceive::List(v) => visitor.visit_seq(ListAccess::new(v.iter())),
+            ValueReceive::Map(v) => visitor.visit_map(EntryAccess::new(PropertyIter(v.iter()))),
+            ValueReceive::Node(v) => {
+                visitor.visit_map(EntryAccess::new(PropertyIter(v.properties.iter())))
+            }
+            ValueReceive::Relationship(v) => {
+                visitor.visit_map(EntryAccess::new(PropertyIter(v.properties.iter())))
+            }
+            ValueReceive::Cartesian2D(v) => visit_coordinates(
+                visitor,
+                [("srid", v.srid() as f64), ("x", v.x()), ("y", v.y())],
+            ),
+            ValueReceive::Cartesian3D(v) => visit_coordinates(
+                visitor,
+                [
+                    ("srid", v.srid() as f64),
+                    ("x", v.x()),
+                    ("y", v.y()),
+                    ("z", v.z()),
+                ],
+            ),
+            ValueReceive::WGS84_2D(v) => visit_coordinates(
+                visitor,
+                [
+                    ("srid", v.srid() as f64),
+                    ("longitude", v.longitude()),
+                    ("latitude", v.latitude()),
+                ],
+            ),
+            ValueReceive::WGS84_3D(v) => visit_coordinates(
+                visitor,
+                [
+                    ("srid", v.srid() as f64),
+                    ("longitude", v.longitude()),
+                    ("latitude", v.latitude()),
+                    ("altitude", v.altitude()),
+                ],
+            ),
+            ValueReceive::Vector(v) => match v {
+                Vector::F64(v) => visit_vector(visitor, v),
+                Vector::F32(v) => visit_vector(visitor, v),
+                Vector::I64(v) => visit_vector(visitor, v),
+                Vector::I32(v) => visit_vector(visitor, v),
+                Vector::I16(v) => visit_vector(visitor, v),
+                Vector::I8(v) => visit_vector(visitor, v),
+            },
+            ValueReceive::Path(_)
+            | ValueReceive::Duration(_)
+            | ValueReceive::LocalTime(_)
+            | ValueReceive::Time(_)
+            | ValueReceive::Date(_)
+            | ValueReceive::LocalDateTime(_)
+            | ValueReceive::DateTime(_)
+            | ValueReceive::DateTimeFixed(_)
+            | ValueReceive::UnsupportedType(_)
+            | ValueReceive::BrokenValue(_) => {
+                Err(de::Error::invalid_type(self.unexpected(), &visitor))
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.0 {
+            ValueReceive::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        match self.0 {
+            ValueReceive::String(variant) => {
+                visitor.visit_enum(BorrowedStrDeserializer::new(variant.as_str()))
+            }
+            ValueReceive::Map(map) if map.len() == 1 => {
+                let (variant, value) = map.iter().next().unwrap();
+                visitor
+                    .visit_enum(EnumDeserializer { variant, value })
+                    .map_err(|e| e.prepend_path(DeserializePathElement::Key(variant.clone())))
+            }
+            _ => Err(de::Error::invalid_type(
+                self.unexpected(),
+                &"a string or a map with a single key",
+            )),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+fn visit_coordinates<'de, V: Visitor<'de>, const N: usize>(
+    visitor: V,
+    coordinates: [(&'static str, f64); N],
+) -> Result<V::Value> {
+    let mut access = MapDeserializer::new(coordinates.into_iter());
+    let value = visitor.visit_map(&mut access)?;
+    access.end()?;
+    Ok(value)
+}
+
+fn visit_vector<'de, V: Visitor<'de>, T: Copy + IntoDeserializer<'de, DeserializeError>>(
+    visitor: V,
+    vector: &[T],
+) -> Result<V::Value> {
+    let mut access = SeqDeserializer::new(vector.iter().copied());
+    let value = visitor.visit_seq(&mut access)?;
+    access.end()?;
+    Ok(value)
+}
+
+struct ListAccess<I> {
+    iter: I,
+    index: usize,
+}
+
+impl<I> ListAccess<I> {
+    fn new(iter: I) -> Self {
+        Self { iter, index: 0 }
+    }
+}
+
+impl<'de, I: ExactSizeIterator<Item = &'de ValueReceive>> SeqAccess<'de> for ListAccess<I> {
+    type Error = DeserializeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        let Some(value) = self.iter.next() else {
+            return Ok(None);
+        };
+        let index = self.index;
+        self.index += 1;
+        seed.deserialize(ValueDeserializer(value))
+            .map(Some)
+            .map_err(|e| e.prepend_path(DeserializePathElement::Index(index)))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct PropertyIter<I>(I);
+
+impl<'de, I: Iterator<Item = (&'de String, &'de ValueReceive)>> Iterator for PropertyIter<I> {
+    type Item = (&'de str, &'de ValueReceive);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, v)| (k.as_str(), v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+struct EntryAccess<'de, I> {
+    iter: I,
+    value: Option<(&'de str, &'de ValueReceive)>,
+}
+
+impl<I> EntryAccess<'_, I> {
+    fn new(iter: I) -> Self {
+        Self { iter, value: None }
+    }
+}
+
+impl<'de, I: Iterator<Item = (&'de str, &'de ValueReceive)>> MapAccess<'de>
+    for EntryAccess<'de, I>
+{
+    type Error = DeserializeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        let Some((key, value)) = self.iter.next() else {
+            return Ok(None);
+        };
+        self.value = Some((key, value));
+        seed.deserialize(BorrowedStrDeserializer::new(key))
+            .map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let (key, value) = self
+            .value
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        seed.deserialize(ValueDeserializer(value))
+            .map_err(|e| e.prepend_path(DeserializePathElement::Key(key.into())))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        match self.iter.size_hint() {
+            (lower, Some(upper)) if lower == upper => Some(upper),
+            _ => None,
+        }
+    }
+}
+
+/// Deserializes key-value collections (records, properties) as maps or, if a sequence is
+/// requested, as the sequence of their values.
+struct MapLikeDeserializer<I>(I);
+
+impl<'de, I: Iterator<Item = (&'de str, &'de ValueReceive)>> Deserializer<'de>
+    for MapLikeDeserializer<I>
+{
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_map(EntryAccess::new(self.0))
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let values = self.0.map(|(_, v)| v).collect::<Vec<_>>();
+        visitor.visit_seq(ListAccess::new(values.into_iter()))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct map struct enum identifier ignored_any
+    }
+}
+
+struct EnumDeserializer<'de> {
+    variant: &'de str,
+    value: &'de ValueReceive,
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer<'de> {
+    type Error = DeserializeError;
+    type Variant = ValueDeserializer<'de>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self::Variant)> {
+        let variant = seed.deserialize(BorrowedStrDeserializer::new(self.variant))?;
+        Ok((variant, ValueDeserializer(self.value)))
+    }
+}
+
+impl<'de> VariantAccess<'de> for ValueDeserializer<'de> {
+    type Error = DeserializeError;
+
+    fn unit_variant(self) -> Result<()> {
+        Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_map(visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rstest::*;
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::value::graph::Node;
+    use crate::value::spatial::Cartesian2D;
+    use crate::value::time::Date;
+
+    fn map<const N: usize>(entries: [(&str, ValueReceive); N]) -> ValueReceive {
+        ValueReceive::Map(entries.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Person {
+        name: String,
+        age: u8,
+        #[serde(default)]
+        nick: Option<String>,
+    }
+
+    #[test]
+    fn test_struct_from_map() {
+        let value = map([
+            ("name", ValueReceive::String("Alice".into())),
+            ("age", ValueReceive::Integer(42)),
+            ("nick", ValueReceive::Null),
+        ]);
+        assert_eq!(
+            value.to::<Person>().unwrap(),
+            Person {
+                name: "Alice".into(),
+                age: 42,
+                nick: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_struct_from_node() {
+        let node = Node {
+            id: 1,
+            labels: vec!["Person".into()],
+            properties: [
+                ("name".into(), ValueReceive::String("Bob".into())),
+                ("age".into(), ValueReceive::Integer(7)),
+                ("nick".into(), ValueReceive::String("Bobby".into())),
+            ]
+            .into(),
+            element_id: "1".into(),
+        };
+        let expected = Person {
+            name: "Bob".into(),
+            age: 7,
+            nick: Some("Bobby".into()),
+        };
+        assert_eq!(node.to::<Person>().unwrap(), expected);
+        assert_eq!(ValueReceive::Node(node).to::<Person>().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_struct_from_record() {
+        let keys = [
+            Arc::new(String::from("name")),
+            Arc::new(String::from("age")),
+        ];
+        let record = Record::new(
+            &keys,
+            vec![
+                ValueReceive::String("Carol".into()),
+                ValueReceive::Integer(3),
+            ],
+        );
+        assert_eq!(
+            record.to::<Person>().unwrap(),
+            Person {
+                name: "Carol".into(),
+                age: 3,
+                nick: None,
+            }
+        );
+        assert_eq!(
+            record.to::<(String, i64)>().unwrap(),
+            (String::from("Carol"), 3)
+        );
+        assert_eq!(
+            record
+                .to::<HashMap<String, ValueReceiveLike>>()
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(untagged)]
+    #[allow(dead_code)]
+    enum ValueReceiveLike {
+        Int(i64),
+        String(String),
+    }
+
+    #[test]
+    fn test_borrowed_str() {
+        let value = ValueReceive::String("borrowed".into());
+        let s: &str = value.to().unwrap();
+        assert_eq!(s, "borrowed");
+    }
+
+    #[rstest]
+    #[case(map([("age", ValueReceive::Integer(1))]), &[], "missing field `name`")]
+    #[case(
+        map([("name", ValueReceive::Integer(1)), ("age", ValueReceive::Integer(1))]),
+        &[DeserializePathElement::Key("name".into())],
+        "at `name`: invalid type: integer `1`, expected a string",
+    )]
+    #[case(
+        map([("name", ValueReceive::String("".into())), ("age", ValueReceive::Integer(-1))]),
+        &[DeserializePathElement::Key("age".into())],
+        "at `age`: invalid value: integer `-1`, expected u8",
+    )]
+    fn test_error_path(
+        #[case] value: ValueReceive,
+        #[case] path: &[DeserializePathElement],
+        #[case] message: &str,
+    ) {
+        let err = value.to::<Person>().unwrap_err();
+        assert_eq!(err.path(), path);
+        assert_eq!(err.to_string(), message);
+    }
+
+    #[test]
+    fn test_nested_error_path() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Group {
+            members: Vec<Person>,
+        }
+
+        let value = map([(
+            "members",
+            ValueReceive::List(vec![
+                map([
+                    ("name", ValueReceive::String("Alice".into())),
+                    ("age", ValueReceive::Integer(1)),
+                ]),
+                map([
+                    ("name", ValueReceive::String("Bob".into())),
+                    ("age", ValueReceive::Float(1.5)),
+                ]),
+            ]),
+        )]);
+        let err = value.to::<Group>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "at `members[1].age`: invalid type: floating point `1.5`, expected u8"
+        );
+    }
+
+    #[test]
+    fn test_point() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Point {
+            x: f64,
+            y: f64,
+        }
+
+        let value = ValueReceive::Cartesian2D(Cartesian2D::new(1.0, 2.0));
+        assert_eq!(value.to::<Point>().unwrap(), Point { x: 1.0, y: 2.0 });
+    }
+
+    #[test]
+    fn test_vector() {
+        let value = ValueReceive::Vector(Vector::I8(vec![1, 2, 3]));
+        assert_eq!(value.to::<Vec<i64>>().unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_enum() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        enum Shape {
+            Empty,
+            Circle(f64),
+            Rect { w: i64, h: i64 },
+        }
+
+        let value = ValueReceive::String("Empty".into());
+        assert_eq!(value.to::<Shape>().unwrap(), Shape::Empty);
+        let value = map([("Circle", ValueReceive::Float(1.0))]);
+        assert_eq!(value.to::<Shape>().unwrap(), Shape::Circle(1.0));
+        let value = map([(
+            "Rect",
+            map([
+                ("w", ValueReceive::Integer(1)),
+                ("h", ValueReceive::Integer(2)),
+            ]),
+        )]);
+        assert_eq!(value.to::<Shape>().unwrap(), Shape::Rect { w: 1, h: 2 });
+    }
+
+    #[test]
+    fn test_temporal_is_rejected() {
+        let value = ValueReceive::Date(Date::from_ordinal(0).unwrap());
+        let err = value.to::<String>().unwrap_err();
+        assert_eq!(err.to_string(), "invalid type: date, expected a string");
+    }
+}
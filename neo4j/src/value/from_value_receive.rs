@@ -0,0 +1,558 @@
+// Copyright Rouven Bauer
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use super::ValueReceive;
+use super::graph::{Node, Path, Relationship};
+use super::spatial::{Cartesian2D, Cartesian3D, WGS84_2D, WGS84_3D};
+use super::time::{Date, DateTime, DateTimeFixed, Duration, LocalDateTime, LocalTime, Time};
+use super::value_path::{ValuePathElement, ValuePathError};
+use super::vector::Vector;
+
+// imports for docs
+#[allow(unused)]
+use crate::driver::{EagerResult, Record};
+
+/// Conversion of a [`ValueReceive`] into a Rust type.
+///
+/// This is what powers [`Record::get()`], [`Record::get_index()`], and
+/// [`EagerResult::into_rows()`].
+///
+/// Implementations are provided for
+///  * [`ValueReceive`] itself (no conversion),
+///  * [`bool`], all integer types (failing if the value is out of range), [`f64`], [`f32`],
+///    and [`String`],
+///  * [`Option<T>`] (mapping [`ValueReceive::Null`] to [`None`]),
+///  * [`Vec<T>`] (from lists, vectors, and bytes),
+///  * [`HashMap<String, T>`] (from maps),
+///  * tuples of up to 12 elements (from lists of the same length),
+///  * the graph, spatial, temporal, and vector types.
+///
+/// # Example
+/// ```
+/// use neo4j::value::FromValueReceive;
+/// use neo4j::ValueReceive;
+///
+/// let value = ValueReceive::List(vec![
+///     ValueReceive::String("Alice".into()),
+///     ValueReceive::Integer(42),
+///     ValueReceive::Null,
+/// ]);
+/// assert_eq!(
+///     <(String, u8, Option<i64>)>::from_value_receive(value.clone()).unwrap(),
+///     (String::from("Alice"), 42, None),
+/// );
+///
+/// let err = <Vec<i64>>::from_value_receive(value).unwrap_err();
+/// assert_eq!(err.to_string(), "at `[0]`: expected integer, found string");
+/// ```
+pub trait FromValueReceive: Sized {
+    /// Try to convert the value into `Self`.
+    fn from_value_receive(value: ValueReceive) -> Result<Self, FromValueReceiveError>;
+}
+
+/// Error returned when a [`FromValueReceive`] conversion fails.
+///
+/// Besides the reason of the failure, the error carries the path to the offending value.
+#[derive(Debug, Clone, Error)]
+#[error("{inner}")]
+pub struct FromValueReceiveError {
+    inner: ValuePathError,
+}
+
+impl FromValueReceiveError {
+    /// Create a new error with the given reason and an empty path.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            inner: ValuePathError::new(message),
+        }
+    }
+
+    /// Create a new error stating that `expected` was expected, but `found` was found.
+    pub fn invalid_type(expected: &str, found: &ValueReceive) -> Self {
+        Self::new(format!("expected {expected}, found {}", found.type_name()))
+    }
+
+    /// The path from the converted root to the value that failed to convert.
+    ///
+    /// The path is empty if the root itself failed to convert.
+    pub fn path(&self) -> &[FromValueReceivePathElement] {
+        self.inner.path()
+    }
+
+    /// The reason the conversion failed (without the path).
+    pub fn message(&self) -> &str {
+        self.inner.message()
+    }
+
+    /// Add an element to the front of the path.
+    ///
+    /// Useful when implementing [`FromValueReceive`] for container types.
+    pub fn prepend_path(self, element: FromValueReceivePathElement) -> Self {
+        Self {
+            inner: self.inner.prepend_path(element),
+        }
+    }
+}
+
+/// A single step in the path of a [`FromValueReceiveError`].
+pub type FromValueReceivePathElement = ValuePathElement;
+
+impl FromValueReceive for ValueReceive {
+    #[inline]
+    fn from_value_receive(value: ValueReceive) -> Result<Self, FromValueReceiveError> {
+        Ok(value)
+    }
+}
+
+macro_rules! impl_via_try_from {
+    ( $($ty:ty => $expected:literal),* $(,)? ) => {
+        $(
+            impl FromValueReceive for $ty {
+                #[inline]
+                fn from_value_receive(value: ValueReceive) -> Result<Self, FromValueReceiveError> {
+                    Self::try_from(value)
+                        .map_err(|value| FromValueReceiveError::invalid_type($expected, &value))
+                }
+            }
+        )*
+    };
+}
+
+impl_via_try_from!(
+    bool => "boolean",
+    i64 => "integer",
+    f64 => "float",
+    String => "string",
+    Node => "node",
+    Relationship => "relationship",
+    Path => "path",
+    Cartesian2D => "cartesian 2D point",
+    Cartesian3D => "cartesian 3D point",
+    WGS84_2D => "WGS-84 2D point",
+    WGS84_3D => "WGS-84 3D point",
+    Duration => "duration",
+    LocalTime => "local time",
+    Time => "time",
+    Date => "date",
+    LocalDateTime => "local datetime",
+    DateTime => "datetime",
+    DateTimeFixed => "datetime with fixed offset",
+    Vector => "vector",
+);
+
+macro_rules! impl_integer {
+    ( $($ty:ty),* $(,)? ) => {
+        $(
+            impl FromValueReceive for $ty {
+                fn from_value_receive(value: ValueReceive) -> Result<Self, FromValueReceiveError> {
+                    let value = i64::from_value_receive(value)?;
+                    <$ty>::try_from(value).map_err(|_| {
+                        FromValueReceiveError::new(format!(
+                            "integer {value} out of range for {}",
+                            stringify!($ty),
+                        ))
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i8, i16, i32, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl FromValueReceive for f32 {
+    /// Converts floats, potentially losing precision.
+    #[inline]
+    fn from_value_receive(value: ValueReceive) -> Result<Self, FromValueReceiveError> {
+        f64::from_value_receive(value).map(|value| value as f32)
+    }
+}
+
+impl<T: FromValueReceive> FromValueReceive for Option<T> {
+    #[inline]
+    fn from_value_receive(value: ValueReceive) -> Result<Self, FromValueReceiveError> {
+        match value {
+            ValueReceive::Null => Ok(None),
+            value => T::from_value_receive(value).map(Some),
+        }
+    }
+}
+
+impl<T: FromValueReceive> FromValueReceive for Vec<T> {
+    fn from_value_receive(value: ValueReceive) -> Result<Self, FromValueReceiveError> {
+        let values: Vec<ValueReceive> = match value {
+            ValueReceive::List(values) => values,
+            ValueReceive::Bytes(bytes) => bytes
+                .into_iter()
+                .map(|byte| ValueReceive::Integer(byte.into()))
+                .collect(),
+            ValueReceive::Vector(vector) => match vector {
+                Vector::F64(v) => v.into_iter().map(ValueReceive::Float).collect(),
+                Vector::F32(v) => v
+                    .into_iter()
+                    .map(|x| ValueReceive::Float(x.into()))
+                    .collect(),
+                Vector::I64(v) => v.into_iter().map(ValueReceive::Integer).collect(),
+                Vector::I32(v) => v
+                    .into_iter()
+                    .map(|x| ValueReceive::Integer(x.into()))
+                    .collect(),
+                Vector::I16(v) => v
+                    .into_iter()
+                    .map(|x| ValueReceive::Integer(x.into()))
+                    .collect(),
+                Vector::I8(v) => v
+                    .into_iter()
+                    .map(|x| ValueReceive::Integer(x.into()))
+                    .collect(),
+            },
+            value => return Err(FromValueReceiveError::invalid_type("list", &value)),
+        };
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| {
+                T::from_value_receive(value)
+                    .map_err(|e| e.prepend_path(FromValueReceivePathElement::Index(i)))
+            })
+            .collect()
+    }
+}
+
+impl<T: FromValueReceive> FromValueReceive for HashMap<String, T> {
+    fn from_value_receive(value: ValueReceive) -> Result<Self, FromValueReceiveError> {
+        let ValueReceive::Map(map) = value else {
+            return Err(FromValueReceiveError::invalid_type("map", &value));
+        };
+        map.into_iter()
+            .map(|(key, value)| match T::from_value_receive(value) {
+                Ok(value) => Ok((key, value)),
+                Err(e) => Err(e.prepend_path(FromValueReceivePathElement::Key(key))),
+            })
+            .collect()
+    }
+}
+
+macro_rules! impl_tuple {
+    ( $len:literal => $($idx:tt $ty:ident),+ ) => {
+        impl<$($ty: FromValueReceive),+> FromValueReceive for ($($ty,)+) {
+            fn from_value_receive(value: ValueReceive) -> Result<Self, FromValueReceiveError> {
+                let values = match value {
+                    ValueReceive::List(values) if values.len() == $len => values,
+                    ValueReceive::List(values) => {
+                        return Err(FromValueReceiveError::new(format!(
+                            "expected list of length {}, found list of length {}",
+                            $len,
+                            values.len(),
+                        )));
+                    }
+                    value => return Err(FromValueReceiveError::invalid_type("list", &value)),
+                };
+                let mut values = values.into_iter();
+                Ok(($(
+                    $ty::from_value_receive(values.next().expect("length checked above"))
+                        .map_err(|e| e.prepend_path(FromValueReceivePathElement::Index($idx)))?,
+                )+))
+            }
+        }
+    };
+}
+
+macro_rules! impl_tuple_all {
+    ($impl:ident) => {
+        $impl!(1 => 0 T0);
+        $impl!(2 => 0 T0, 1 T1);
+        $impl!(3 => 0 T0, 1 T1, 2 T2);
+        $impl!(4 => 0 T0, 1 T1, 2 T2, 3 T3);
+        $impl!(5 => 0 T0, 1 T1, 2 T2, 3 T3, 4 T4);
+        $impl!(6 => 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5);
+        $impl!(7 => 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6);
+        $impl!(8 => 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7);
+        $impl!(9 => 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8);
+        $impl!(10 => 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8, 9 T9);
+        $impl!(11 => 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8, 9 T9, 10 T10);
+        $impl!(12 => 0 T0, 1 T1, 2 T2, 3 T3, 4 T4, 5 T5, 6 T6, 7 T7, 8 T8, 9 T9, 10 T10, 11 T11);
+    };
+}
+
+pub(crate) use impl_tuple_all;
+
+impl_tuple_all!(impl_tuple);
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rstest::*;
+
+    use super::*;
+    use crate::driver::{FromRecord, Record};
+
+    fn record() -> Record {
+        let keys = [
+            Arc::new(String::from("name")),
+            Arc::new(String::from("age")),
+        ];
+        Record::new(
+            &keys,
+            vec![
+                ValueReceive::String("Alice".into()),
+                ValueReceive::Integer(42),
+            ],
+        )
+    }
+
+    #[rstest]
+    #[case(ValueReceive::Integer(255), Some(255))]
+    #[case(ValueReceive::Integer(0), Some(0))]
+    #[case(ValueReceive::Integer(256), None)]
+    #[case(ValueReceive::Integer(-1), None)]
+    #[case(ValueReceive::Float(1.0), None)]
+    fn test_integer_range(#[case] value: ValueReceive, #[case] expected: Option<u8>) {
+        assert_eq!(u8::from_value_receive(value).ok(), expected);
+    }
+
+    #[test]
+    fn test_integer_out_of_range_message() {
+        let err = u8::from_value_receive(ValueReceive::Integer(256)).unwrap_err();
+        assert_eq!(err.to_string(), "integer 256 out of range for u8");
+    }
+
+    #[rstest]
+    #[case(ValueReceive::Null, None)]
+    #[case(ValueReceive::Boolean(true), Some(true))]
+    fn test_option(#[case] value: ValueReceive, #[case] expected: Option<bool>) {
+        assert_eq!(Option::<bool>::from_value_receive(value).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(ValueReceive::List(vec![ValueReceive::Integer(1), ValueReceive::Integer(2)]))]
+    #[case(ValueReceive::Bytes(vec![1, 2]))]
+    #[case(ValueReceive::Vector(Vector::I8(vec![1, 2])))]
+    fn test_vec(#[case] value: ValueReceive) {
+        assert_eq!(Vec::<u8>::from_value_receive(value).unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_nested_error_path() {
+        let value = ValueReceive::Map(HashMap::from([(
+            String::from("friends"),
+            ValueReceive::List(vec![
+                ValueReceive::String("Bob".into()),
+                ValueReceive::Integer(42),
+            ]),
+        )]));
+        let err = HashMap::<String, Vec<String>>::from_value_receive(value).unwrap_err();
+        assert_eq!(
+            err.path(),
+            &[
+                FromValueReceivePathElement::Key("friends".into()),
+                FromValueReceivePathElement::Index(1),
+            ]
+        );
+        assert_eq!(err.message(), "expected string, found integer");
+        assert_eq!(
+            err.to_string(),
+            "at `friends[1]`: expected string, found integer"
+        );
+    }
+
+    #[test]
+    fn test_tuple() {
+        let value = ValueReceive::List(vec![
+            ValueReceive::String("Alice".into()),
+            ValueReceive::Float(1.5),
+        ]);
+        assert_eq!(
+            <(String, f64)>::from_value_receive(value.clone()).unwrap(),
+            (String::from("Alice"), 1.5)
+        );
+        let err = <(String, f64, bool)>::from_value_receive(value).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected list of length 3, found list of length 2"
+        );
+    }
+
+    #[test]
+    fn test_record_get() {
+        let mut record = record();
+        assert_eq!(record.get::<String>("name").unwrap(), "Alice");
+        assert_eq!(record.get_index::<i32>(1).unwrap(), 42);
+        assert_eq!(
+            record.get::<bool>("age").unwrap_err().to_string(),
+            "at `age`: expected boolean, found integer"
+        );
+        assert_eq!(
+            record.get::<bool>("nope").unwrap_err().to_string(),
+            "at `nope`: no such key"
+        );
+        assert_eq!(
+            record.get_index::<bool>(2).unwrap_err().path(),
+            &[FromValueReceivePathElement::Index(2)]
+        );
+        record.take_value("name");
+        assert!(record.get::<String>("name").is_err());
+        assert!(record.get_index::<String>(0).is_err());
+    }
+
+    #[test]
+    fn test_record_into_tuple() {
+        assert_eq!(
+            <(String, u8)>::from_record(record()).unwrap(),
+            (String::from("Alice"), 42)
+        );
+        assert_eq!(
+            <(String, String)>::from_record(record())
+                .unwrap_err()
+                .to_string(),
+            "at `age`: expected string, found integer"
+        );
+        assert_eq!(
+            <(String,)>::from_record(record()).unwrap_err().to_string(),
+            "expected record with 1 values, found 2"
+        );
+    }
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod derive_tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::driver::{FromRecord, Record};
+    use crate::value::FromNode;
+
+    #[derive(Debug, PartialEq, FromNode)]
+    struct Person {
+        name: String,
+        #[neo4j(rename = "yearsOld")]
+        age: u8,
+        nick: Option<String>,
+        #[neo4j(default)]
+        friends: Vec<String>,
+        #[neo4j(labels)]
+        labels: Vec<String>,
+    }
+
+    #[derive(Debug, PartialEq, FromRecord)]
+    struct Row {
+        person: Person,
+        #[neo4j(rename = "score")]
+        points: f64,
+        #[neo4j(default)]
+        rank: i64,
+    }
+
+    fn node(properties: Vec<(&str, ValueReceive)>) -> ValueReceive {
+        ValueReceive::Node(Node {
+            id: 1,
+            labels: vec![String::from("Person")],
+            properties: properties
+                .into_iter()
+                .map(|(k, v)| (String::from(k), v))
+                .collect(),
+            element_id: String::from("1"),
+        })
+    }
+
+    #[test]
+    fn test_from_node() {
+        let value = node(vec![
+            ("name", ValueReceive::String("Alice".into())),
+            ("yearsOld", ValueReceive::Integer(42)),
+            ("extra", ValueReceive::Boolean(true)),
+        ]);
+        assert_eq!(
+            Person::from_value_receive(value).unwrap(),
+            Person {
+                name: String::from("Alice"),
+                age: 42,
+                nick: None,
+                friends: vec![],
+                labels: vec![String::from("Person")],
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_node_missing_property() {
+        let value = node(vec![("yearsOld", ValueReceive::Integer(42))]);
+        let err = Person::from_value_receive(value).unwrap_err();
+        assert_eq!(err.to_string(), "at `name`: no such property");
+    }
+
+    #[test]
+    fn test_from_node_wrong_type() {
+        let err = Person::from_value_receive(ValueReceive::Integer(1)).unwrap_err();
+        assert_eq!(err.to_string(), "expected node, found integer");
+    }
+
+    #[test]
+    fn test_from_record() {
+        let keys = [
+            Arc::new(String::from("person")),
+            Arc::new(String::from("score")),
+        ];
+        let record = Record::new(
+            &keys,
+            vec![
+                node(vec![
+                    ("name", ValueReceive::String("Alice".into())),
+                    ("yearsOld", ValueReceive::Integer(42)),
+                ]),
+                ValueReceive::Float(1.5),
+            ],
+        );
+        let row = Row::from_record(record).unwrap();
+        assert_eq!(row.person.name, "Alice");
+        assert_eq!(row.points, 1.5);
+        assert_eq!(row.rank, 0);
+    }
+
+    #[test]
+    fn test_from_record_error_path() {
+        let keys = [
+            Arc::new(String::from("person")),
+            Arc::new(String::from("score")),
+        ];
+        let record = Record::new(
+            &keys,
+            vec![
+                node(vec![
+                    ("name", ValueReceive::String("Alice".into())),
+                    ("yearsOld", ValueReceive::Integer(256)),
+                ]),
+                ValueReceive::Float(1.5),
+            ],
+        );
+        let err = Row::from_record(record).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "at `person.yearsOld`: integer 256 out of range for u8"
+        );
+    }
+
+    #[test]
+    fn test_from_record_missing_column() {
+        let keys = [Arc::new(String::from("person"))];
+        let record = Record::new(&keys, vec![node(vec![])]);
+        let err = Row::from_record(record).unwrap_err();
+        assert_eq!(err.to_string(), "at `person.name`: no such property");
+    }
+}
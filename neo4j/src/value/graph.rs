@@ -16,7 +16,9 @@ use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use thiserror::Error;
 
-use super::ValueReceive;
+#[cfg(feature = "serde_1")]
+use super::DeserializeError;
+use super::{ValueReceive, ValueSend};
 
 #[derive(Debug, Clone, PartialEq)]
 #[non_exhaustive]
@@ -27,6 +29,43 @@ pub struct Node {
     pub element_id: String,
 }
 
+impl Node {
+    /// Start building a `Node`, e.g., as a test fixture.
+    ///
+    /// # Example
+    /// ```
+    /// use neo4j::value::graph::Node;
+    /// use neo4j::ValueReceive;
+    ///
+    /// let node = Node::builder()
+    ///     .with_element_id("4:db:1")
+    ///     .with_label("Person")
+    ///     .with_property("name", "Alice")
+    ///     .build();
+    ///
+    /// assert_eq!(node.labels, vec![String::from("Person")]);
+    /// assert_eq!(
+    ///     node.properties["name"],
+    ///     ValueReceive::String(String::from("Alice"))
+    /// );
+    /// ```
+    pub fn builder() -> NodeBuilder {
+        NodeBuilder::default()
+    }
+}
+
+#[cfg(feature = "serde_1")]
+impl Node {
+    /// Deserialize the node's properties into any type implementing [`serde::Deserialize`].
+    ///
+    /// See [`ValueReceive::to()`] for details.
+    ///
+    /// Requires the `serde_1` feature.
+    pub fn to<'de, T: serde::Deserialize<'de>>(&'de self) -> Result<T, DeserializeError> {
+        super::from_properties(&self.properties)
+    }
+}
+
 impl Display for Node {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -37,7 +76,7 @@ impl Display for Node {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct Relationship {
     pub id: i64,
     pub start_node_id: i64,
@@ -49,6 +88,43 @@ pub struct Relationship {
     pub end_node_element_id: String,
 }
 
+impl Relationship {
+    /// Start building a `Relationship`, e.g., as a test fixture.
+    ///
+    /// # Example
+    /// ```
+    /// use neo4j::value::graph::{Node, Relationship};
+    ///
+    /// let alice = Node::builder().with_id(1).with_element_id("4:db:1").build();
+    /// let bob = Node::builder().with_id(2).with_element_id("4:db:2").build();
+    /// let knows = Relationship::builder()
+    ///     .with_type("KNOWS")
+    ///     .with_start_node(&alice)
+    ///     .with_end_node(&bob)
+    ///     .with_property("since", 2020)
+    ///     .build();
+    ///
+    /// assert_eq!(knows.start_node_element_id, "4:db:1");
+    /// assert_eq!(knows.end_node_id, 2);
+    /// ```
+    pub fn builder() -> RelationshipBuilder {
+        RelationshipBuilder::default()
+    }
+}
+
+#[cfg(feature = "serde_1")]
+impl Relationship {
+    /// Deserialize the relationship's properties into any type implementing
+    /// [`serde::Deserialize`].
+    ///
+    /// See [`ValueReceive::to()`] for details.
+    ///
+    /// Requires the `serde_1` feature.
+    pub fn to<'de, T: serde::Deserialize<'de>>(&'de self) -> Result<T, DeserializeError> {
+        super::from_properties(&self.properties)
+    }
+}
+
 impl Display for Relationship {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -78,6 +154,44 @@ pub struct Path {
 }
 
 impl Path {
+    /// Assemble a `Path` from its parts, e.g., as a test fixture.
+    ///
+    /// See [`Path::indices`] for the semantics of `indices`.
+    ///
+    /// # Errors
+    /// A [`PathInvariantError`] if the parts violate the
+    /// [invariants of a `Path`](`Path::verify_invariants()`).
+    ///
+    /// # Example
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use neo4j::value::graph::{Node, Path, UnboundRelationship};
+    ///
+    /// let alice = Node::builder().with_element_id("n1").build();
+    /// let bob = Node::builder().with_element_id("n2").build();
+    /// let knows = UnboundRelationship {
+    ///     id: 0,
+    ///     type_: String::from("KNOWS"),
+    ///     properties: HashMap::new(),
+    ///     element_id: String::from("r1"),
+    /// };
+    ///
+    /// // (alice)-[knows]->(bob)
+    /// let path = Path::from_parts(vec![alice, bob], vec![knows.clone()], vec![1, 1]).unwrap();
+    /// assert_eq!(path.to_string(), "(n1)-[r1]->(n2)");
+    ///
+    /// // relationship index out of range
+    /// assert!(Path::from_parts(path.nodes, vec![knows], vec![2, 1]).is_err());
+    /// ```
+    pub fn from_parts(
+        nodes: Vec<Node>,
+        relationships: Vec<UnboundRelationship>,
+        indices: Vec<isize>,
+    ) -> Result<Self, PathInvariantError> {
+        Self::new(nodes, relationships, indices)
+    }
+
     /// Initializes a new `Path` verifying its [invariants](`Path::verify_invariants()`).
     pub(crate) fn new(
         nodes: Vec<Node>,
@@ -320,6 +434,137 @@ pub struct UnboundRelationship {
     pub element_id: String,
 }
 
+/// Builder for [`Node`]s.
+///
+/// See [`Node::builder()`].
+/// Unless configured otherwise, the node has `id` `0`, an empty `element_id`, no labels, and no
+/// properties.
+#[derive(Debug, Clone, Default)]
+pub struct NodeBuilder {
+    id: i64,
+    labels: Vec<String>,
+    properties: HashMap<String, ValueReceive>,
+    element_id: String,
+}
+
+impl NodeBuilder {
+    /// Set the node's (legacy) numeric id.
+    #[inline]
+    pub fn with_id(mut self, id: i64) -> Self {
+        self.id = id;
+        self
+    }
+
+    #[inline]
+    pub fn with_element_id(mut self, element_id: impl Into<String>) -> Self {
+        self.element_id = element_id.into();
+        self
+    }
+
+    /// Add a label to the node.
+    #[inline]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.labels.push(label.into());
+        self
+    }
+
+    /// Add a property to the node, replacing any previous property with the same key.
+    #[inline]
+    pub fn with_property(mut self, key: impl Into<String>, value: impl Into<ValueSend>) -> Self {
+        self.properties.insert(key.into(), value.into().into());
+        self
+    }
+
+    /// Replace all properties of the node.
+    #[inline]
+    pub fn with_properties(mut self, properties: HashMap<String, ValueReceive>) -> Self {
+        self.properties = properties;
+        self
+    }
+
+    pub fn build(self) -> Node {
+        let Self {
+            id,
+            labels,
+            properties,
+            element_id,
+        } = self;
+        Node {
+            id,
+            labels,
+            properties,
+            element_id,
+        }
+    }
+}
+
+/// Builder for [`Relationship`]s.
+///
+/// See [`Relationship::builder()`].
+/// Unless configured otherwise, all ids are `0`, all element ids and the type are empty, and the
+/// relationship has no properties.
+#[derive(Debug, Clone, Default)]
+pub struct RelationshipBuilder {
+    relationship: Relationship,
+}
+
+impl RelationshipBuilder {
+    /// Set the relationship's (legacy) numeric id.
+    #[inline]
+    pub fn with_id(mut self, id: i64) -> Self {
+        self.relationship.id = id;
+        self
+    }
+
+    #[inline]
+    pub fn with_element_id(mut self, element_id: impl Into<String>) -> Self {
+        self.relationship.element_id = element_id.into();
+        self
+    }
+
+    #[inline]
+    pub fn with_type(mut self, type_: impl Into<String>) -> Self {
+        self.relationship.type_ = type_.into();
+        self
+    }
+
+    /// Use the ids of `node` as the relationship's start node ids.
+    #[inline]
+    pub fn with_start_node(mut self, node: &Node) -> Self {
+        self.relationship.start_node_id = node.id;
+        self.relationship.start_node_element_id = node.element_id.clone();
+        self
+    }
+
+    /// Use the ids of `node` as the relationship's end node ids.
+    #[inline]
+    pub fn with_end_node(mut self, node: &Node) -> Self {
+        self.relationship.end_node_id = node.id;
+        self.relationship.end_node_element_id = node.element_id.clone();
+        self
+    }
+
+    /// Add a property to the relationship, replacing any previous property with the same key.
+    #[inline]
+    pub fn with_property(mut self, key: impl Into<String>, value: impl Into<ValueSend>) -> Self {
+        self.relationship
+            .properties
+            .insert(key.into(), value.into().into());
+        self
+    }
+
+    /// Replace all properties of the relationship.
+    #[inline]
+    pub fn with_properties(mut self, properties: HashMap<String, ValueReceive>) -> Self {
+        self.relationship.properties = properties;
+        self
+    }
+
+    pub fn build(self) -> Relationship {
+        self.relationship
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -431,6 +676,50 @@ mod tests {
         path
     }
 
+    #[test]
+    fn test_relationship_builder() {
+        let nodes = get_nodes(2, "Person", true);
+        let relationship = Relationship::builder()
+            .with_id(3)
+            .with_element_id("r1")
+            .with_type("KNOWS")
+            .with_start_node(&nodes[0])
+            .with_end_node(&nodes[1])
+            .with_property("since", 2020)
+            .build();
+
+        assert_eq!(
+            relationship,
+            Relationship {
+                id: 3,
+                start_node_id: 0,
+                end_node_id: 1,
+                type_: String::from("KNOWS"),
+                properties: HashMap::from([(String::from("since"), ValueReceive::Integer(2020))]),
+                element_id: String::from("r1"),
+                start_node_element_id: String::from("n1"),
+                end_node_element_id: String::from("n2"),
+            }
+        );
+    }
+
+    #[test]
+    fn test_path_from_parts() {
+        let path = get_path();
+        assert_eq!(
+            Path::from_parts(
+                path.nodes.clone(),
+                path.relationships.clone(),
+                path.indices.clone()
+            ),
+            Ok(path)
+        );
+        assert_eq!(
+            Path::from_parts(vec![], vec![], vec![]),
+            Err(PathInvariantError::EmptyNodes {})
+        );
+    }
+
     #[test]
     fn test_path_display() {
         let path = get_path();
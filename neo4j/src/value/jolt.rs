@@ -0,0 +1,1536 @@
+// Copyright Rouven Bauer
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Encoding and decoding of values as Jolt, Neo4j's typed JSON format.
+//!
+//! Jolt wraps every value in a JSON object with a single key (the sigil) denoting its type.
+//! This allows to log, cache, or forward values as JSON without losing type information
+//! (e.g., integer vs. float, date vs. string, node vs. map).
+//!
+//! Requires the `serde_json_1` feature.
+//!
+//! # Format
+//! Values are encoded in strict Jolt (v2, i.e., with element ids):
+//!
+//! | Type                   | Jolt                                                          |
+//! |------------------------|---------------------------------------------------------------|
+//! | `Null`                 | `null`                                                        |
+//! | `Boolean`              | `{"?": "true"}`                                               |
+//! | `Integer`              | `{"Z": "42"}`                                                 |
+//! | `Float`                | `{"R": "1.5"}` (also `"NaN"`, `"Infinity"`, `"-Infinity"`)    |
+//! | `String`               | `{"U": "foo"}`                                                |
+//! | `Bytes`                | `{"#": "0A0B"}` (hex)                                         |
+//! | `List`                 | `{"[]": [...]}`                                               |
+//! | `Map`                  | `{"{}": {...}}`                                               |
+//! | temporal types         | `{"T": "2020-01-02T03:04:05+01:00[Europe/Paris]"}` (ISO 8601) |
+//! | spatial types          | `{"@": "SRID=4326;POINT(1.5 2.5)"}` (WKT)                     |
+//! | `Node`                 | `{"()": [element_id, [labels...], {properties...}]}`          |
+//! | `Relationship`         | `{"->": [element_id, start_id, type, end_id, {properties...}]}` |
+//! | `Path`                 | `{"..": [node, relationship, node, ...]}`                     |
+//! | `Vector`               | `{"V": ["FLOAT32", ["1.0", "2.0"]]}`                          |
+//! | `UnsupportedType`      | `{"!": {"name": ..., "minimum_protocol_version": "6.0", "message": ...}}` |
+//!
+//! Vectors and unsupported types are extensions of this driver as Jolt does not define them.
+//! Vector types are named as in Cypher (`FLOAT`, `FLOAT32`, `INTEGER`, `INTEGER32`, `INTEGER16`,
+//! `INTEGER8`).
+//!
+//! Within a path, relationships traversed against their direction are encoded with `"<-"`
+//! instead of `"->"`.
+//! In both cases, the element ids of the nodes are listed in path order (left to right).
+//!
+//! Legacy (integer) ids of nodes and relationships are not encoded.
+//! When decoding, they are taken from the last `:`-separated segment of the element id if
+//! that is an integer (as for element ids generated by Neo4j 5) and are `-1` otherwise.
+//!
+//! [`BrokenValue`](ValueReceive::BrokenValue)s cannot be encoded.
+//!
+//! When decoding, sparse Jolt is accepted as well: plain JSON booleans, strings, numbers, and
+//! arrays decode to booleans, strings, integers or floats, and lists respectively.
+//!
+//! # Example
+//! ```
+//! use neo4j::value::vector::Vector;
+//! use neo4j::ValueReceive;
+//! use serde_json::json;
+//!
+//! let value = ValueReceive::List(vec![
+//!     ValueReceive::Integer(1),
+//!     ValueReceive::Float(1.0),
+//!     ValueReceive::Vector(Vector::F32(vec![0.5, 1.5])),
+//! ]);
+//!
+//! let jolt = value.to_jolt().unwrap();
+//! assert_eq!(
+//!     jolt,
+//!     json!({"[]": [{"Z": "1"}, {"R": "1.0"}, {"V": ["FLOAT32", ["0.5", "1.5"]]}]})
+//! );
+//! assert_eq!(ValueReceive::from_jolt(&jolt).unwrap(), value);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use serde_json::{Map, Value as Json};
+use thiserror::Error;
+
+use super::graph::{Node, Path, Relationship, UnboundRelationship};
+use super::spatial::{Cartesian2D, Cartesian3D, WGS84_2D, WGS84_3D};
+use super::time::chrono::Offset;
+use super::time::{
+    Date, DateComponents, DateTime, DateTimeComponents, DateTimeFixed, Duration, LocalDateTime,
+    LocalTime, Time, TimeComponents,
+};
+use super::unsupported_type::UnsupportedType;
+use super::value_path::{ValuePathElement, ValuePathError};
+use super::vector::Vector;
+use super::{ValueReceive, ValueSend};
+use crate::driver::Record;
+use crate::value::graph::RelationshipDirection;
+
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+/// Error returned when a value cannot be encoded as or decoded from Jolt.
+///
+/// See the [module-level documentation](self) for the format.
+#[derive(Debug, Error)]
+#[error("{inner}")]
+pub struct JoltError {
+    inner: ValuePathError,
+}
+
+impl JoltError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            inner: ValuePathError::new(message),
+        }
+    }
+
+    /// The path from the root to the value that failed to be encoded or decoded.
+    ///
+    /// The path is empty if the root itself failed.
+    pub fn path(&self) -> &[JoltPathElement] {
+        self.inner.path()
+    }
+
+    /// A description of what went wrong.
+    pub fn message(&self) -> &str {
+        self.inner.message()
+    }
+
+    pub(crate) fn prepend_path(self, element: JoltPathElement) -> Self {
+        Self {
+            inner: self.inner.prepend_path(element),
+        }
+    }
+}
+
+/// A single step in the path of a [`JoltError`].
+pub type JoltPathElement = ValuePathElement;
+
+type JoltResult<T> = Result<T, JoltError>;
+
+impl ValueReceive {
+    /// Encode the value as (strict) [Jolt](crate::value::jolt).
+    ///
+    /// Requires the `serde_json_1` feature.
+    ///
+    /// # Errors
+    /// A [`JoltError`] if the value is or contains a [`ValueReceive::BrokenValue`] or a temporal
+    /// value out of the range representable in ISO 8601 by this driver.
+    pub fn to_jolt(&self) -> JoltResult<Json> {
+        encode_receive(self)
+    }
+
+    /// Decode a value from (strict or sparse) [Jolt](crate::value::jolt).
+    ///
+    /// Requires the `serde_json_1` feature.
+    ///
+    /// # Errors
+    /// A [`JoltError`] carrying the path to the offending value if `jolt` is not valid Jolt.
+    pub fn from_jolt(jolt: &Json) -> JoltResult<Self> {
+        decode(jolt, Target::Receive)
+    }
+}
+
+impl ValueSend {
+    /// Encode the value as (strict) [Jolt](crate::value::jolt).
+    ///
+    /// Requires the `serde_json_1` feature.
+    ///
+    /// # Errors
+    /// A [`JoltError`] if the value is or contains a temporal value out of the range
+    /// representable in ISO 8601 by this driver.
+    pub fn to_jolt(&self) -> JoltResult<Json> {
+        encode_send(self)
+    }
+
+    /// Decode a value from (strict or sparse) [Jolt](crate::value::jolt).
+    ///
+    /// Requires the `serde_json_1` feature.
+    ///
+    /// # Errors
+    /// A [`JoltError`] carrying the path to the offending value if `jolt` is not valid Jolt or
+    /// contains a type that cannot be sent to the DBMS (e.g., a node).
+    pub fn from_jolt(jolt: &Json) -> JoltResult<Self> {
+        let value = decode(jolt, Target::Send)?;
+        Ok(ValueSend::try_from(value).expect("decoding for sending rejects unsendable types"))
+    }
+}
+
+/// Append the record as a single line of JSON (mapping keys to Jolt values) to `out`.
+pub(crate) fn write_ndjson_line(record: &Record, out: &mut String) -> JoltResult<()> {
+    out.push('{');
+    for (i, (key, value)) in record.entries_ref().enumerate() {
+        let value =
+            encode_receive(value).map_err(|e| e.prepend_path(JoltPathElement::Key(key.into())))?;
+        if i > 0 {
+            out.push(',');
+        }
+        write!(out, "{}:{value}", Json::String(key.into())).expect("writing to String can't fail");
+    }
+    out.push_str("}\n");
+    Ok(())
+}
+
+fn sigil(sigil: &str, payload: impl Into<Json>) -> Json {
+    let mut map = Map::with_capacity(1);
+    map.insert(String::from(sigil), payload.into());
+    Json::Object(map)
+}
+
+fn encode_receive(value: &ValueReceive) -> JoltResult<Json> {
+    Ok(match value {
+        ValueReceive::Null => Json::Null,
+        ValueReceive::Boolean(v) => encode_bool(*v),
+        ValueReceive::Integer(v) => encode_int(*v),
+        ValueReceive::Float(v) => encode_float(*v),
+        ValueReceive::Bytes(v) => encode_bytes(v),
+        ValueReceive::String(v) => encode_string(v),
+        ValueReceive::List(v) => sigil("[]", encode_list(v, encode_receive)?),
+        ValueReceive::Map(v) => sigil("{}", encode_map(v, encode_receive)?),
+        ValueReceive::Node(v) => encode_node(v)?,
+        ValueReceive::Relationship(v) => sigil(
+            "->",
+            encode_relationship(
+                &v.element_id,
+                &v.start_node_element_id,
+                &v.type_,
+                &v.end_node_element_id,
+                &v.properties,
+            )?,
+        ),
+        ValueReceive::Path(v) => encode_path(v)?,
+        ValueReceive::Cartesian2D(v) => encode_cartesian_2d(v),
+        ValueReceive::Cartesian3D(v) => encode_cartesian_3d(v),
+        ValueReceive::WGS84_2D(v) => encode_wgs84_2d(v),
+        ValueReceive::WGS84_3D(v) => encode_wgs84_3d(v),
+        ValueReceive::Duration(v) => encode_temporal(format_duration(v)),
+        ValueReceive::LocalTime(v) => encode_temporal(format_local_time(v)),
+        ValueReceive::Time(v) => encode_temporal(format_time(v)),
+        ValueReceive::Date(v) => encode_temporal(format_date(*v)?),
+        ValueReceive::LocalDateTime(v) => encode_temporal(format_local_date_time(*v)?),
+        ValueReceive::DateTime(v) => encode_temporal(format_date_time(v)?),
+        ValueReceive::DateTimeFixed(v) => encode_temporal(format_date_time_fixed(*v)?),
+        ValueReceive::Vector(v) => encode_vector(v),
+        ValueReceive::UnsupportedType(v) => encode_unsupported_type(v),
+        ValueReceive::BrokenValue(v) => {
+            return Err(JoltError::new(format!(
+                "cannot encode broken value: {}",
+                v.reason()
+            )));
+        }
+    })
+}
+
+fn encode_send(value: &ValueSend) -> JoltResult<Json> {
+    Ok(match value {
+        ValueSend::Null => Json::Null,
+        ValueSend::Boolean(v) => encode_bool(*v),
+        ValueSend::Integer(v) => encode_int(*v),
+        ValueSend::Float(v) => encode_float(*v),
+        ValueSend::Bytes(v) => encode_bytes(v),
+        ValueSend::String(v) => encode_string(v),
+        ValueSend::List(v) => sigil("[]", encode_list(v, encode_send)?),
+        ValueSend::Map(v) => sigil("{}", encode_map(v, encode_send)?),
+        ValueSend::Cartesian2D(v) => encode_cartesian_2d(v),
+        ValueSend::Cartesian3D(v) => encode_cartesian_3d(v),
+        ValueSend::WGS84_2D(v) => encode_wgs84_2d(v),
+        ValueSend::WGS84_3D(v) => encode_wgs84_3d(v),
+        ValueSend::Duration(v) => encode_temporal(format_duration(v)),
+        ValueSend::LocalTime(v) => encode_temporal(format_local_time(v)),
+        ValueSend::Time(v) => encode_temporal(format_time(v)),
+        ValueSend::Date(v) => encode_temporal(format_date(*v)?),
+        ValueSend::LocalDateTime(v) => encode_temporal(format_local_date_time(*v)?),
+        ValueSend::DateTime(v) => encode_temporal(format_date_time(v)?),
+        ValueSend::DateTimeFixed(v) => encode_temporal(format_date_time_fixed(*v)?),
+        ValueSend::Vector(v) => encode_vector(v),
+    })
+}
+
+fn encode_bool(value: bool) -> Json {
+    sigil("?", value.to_string())
+}
+
+fn encode_int(value: i64) -> Json {
+    sigil("Z", value.to_string())
+}
+
+fn encode_float(value: f64) -> Json {
+    sigil("R", format_float(value))
+}
+
+fn encode_bytes(value: &[u8]) -> Json {
+    let mut hex = String::with_capacity(value.len() * 2);
+    for byte in value {
+        write!(hex, "{byte:02X}").expect("writing to String can't fail");
+    }
+    sigil("#", hex)
+}
+
+fn encode_string(value: &str) -> Json {
+    sigil("U", value)
+}
+
+fn encode_temporal(value: String) -> Json {
+    sigil("T", value)
+}
+
+fn encode_list<V>(values: &[V], encode: fn(&V) -> JoltResult<Json>) -> JoltResult<Json> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| encode(value).map_err(|e| e.prepend_path(JoltPathElement::Index(i))))
+        .collect::<JoltResult<Vec<_>>>()
+        .map(Json::Array)
+}
+
+fn encode_map<V>(
+    values: &HashMap<String, V>,
+    encode: fn(&V) -> JoltResult<Json>,
+) -> JoltResult<Json> {
+    values
+        .iter()
+        .map(|(key, value)| {
+            encode(value)
+                .map(|value| (key.clone(), value))
+                .map_err(|e| e.prepend_path(JoltPathElement::Key(key.clone())))
+        })
+        .collect::<JoltResult<Map<_, _>>>()
+        .map(Json::Object)
+}
+
+fn encode_node(node: &Node) -> JoltResult<Json> {
+    Ok(sigil(
+        "()",
+        vec![
+            Json::from(node.element_id.as_str()),
+            Json::from(node.labels.clone()),
+            encode_map(&node.properties, encode_receive)?,
+        ],
+    ))
+}
+
+fn encode_relationship(
+    element_id: &str,
+    left_element_id: &str,
+    type_: &str,
+    right_element_id: &str,
+    properties: &HashMap<String, ValueReceive>,
+) -> JoltResult<Vec<Json>> {
+    Ok(vec![
+        Json::from(element_id),
+        Json::from(left_element_id),
+        Json::from(type_),
+        Json::from(right_element_id),
+        encode_map(properties, encode_receive)?,
+    ])
+}
+
+fn encode_path(path: &Path) -> JoltResult<Json> {
+    let (start, hops) = path.traverse();
+    let mut entries = Vec::with_capacity(1 + hops.len() * 2);
+    entries.push(encode_node(start)?);
+    let mut previous = start;
+    for (direction, relationship, node) in hops {
+        let sigil_ = match direction {
+            RelationshipDirection::To => "->",
+            RelationshipDirection::From => "<-",
+        };
+        let index = entries.len();
+        let encoded = encode_relationship(
+            &relationship.element_id,
+            &previous.element_id,
+            &relationship.type_,
+            &node.element_id,
+            &relationship.properties,
+        )
+        .map_err(|e| e.prepend_path(JoltPathElement::Index(index)))?;
+        entries.push(sigil(sigil_, encoded));
+        entries.push(
+            encode_node(node).map_err(|e| e.prepend_path(JoltPathElement::Index(index + 1)))?,
+        );
+        previous = node;
+    }
+    Ok(sigil("..", entries))
+}
+
+fn encode_point(srid: i64, coordinates: &[f64]) -> Json {
+    let mut wkt = format!("SRID={srid};POINT");
+    if coordinates.len() == 3 {
+        wkt.push_str(" Z");
+    }
+    wkt.push('(');
+    for (i, coordinate) in coordinates.iter().enumerate() {
+        if i > 0 {
+            wkt.push(' ');
+        }
+        wkt.push_str(&format_float(*coordinate));
+    }
+    wkt.push(')');
+    sigil("@", wkt)
+}
+
+fn encode_cartesian_2d(point: &Cartesian2D) -> Json {
+    encode_point(point.srid(), &[point.x(), point.y()])
+}
+
+fn encode_cartesian_3d(point: &Cartesian3D) -> Json {
+    encode_point(point.srid(), &[point.x(), point.y(), point.z()])
+}
+
+fn encode_wgs84_2d(point: &WGS84_2D) -> Json {
+    encode_point(point.srid(), &[point.longitude(), point.latitude()])
+}
+
+fn encode_wgs84_3d(point: &WGS84_3D) -> Json {
+    encode_point(
+        point.srid(),
+        &[point.longitude(), point.latitude(), point.altitude()],
+    )
+}
+
+fn encode_vector(vector: &Vector) -> Json {
+    fn elements<T>(values: &[T], format: impl Fn(&T) -> String) -> Json {
+        Json::Array(values.iter().map(|v| Json::String(format(v))).collect())
+    }
+
+    let (type_, elements) = match vector {
+        Vector::F64(v) => ("FLOAT", elements(v, |v| format_float(*v))),
+        Vector::F32(v) => ("FLOAT32", elements(v, |v| format_float_32(*v))),
+        Vector::I64(v) => ("INTEGER", elements(v, i64::to_string)),
+        Vector::I32(v) => ("INTEGER32", elements(v, i32::to_string)),
+        Vector::I16(v) => ("INTEGER16", elements(v, i16::to_string)),
+        Vector::I8(v) => ("INTEGER8", elements(v, i8::to_string)),
+    };
+    sigil("V", vec![Json::from(type_), elements])
+}
+
+fn encode_unsupported_type(value: &UnsupportedType) -> Json {
+    let (major, minor) = value.minimum_protocol_version;
+    let mut map = Map::with_capacity(3);
+    map.insert(String::from("name"), Json::from(value.name.as_str()));
+    map.insert(
+        String::from("minimum_protocol_version"),
+        Json::from(format!("{major}.{minor}")),
+    );
+    map.insert(String::from("message"), Json::from(value.message.clone()));
+    sigil("!", map)
+}
+
+fn format_float(value: f64) -> String {
+    if value.is_nan() {
+        String::from("NaN")
+    } else if value.is_infinite() {
+        String::from(if value > 0.0 { "Infinity" } else { "-Infinity" })
+    } else {
+        format!("{value:?}")
+    }
+}
+
+fn format_float_32(value: f32) -> String {
+    if value.is_finite() {
+        format!("{value:?}")
+    } else {
+        format_float(value.into())
+    }
+}
+
+fn parse_float<T: std::str::FromStr + From<f32>>(value: &str) -> Option<T> {
+    match value {
+        "NaN" => Some(f32::NAN.into()),
+        "Infinity" => Some(f32::INFINITY.into()),
+        "-Infinity" => Some(f32::NEG_INFINITY.into()),
+        _ => value.parse().ok(),
+    }
+}
+
+fn out_of_range(type_name: &str) -> JoltError {
+    JoltError::new(format!("{type_name} out of range for ISO 8601 encoding"))
+}
+
+fn format_date(date: Date) -> JoltResult<String> {
+    let components = date.to_components().ok_or_else(|| out_of_range("date"))?;
+    Ok(format_date_components(components))
+}
+
+fn format_date_components(components: DateComponents) -> String {
+    let DateComponents { year, month, day } = components;
+    if (0..=9999).contains(&year) {
+        format!("{year:04}-{month:02}-{day:02}")
+    } else if year < 0 {
+        format!("-{:04}-{month:02}-{day:02}", year.unsigned_abs())
+    } else {
+        format!("+{year}-{month:02}-{day:02}")
+    }
+}
+
+fn format_time_components(components: TimeComponents) -> String {
+    let TimeComponents {
+        hour,
+        min,
+        sec,
+        nano,
+    } = components;
+    let mut time = format!("{hour:02}:{min:02}:{sec:02}");
+    push_fraction(&mut time, nano);
+    time
+}
+
+fn push_fraction(out: &mut String, nanos: u32) {
+    if nanos != 0 {
+        let fraction = format!("{nanos:09}");
+        out.push('.');
+        out.push_str(fraction.trim_end_matches('0'));
+    }
+}
+
+fn format_offset(offset: i32) -> String {
+    if offset == 0 {
+        return String::from("Z");
+    }
+    let sign = if offset < 0 { '-' } else { '+' };
+    let offset = offset.unsigned_abs();
+    let (hours, minutes, seconds) = (offset / 3600, offset % 3600 / 60, offset % 60);
+    match seconds {
+        0 => format!("{sign}{hours:02}:{minutes:02}"),
+        _ => format!("{sign}{hours:02}:{minutes:02}:{seconds:02}"),
+    }
+}
+
+fn format_local_time(time: &LocalTime) -> String {
+    format_time_components(time.to_components())
+}
+
+fn format_time(time: &Time) -> String {
+    // Bolt transmits the time of day as seen at the given offset.
+    let (components, offset) = time.to_utc_components();
+    format_time_components(components) + &format_offset(offset)
+}
+
+fn format_date_time_components(components: DateTimeComponents) -> String {
+    format!(
+        "{}T{}",
+        format_date_components(components.date()),
+        format_time_components(components.time())
+    )
+}
+
+fn format_local_date_time(date_time: LocalDateTime) -> JoltResult<String> {
+    let components = date_time
+        .to_components()
+        .ok_or_else(|| out_of_range("local date time"))?;
+    Ok(format_date_time_components(components))
+}
+
+fn format_utc_timestamp(secs: i64, nanos: u32, offset: i32) -> Option<String> {
+    let local = LocalDateTime::from_timestamp(secs.checked_add(offset.into())?, nanos)?;
+    Some(format_date_time_components(local.to_components()?) + &format_offset(offset))
+}
+
+fn format_date_time(date_time: &DateTime) -> JoltResult<String> {
+    let (secs, nanos) = date_time.utc_timestamp();
+    // Unknown time zones (to this driver) are encoded with their UTC instant.
+    let offset = date_time
+        .to_chrono()
+        .map(|date_time| date_time.offset().fix().local_minus_utc())
+        .unwrap_or(0);
+    let mut formatted =
+        format_utc_timestamp(secs, nanos, offset).ok_or_else(|| out_of_range("date time"))?;
+    write!(formatted, "[{}]", date_time.timezone_name()).expect("writing to String can't fail");
+    Ok(formatted)
+}
+
+fn format_date_time_fixed(date_time: DateTimeFixed) -> JoltResult<String> {
+    let (secs, nanos) = date_time.utc_timestamp();
+    format_utc_timestamp(secs, nanos, date_time.utc_offset())
+        .ok_or_else(|| out_of_range("date time"))
+}
+
+fn format_duration(duration: &Duration) -> String {
+    let mut formatted = String::from("P");
+    let (years, months) = (duration.months / 12, duration.months % 12);
+    if years != 0 {
+        write!(formatted, "{years}Y").expect("writing to String can't fail");
+    }
+    if months != 0 {
+        write!(formatted, "{months}M").expect("writing to String can't fail");
+    }
+    if duration.days != 0 {
+        write!(formatted, "{}D", duration.days).expect("writing to String can't fail");
+    }
+    let nanos = i128::from(duration.secs) * NANOS_PER_SEC + i128::from(duration.nanos);
+    if nanos == 0 && formatted.len() > 1 {
+        return formatted;
+    }
+    formatted.push('T');
+    let (hours, nanos) = (
+        nanos / (3600 * NANOS_PER_SEC),
+        nanos % (3600 * NANOS_PER_SEC),
+    );
+    let (minutes, nanos) = (nanos / (60 * NANOS_PER_SEC), nanos % (60 * NANOS_PER_SEC));
+    if hours != 0 {
+        write!(formatted, "{hours}H").expect("writing to String can't fail");
+    }
+    if minutes != 0 {
+        write!(formatted, "{minutes}M").expect("writing to String can't fail");
+    }
+    if nanos != 0 || (hours == 0 && minutes == 0) {
+        let sign = if nanos < 0 { "-" } else { "" };
+        let nanos = nanos.unsigned_abs();
+        let (secs, nanos) = (nanos / 1_000_000_000, (nanos % 1_000_000_000) as u32);
+        write!(formatted, "{sign}{secs}").expect("writing to String can't fail");
+        push_fraction(&mut formatted, nanos);
+        formatted.push('S');
+    }
+    formatted
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    Receive,
+    Send,
+}
+
+fn decode(jolt: &Json, target: Target) -> JoltResult<ValueReceive> {
+    match jolt {
+        Json::Null => Ok(ValueReceive::Null),
+        Json::Bool(v) => Ok(ValueReceive::Boolean(*v)),
+        Json::Number(v) => match v.as_i64() {
+            Some(v) => Ok(ValueReceive::Integer(v)),
+            None if v.is_u64() => Err(JoltError::new(format!("integer {v} out of range"))),
+            None => Ok(ValueReceive::Float(v.as_f64().expect("number is a float"))),
+        },
+        Json::String(v) => Ok(ValueReceive::String(v.clone())),
+        Json::Array(v) => decode_list(v, target),
+        Json::Object(map) => {
+            let mut entries = map.iter();
+            let (Some((sigil, payload)), None) = (entries.next(), entries.next()) else {
+                return Err(JoltError::new(
+                    "expected an object with a single key (the type sigil)",
+                ));
+            };
+            decode_sigil(sigil, payload, target)
+        }
+    }
+}
+
+fn decode_sigil(sigil: &str, payload: &Json, target: Target) -> JoltResult<ValueReceive> {
+    let unsendable = |type_name: &str| {
+        Err(JoltError::new(format!(
+            "{type_name} cannot be decoded into ValueSend"
+        )))
+    };
+    match sigil {
+        "?" => match expect_str(payload)? {
+            "true" => Ok(ValueReceive::Boolean(true)),
+            "false" => Ok(ValueReceive::Boolean(false)),
+            v => Err(JoltError::new(format!("invalid boolean {v:?}"))),
+        },
+        "Z" => {
+            let v = expect_str(payload)?;
+            v.parse()
+                .map(ValueReceive::Integer)
+                .map_err(|_| JoltError::new(format!("invalid integer {v:?}")))
+        }
+        "R" => {
+            let v = expect_str(payload)?;
+            parse_float(v)
+                .map(ValueReceive::Float)
+                .ok_or_else(|| JoltError::new(format!("invalid float {v:?}")))
+        }
+        "U" => expect_str(payload).map(|v| ValueReceive::String(v.into())),
+        "#" => decode_bytes(expect_str(payload)?).map(ValueReceive::Bytes),
+        "[]" => match payload {
+            Json::Array(v) => decode_list(v, target),
+            _ => Err(JoltError::new("expected an array")),
+        },
+        "{}" => decode_map(payload, target).map(ValueReceive::Map),
+        "T" => {
+            let v = expect_str(payload)?;
+            parse_temporal(v).ok_or_else(|| JoltError::new(format!("invalid temporal {v:?}")))
+        }
+        "@" => {
+            let v = expect_str(payload)?;
+            parse_point(v).ok_or_else(|| JoltError::new(format!("invalid point {v:?}")))
+        }
+        "()" if target == Target::Send => unsendable("nodes"),
+        "()" => decode_node(payload).map(ValueReceive::Node),
+        "->" | "<-" if target == Target::Send => unsendable("relationships"),
+        "->" | "<-" => decode_relationship(sigil, payload).map(ValueReceive::Relationship),
+        ".." if target == Target::Send => unsendable("paths"),
+        ".." => decode_path(payload).map(ValueReceive::Path),
+        "V" => decode_vector(payload).map(ValueReceive::Vector),
+        "!" if target == Target::Send => unsendable("unsupported types"),
+        "!" => decode_unsupported_type(payload).map(ValueReceive::UnsupportedType),
+        _ => Err(JoltError::new(format!("unknown type sigil {sigil:?}"))),
+    }
+}
+
+fn expect_str(jolt: &Json) -> JoltResult<&str> {
+    jolt.as_str()
+        .ok_or_else(|| JoltError::new("expected a string"))
+}
+
+fn expect_array<const N: usize>(jolt: &Json) -> JoltResult<&[Json; N]> {
+    jolt.as_array()
+        .and_then(|v| v.as_slice().try_into().ok())
+        .ok_or_else(|| JoltError::new(format!("expected an array of {N} elements")))
+}
+
+fn decode_list(values: &[Json], target: Target) -> JoltResult<ValueReceive> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| decode(v, target).map_err(|e| e.prepend_path(JoltPathElement::Index(i))))
+        .collect::<JoltResult<_>>()
+        .map(ValueReceive::List)
+}
+
+fn decode_map(jolt: &Json, target: Target) -> JoltResult<HashMap<String, ValueReceive>> {
+    let Json::Object(map) = jolt else {
+        return Err(JoltError::new("expected an object"));
+    };
+    map.iter()
+        .map(|(k, v)| {
+            decode(v, target)
+                .map(|v| (k.clone(), v))
+                .map_err(|e| e.prepend_path(JoltPathElement::Key(k.clone())))
+        })
+        .collect()
+}
+
+fn decode_bytes(hex: &str) -> JoltResult<Vec<u8>> {
+    let invalid = || JoltError::new(format!("invalid hex bytes {hex:?}"));
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return Err(invalid());
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid()))
+        .collect()
+}
+
+fn legacy_id(element_id: &str) -> i64 {
+    element_id
+        .rsplit(':')
+        .next()
+        .and_then(|id| id.parse().ok())
+        .unwrap_or(-1)
+}
+
+fn decode_properties(jolt: &Json) -> JoltResult<HashMap<String, ValueReceive>> {
+    decode_map(jolt, Target::Receive)
+}
+
+fn decode_node(jolt: &Json) -> JoltResult<Node> {
+    let [element_id, labels, properties] = expect_array(jolt)?;
+    let element_id = expect_str(element_id)?;
+    let labels = labels
+        .as_array()
+        .and_then(|labels| {
+            labels
+                .iter()
+                .map(|label| label.as_str().map(String::from))
+                .collect::<Option<_>>()
+        })
+        .ok_or_else(|| JoltError::new("expected an array of label strings"))?;
+    Ok(Node {
+        id: legacy_id(element_id),
+        labels,
+        properties: decode_properties(properties)?,
+        element_id: element_id.into(),
+    })
+}
+
+fn decode_relationship(sigil: &str, jolt: &Json) -> JoltResult<Relationship> {
+    let [element_id, left, type_, right, properties] = expect_array(jolt)?;
+    let element_id = expect_str(element_id)?;
+    let (start, end) = match sigil {
+        "->" => (expect_str(left)?, expect_str(right)?),
+        _ => (expect_str(right)?, expect_str(left)?),
+    };
+    Ok(Relationship {
+        id: legacy_id(element_id),
+        start_node_id: legacy_id(start),
+        end_node_id: legacy_id(end),
+        type_: expect_str(type_)?.into(),
+        properties: decode_properties(properties)?,
+        element_id: element_id.into(),
+        start_node_element_id: start.into(),
+        end_node_element_id: end.into(),
+    })
+}
+
+fn decode_path(jolt: &Json) -> JoltResult<Path> {
+    let entries = jolt
+        .as_array()
+        .filter(|entries| entries.len() % 2 == 1)
+        .ok_or_else(|| {
+            JoltError::new("expected an array of alternating nodes and relationships")
+        })?;
+    let mut nodes = Vec::<Node>::new();
+    let mut relationships = Vec::<UnboundRelationship>::new();
+    let mut indices = Vec::with_capacity(entries.len() - 1);
+    let mut previous = String::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let at = |e: JoltError| e.prepend_path(JoltPathElement::Index(i));
+        if i % 2 == 0 {
+            let node = match entry.as_object().and_then(|entry| entry.get("()")) {
+                Some(node) => decode_node(node).map_err(at)?,
+                None => return Err(at(JoltError::new("expected a node"))),
+            };
+            if i > 0 {
+                let (left, right) = relationship_ends(&entries[i - 1]);
+                if (left, right) != (previous.as_str(), node.element_id.as_str()) {
+                    return Err(at(JoltError::new(
+                        "relationship does not connect adjacent nodes",
+                    )));
+                }
+            }
+            previous.clone_from(&node.element_id);
+            let index = match nodes.iter().position(|n| n.element_id == node.element_id) {
+                Some(index) => index,
+                None => {
+                    nodes.push(node);
+                    nodes.len() - 1
+                }
+            };
+            if i > 0 {
+                indices.push(index as isize);
+            }
+        } else {
+            let (sigil, payload) = entry
+                .as_object()
+                .and_then(|entry| entry.iter().next())
+                .filter(|(sigil, _)| matches!(sigil.as_str(), "->" | "<-"))
+                .ok_or_else(|| at(JoltError::new("expected a relationship")))?;
+            let relationship = decode_relationship(sigil, payload).map_err(at)?;
+            let relationship = UnboundRelationship {
+                id: relationship.id,
+                type_: relationship.type_,
+                properties: relationship.properties,
+                element_id: relationship.element_id,
+            };
+            let index = match relationships
+                .iter()
+                .position(|r| r.element_id == relationship.element_id)
+            {
+                Some(index) => index,
+                None => {
+                    relationships.push(relationship);
+                    relationships.len() - 1
+                }
+            };
+            // relationship indices are 1-based and negative if traversed against their direction
+            let index = index as isize + 1;
+            indices.push(if sigil == "->" { index } else { -index });
+        }
+    }
+    Path::new(nodes, relationships, indices).map_err(|e| JoltError::new(e.to_string()))
+}
+
+/// The element ids of the nodes a (well-formed) path relationship lists, in path order.
+fn relationship_ends(jolt: &Json) -> (&str, &str) {
+    let fields = jolt
+        .as_object()
+        .and_then(|entry| entry.values().next())
+        .and_then(Json::as_array)
+        .expect("relationship has been decoded before");
+    (
+        fields[1]
+            .as_str()
+            .expect("relationship has been decoded before"),
+        fields[3]
+            .as_str()
+            .expect("relationship has been decoded before"),
+    )
+}
+
+fn decode_vector(jolt: &Json) -> JoltResult<Vector> {
+    fn elements<T>(jolt: &Json, parse: impl Fn(&str) -> Option<T>) -> JoltResult<Vec<T>> {
+        let values = jolt
+            .as_array()
+            .ok_or_else(|| JoltError::new("expected an array of vector elements"))?;
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                v.as_str().and_then(&parse).ok_or_else(|| {
+                    JoltError::new(format!("invalid vector element {v}"))
+                        .prepend_path(JoltPathElement::Index(i))
+                })
+            })
+            .collect()
+    }
+
+    let [type_, values] = expect_array(jolt)?;
+    Ok(match expect_str(type_)? {
+        "FLOAT" => Vector::F64(elements(values, parse_float)?),
+        "FLOAT32" => Vector::F32(elements(values, parse_float)?),
+        "INTEGER" => Vector::I64(elements(values, |v| v.parse().ok())?),
+        "INTEGER32" => Vector::I32(elements(values, |v| v.parse().ok())?),
+        "INTEGER16" => Vector::I16(elements(values, |v| v.parse().ok())?),
+        "INTEGER8" => Vector::I8(elements(values, |v| v.parse().ok())?),
+        type_ => return Err(JoltError::new(format!("unknown vector type {type_:?}"))),
+    })
+}
+
+fn decode_unsupported_type(jolt: &Json) -> JoltResult<UnsupportedType> {
+    let invalid = || {
+        JoltError::new(
+            "expected an object with name, minimum_protocol_version (\"<major>.<minor>\"), \
+            and message",
+        )
+    };
+    let map = jolt.as_object().ok_or_else(invalid)?;
+    let name = map.get("name").and_then(Json::as_str).ok_or_else(invalid)?;
+    let minimum_protocol_version = map
+        .get("minimum_protocol_version")
+        .and_then(Json::as_str)
+        .and_then(|v| v.split_once('.'))
+        .and_then(|(major, minor)| Some((major.parse().ok()?, minor.parse().ok()?)))
+        .ok_or_else(invalid)?;
+    let message = match map.get("message") {
+        None | Some(Json::Null) => None,
+        Some(Json::String(message)) => Some(message.clone()),
+        Some(_) => return Err(invalid()),
+    };
+    Ok(UnsupportedType {
+        name: name.into(),
+        minimum_protocol_version,
+        message,
+    })
+}
+
+fn parse_point(wkt: &str) -> Option<ValueReceive> {
+    let (srid, point) = wkt.strip_prefix("SRID=")?.split_once(';')?;
+    let srid: i64 = srid.parse().ok()?;
+    let point = point.trim_start().strip_prefix("POINT")?.trim_start();
+    let point = point.strip_prefix('Z').unwrap_or(point).trim_start();
+    let coordinates = point
+        .strip_prefix('(')?
+        .strip_suffix(')')?
+        .split_whitespace()
+        .map(parse_float)
+        .collect::<Option<Vec<f64>>>()?;
+    let value = match *coordinates.as_slice() {
+        [x, y] => match srid {
+            7203 => ValueReceive::Cartesian2D(Cartesian2D::new(x, y)),
+            4326 => ValueReceive::WGS84_2D(WGS84_2D::new(x, y)),
+            _ => return None,
+        },
+        [x, y, z] => match srid {
+            9157 => ValueReceive::Cartesian3D(Cartesian3D::new(x, y, z)),
+            4979 => ValueReceive::WGS84_3D(WGS84_3D::new(x, y, z)),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn parse_temporal(iso: &str) -> Option<ValueReceive> {
+    if iso.starts_with('P') {
+        return parse_duration(iso).map(ValueReceive::Duration);
+    }
+    if let Some((date, time)) = iso.split_once('T') {
+        let date = parse_date(date)?;
+        let (time, zone) = match time.split_once('[') {
+            Some((time, zone)) => (time, Some(zone.strip_suffix(']')?)),
+            None => (time, None),
+        };
+        let (time, offset) = split_offset(time)?;
+        let local =
+            LocalDateTime::from_components(DateTimeComponents::combine(date, parse_time(time)?))?;
+        let (secs, nanos) = local.timestamp();
+        return Some(match (offset, zone) {
+            (None, None) => ValueReceive::LocalDateTime(local),
+            (Some(offset), None) => ValueReceive::DateTimeFixed(DateTimeFixed::from_utc_timestamp(
+                secs.checked_sub(offset.into())?,
+                nanos,
+                offset,
+            )?),
+            (Some(offset), Some(zone)) => ValueReceive::DateTime(DateTime::from_utc_timestamp(
+                secs.checked_sub(offset.into())?,
+                nanos,
+                zone,
+            )?),
+            (None, Some(_)) => return None,
+        });
+    }
+    if iso.contains(':') {
+        let (time, offset) = split_offset(iso)?;
+        let nanos = LocalTime::from_components(parse_time(time)?)?.nanos_since_midnight();
+        return Some(match offset {
+            None => ValueReceive::LocalTime(LocalTime::from_nanos_since_midnight(nanos)?),
+            Some(offset) => ValueReceive::Time(Time::from_nanos_since_midnight(nanos, offset)?),
+        });
+    }
+    Date::from_components(parse_date(iso)?).map(ValueReceive::Date)
+}
+
+fn parse_date(date: &str) -> Option<DateComponents> {
+    let (negative, date) = match date.as_bytes().first()? {
+        b'-' => (true, &date[1..]),
+        b'+' => (false, &date[1..]),
+        _ => (false, date),
+    };
+    let mut parts = date.splitn(3, '-');
+    let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+    if [year, month, day]
+        .iter()
+        .any(|part| part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return None;
+    }
+    let year: i64 = year.parse().ok()?;
+    let year = if negative { -year } else { year };
+    Some(DateComponents::from_ymd(
+        year,
+        month.parse().ok()?,
+        day.parse().ok()?,
+    ))
+}
+
+/// Split a time of day from its (optional) UTC offset in seconds.
+fn split_offset(time: &str) -> Option<(&str, Option<i32>)> {
+    let Some(index) = time.find(['Z', '+', '-']) else {
+        return Some((time, None));
+    };
+    let (time, offset) = time.split_at(index);
+    if offset == "Z" {
+        return Some((time, Some(0)));
+    }
+    let sign = if offset.starts_with('-') { -1 } else { 1 };
+    let mut parts = offset[1..].split(':');
+    let hours: i32 = parts.next()?.parse().ok()?;
+    let minutes: i32 = parts.next().unwrap_or("0").parse().ok()?;
+    let seconds: i32 = parts.next().unwrap_or("0").parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((time, Some(sign * (hours * 3600 + minutes * 60 + seconds))))
+}
+
+fn parse_time(time: &str) -> Option<TimeComponents> {
+    let (time, fraction) = match time.split_once('.') {
+        Some((time, fraction)) => (time, Some(fraction)),
+        None => (time, None),
+    };
+    let mut parts = time.split(':');
+    let hour = parts.next()?.parse().ok()?;
+    let min = parts.next()?.parse().ok()?;
+    let sec = parts
+        .next()
+        .map(str::parse::<u8>)
+        .transpose()
+        .ok()?
+        .unwrap_or(0);
+    if parts.next().is_some() {
+        return None;
+    }
+    let nano = match fraction {
+        None => 0,
+        Some(fraction) => parse_fraction(fraction)?,
+    };
+    Some(TimeComponents::from_hms_nano(hour, min, sec, nano))
+}
+
+fn parse_fraction(fraction: &str) -> Option<u32> {
+    if fraction.is_empty() || fraction.len() > 9 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let padding = 9 - fraction.len() as u32;
+    Some(fraction.parse::<u32>().ok()? * 10u32.pow(padding))
+}
+
+fn parse_duration(iso: &str) -> Option<Duration> {
+    let iso = iso.strip_prefix('P')?;
+    let (date, time) = iso.split_once('T').unwrap_or((iso, ""));
+    if iso.is_empty() || iso.ends_with('T') {
+        return None;
+    }
+    let (mut months, mut days) = (0i64, 0i64);
+    for (number, unit) in duration_components(date)? {
+        let number: i64 = number.parse().ok()?;
+        match unit {
+            'Y' => months = months.checked_add(number.checked_mul(12)?)?,
+            'M' => months = months.checked_add(number)?,
+            'W' => days = days.checked_add(number.checked_mul(7)?)?,
+            'D' => days = days.checked_add(number)?,
+            _ => return None,
+        }
+    }
+    let mut nanos = 0i128;
+    for (number, unit) in duration_components(time)? {
+        let unit_nanos = match unit {
+            'H' => 3600 * NANOS_PER_SEC,
+            'M' => 60 * NANOS_PER_SEC,
+            'S' => {
+                let (secs, fraction) = number.split_once('.').unwrap_or((number, ""));
+                let sign = if secs.starts_with('-') { -1 } else { 1 };
+                let secs: i128 = secs.parse().ok()?;
+                let fraction = match fraction {
+                    "" => 0,
+                    fraction => parse_fraction(fraction)?,
+                };
+                nanos += secs * NANOS_PER_SEC + sign * i128::from(fraction);
+                continue;
+            }
+            _ => return None,
+        };
+        nanos += number.parse::<i128>().ok()?.checked_mul(unit_nanos)?;
+    }
+    let secs = (nanos / NANOS_PER_SEC).try_into().ok()?;
+    let nanos = (nanos % NANOS_PER_SEC) as i32;
+    Duration::new(months, days, secs, nanos)
+}
+
+/// Split e.g. `1Y-2M3.5S` into `[("1", 'Y'), ("-2", 'M'), ("3.5", 'S')]`.
+fn duration_components(iso: &str) -> Option<Vec<(&str, char)>> {
+    let mut components = Vec::new();
+    let mut start = 0;
+    for (i, c) in iso.char_indices() {
+        if c.is_ascii_alphabetic() {
+            if i == start {
+                return None;
+            }
+            components.push((&iso[start..i], c));
+            start = i + 1;
+        }
+    }
+    match start == iso.len() {
+        true => Some(components),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+    use serde_json::json;
+
+    use std::sync::Arc;
+
+    use crate::driver::EagerResult;
+    use crate::summary::Summary;
+    use crate::value::{BrokenValue, BrokenValueInner};
+
+    fn round_trip(value: ValueReceive, jolt: Json) {
+        assert_eq!(value.to_jolt().unwrap(), jolt);
+        assert_eq!(ValueReceive::from_jolt(&jolt).unwrap(), value);
+    }
+
+    #[rstest]
+    #[case(ValueReceive::Null, json!(null))]
+    #[case(ValueReceive::Boolean(true), json!({"?": "true"}))]
+    #[case(ValueReceive::Integer(i64::MIN), json!({"Z": "-9223372036854775808"}))]
+    #[case(ValueReceive::Float(1.0), json!({"R": "1.0"}))]
+    #[case(ValueReceive::Float(-0.1), json!({"R": "-0.1"}))]
+    #[case(ValueReceive::Float(f64::INFINITY), json!({"R": "Infinity"}))]
+    #[case(ValueReceive::Float(f64::NEG_INFINITY), json!({"R": "-Infinity"}))]
+    #[case(ValueReceive::Bytes(vec![0, 10, 255]), json!({"#": "000AFF"}))]
+    #[case(ValueReceive::String(String::from("1")), json!({"U": "1"}))]
+    #[case(
+        ValueReceive::List(vec![ValueReceive::Integer(1), ValueReceive::Null]),
+        json!({"[]": [{"Z": "1"}, null]})
+    )]
+    #[case(
+        ValueReceive::Map(HashMap::from([(String::from("a"), ValueReceive::Boolean(false))])),
+        json!({"{}": {"a": {"?": "false"}}})
+    )]
+    #[case(
+        ValueReceive::Cartesian2D(Cartesian2D::new(1.0, -2.5)),
+        json!({"@": "SRID=7203;POINT(1.0 -2.5)"})
+    )]
+    #[case(
+        ValueReceive::Cartesian3D(Cartesian3D::new(1.0, 2.0, 3.0)),
+        json!({"@": "SRID=9157;POINT Z(1.0 2.0 3.0)"})
+    )]
+    #[case(
+        ValueReceive::WGS84_2D(WGS84_2D::new(12.5, 55.0)),
+        json!({"@": "SRID=4326;POINT(12.5 55.0)"})
+    )]
+    #[case(
+        ValueReceive::WGS84_3D(WGS84_3D::new(12.5, 55.0, 10.0)),
+        json!({"@": "SRID=4979;POINT Z(12.5 55.0 10.0)"})
+    )]
+    #[case(ValueReceive::Vector(Vector::F64(vec![1.5])), json!({"V": ["FLOAT", ["1.5"]]}))]
+    #[case(
+        ValueReceive::Vector(Vector::F32(vec![f32::NEG_INFINITY, 0.1])),
+        json!({"V": ["FLOAT32", ["-Infinity", "0.1"]]})
+    )]
+    #[case(ValueReceive::Vector(Vector::I64(vec![i64::MAX])), json!({"V": ["INTEGER", ["9223372036854775807"]]}))]
+    #[case(ValueReceive::Vector(Vector::I32(vec![-1])), json!({"V": ["INTEGER32", ["-1"]]}))]
+    #[case(ValueReceive::Vector(Vector::I16(vec![])), json!({"V": ["INTEGER16", []]}))]
+    #[case(ValueReceive::Vector(Vector::I8(vec![1, 2])), json!({"V": ["INTEGER8", ["1", "2"]]}))]
+    fn test_round_trip(#[case] value: ValueReceive, #[case] jolt: Json) {
+        round_trip(value, jolt);
+    }
+
+    #[test]
+    fn test_nan() {
+        let jolt = ValueReceive::Float(f64::NAN).to_jolt().unwrap();
+        assert_eq!(jolt, json!({"R": "NaN"}));
+        assert!(
+            ValueReceive::from_jolt(&jolt)
+                .unwrap()
+                .as_float()
+                .unwrap()
+                .is_nan()
+        );
+    }
+
+    #[rstest]
+    #[case(ValueReceive::Date(Date::from_ordinal(0).unwrap()), "1970-01-01")]
+    #[case(
+        ValueReceive::Date(Date::from_components(DateComponents::from_ymd(-1, 2, 3)).unwrap()),
+        "-0001-02-03"
+    )]
+    #[case(
+        ValueReceive::Date(Date::from_components(DateComponents::from_ymd(12345, 2, 3)).unwrap()),
+        "+12345-02-03"
+    )]
+    #[case(
+        ValueReceive::LocalTime(LocalTime::from_nanos_since_midnight(3_723_000_000_000).unwrap()),
+        "01:02:03"
+    )]
+    #[case(
+        ValueReceive::LocalTime(LocalTime::from_nanos_since_midnight(3_723_120_000_000).unwrap()),
+        "01:02:03.12"
+    )]
+    #[case(
+        ValueReceive::Time(Time::from_nanos_since_midnight(3_723_000_000_001, 3600).unwrap()),
+        "01:02:03.000000001+01:00"
+    )]
+    #[case(
+        ValueReceive::Time(Time::from_nanos_since_midnight(0, -3661).unwrap()),
+        "00:00:00-01:01:01"
+    )]
+    #[case(
+        ValueReceive::LocalDateTime(LocalDateTime::from_timestamp(86_400 + 1, 5).unwrap()),
+        "1970-01-02T00:00:01.000000005"
+    )]
+    #[case(
+        ValueReceive::DateTimeFixed(DateTimeFixed::from_utc_timestamp(0, 0, 0).unwrap()),
+        "1970-01-01T00:00:00Z"
+    )]
+    #[case(
+        ValueReceive::DateTimeFixed(DateTimeFixed::from_utc_timestamp(0, 0, -7200).unwrap()),
+        "1969-12-31T22:00:00-02:00"
+    )]
+    #[case(
+        ValueReceive::DateTime(DateTime::from_utc_timestamp(0, 0, "Europe/Paris").unwrap()),
+        "1970-01-01T01:00:00+01:00[Europe/Paris]"
+    )]
+    #[case(
+        ValueReceive::DateTime(DateTime::from_utc_timestamp(1, 0, "Mars/Olympus").unwrap()),
+        "1970-01-01T00:00:01Z[Mars/Olympus]"
+    )]
+    #[case(ValueReceive::Duration(Duration::new(0, 0, 0, 0).unwrap()), "PT0S")]
+    #[case(ValueReceive::Duration(Duration::new(14, 3, 0, 0).unwrap()), "P1Y2M3D")]
+    #[case(
+        ValueReceive::Duration(Duration::new(0, -1, 3723, 500_000_000).unwrap()),
+        "P-1DT1H2M3.5S"
+    )]
+    #[case(
+        ValueReceive::Duration(Duration::new(-13, 0, -3600, -1).unwrap()),
+        "P-1Y-1MT-1H-0.000000001S"
+    )]
+    #[case(ValueReceive::Duration(Duration::new(0, 0, 0, -500_000_000).unwrap()), "PT-0.5S")]
+    fn test_temporal_round_trip(#[case] value: ValueReceive, #[case] iso: &str) {
+        round_trip(value, json!({ "T": iso }));
+    }
+
+    #[rstest]
+    #[case("PT1.5S", Duration::new(0, 0, 1, 500_000_000).unwrap())]
+    #[case("P1W", Duration::new(0, 7, 0, 0).unwrap())]
+    #[case("PT90M", Duration::new(0, 0, 5400, 0).unwrap())]
+    fn test_decode_duration(#[case] iso: &str, #[case] expected: Duration) {
+        assert_eq!(
+            ValueReceive::from_jolt(&json!({ "T": iso })).unwrap(),
+            ValueReceive::Duration(expected)
+        );
+    }
+
+    #[rstest]
+    #[case("P")]
+    #[case("PT")]
+    #[case("P1")]
+    #[case("PYM")]
+    #[case("2020-13-01")]
+    #[case("25:00")]
+    #[case("2020-01-01T00:00[Europe/Paris]")]
+    fn test_decode_invalid_temporal(#[case] iso: &str) {
+        let err = ValueReceive::from_jolt(&json!({ "T": iso })).unwrap_err();
+        assert_eq!(err.message(), format!("invalid temporal {iso:?}"));
+    }
+
+    fn graph() -> (Node, Node, UnboundRelationship) {
+        let alice = Node::builder()
+            .with_id(1)
+            .with_element_id("4:db:1")
+            .with_label("Person")
+            .with_property("name", "Alice")
+            .build();
+        let bob = Node::builder().with_id(2).with_element_id("4:db:2").build();
+        let knows = UnboundRelationship {
+            id: 3,
+            type_: String::from("KNOWS"),
+            properties: HashMap::from([(String::from("since"), ValueReceive::Integer(2020))]),
+            element_id: String::from("5:db:3"),
+        };
+        (alice, bob, knows)
+    }
+
+    #[test]
+    fn test_node_round_trip() {
+        let (alice, _, _) = graph();
+        round_trip(
+            ValueReceive::Node(alice),
+            json!({"()": ["4:db:1", ["Person"], {"name": {"U": "Alice"}}]}),
+        );
+    }
+
+    #[test]
+    fn test_relationship_round_trip() {
+        let (alice, bob, _) = graph();
+        let knows = Relationship::builder()
+            .with_id(3)
+            .with_element_id("5:db:3")
+            .with_type("KNOWS")
+            .with_start_node(&alice)
+            .with_end_node(&bob)
+            .build();
+        round_trip(
+            ValueReceive::Relationship(knows.clone()),
+            json!({"->": ["5:db:3", "4:db:1", "KNOWS", "4:db:2", {}]}),
+        );
+        assert_eq!(
+            ValueReceive::from_jolt(&json!({"<-": ["5:db:3", "4:db:2", "KNOWS", "4:db:1", {}]}))
+                .unwrap(),
+            ValueReceive::Relationship(knows)
+        );
+    }
+
+    #[test]
+    fn test_path_round_trip() {
+        let (alice, bob, knows) = graph();
+        // (alice)-[knows]->(bob)<-[knows]-(alice)
+        let path = Path::new(vec![alice, bob], vec![knows], vec![1, 1, -1, 0]).unwrap();
+        let alice_jolt = json!({"()": ["4:db:1", ["Person"], {"name": {"U": "Alice"}}]});
+        let bob_jolt = json!({"()": ["4:db:2", [], {}]});
+        let props = json!({"since": {"Z": "2020"}});
+        round_trip(
+            ValueReceive::Path(path),
+            json!({"..": [
+                alice_jolt,
+                {"->": ["5:db:3", "4:db:1", "KNOWS", "4:db:2", props]},
+                bob_jolt,
+                {"<-": ["5:db:3", "4:db:2", "KNOWS", "4:db:1", props]},
+                alice_jolt,
+            ]}),
+        );
+    }
+
+    #[test]
+    fn test_path_must_be_connected() {
+        let jolt = json!({"..": [
+            {"()": ["n1", [], {}]},
+            {"->": ["r1", "n1", "KNOWS", "n3", {}]},
+            {"()": ["n2", [], {}]},
+        ]});
+        let err = ValueReceive::from_jolt(&jolt).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "at `[2]`: relationship does not connect adjacent nodes"
+        );
+    }
+
+    #[rstest]
+    #[case("4:db:12", 12)]
+    #[case("12", 12)]
+    #[case("custom", -1)]
+    fn test_legacy_id(#[case] element_id: &str, #[case] id: i64) {
+        let node = ValueReceive::from_jolt(&json!({"()": [element_id, [], {}]})).unwrap();
+        assert_eq!(node.try_into_node().unwrap().id, id);
+    }
+
+    #[test]
+    fn test_unsupported_type_round_trip() {
+        let value = UnsupportedType {
+            name: String::from("Foo"),
+            minimum_protocol_version: (6, 1),
+            message: None,
+        };
+        let jolt = ValueReceive::UnsupportedType(value).to_jolt().unwrap();
+        assert_eq!(
+            jolt,
+            json!({"!": {"name": "Foo", "minimum_protocol_version": "6.1", "message": null}})
+        );
+        let ValueReceive::UnsupportedType(decoded) = ValueReceive::from_jolt(&jolt).unwrap() else {
+            panic!("expected unsupported type");
+        };
+        assert_eq!(decoded.name(), "Foo");
+        assert_eq!(decoded.minimum_protocol_version(), (6, 1));
+        assert_eq!(decoded.message(), None);
+    }
+
+    #[test]
+    fn test_broken_value() {
+        let value = ValueReceive::Map(HashMap::from([(
+            String::from("a"),
+            ValueReceive::List(vec![
+                ValueReceive::Null,
+                ValueReceive::BrokenValue(BrokenValue {
+                    inner: BrokenValueInner::Reason(String::from("oh no")),
+                }),
+            ]),
+        )]));
+
+        let err = value.to_jolt().unwrap_err();
+
+        assert_eq!(
+            err.path(),
+            &[
+                JoltPathElement::Key(String::from("a")),
+                JoltPathElement::Index(1)
+            ]
+        );
+        assert_eq!(
+            err.to_string(),
+            "at `a[1]`: cannot encode broken value: oh no"
+        );
+    }
+
+    #[test]
+    fn test_decode_sparse() {
+        assert_eq!(
+            ValueReceive::from_jolt(&json!([true, "a", 1, 1.5, null])).unwrap(),
+            ValueReceive::List(vec![
+                ValueReceive::Boolean(true),
+                ValueReceive::String(String::from("a")),
+                ValueReceive::Integer(1),
+                ValueReceive::Float(1.5),
+                ValueReceive::Null,
+            ])
+        );
+    }
+
+    #[rstest]
+    #[case(json!({"a": 1, "b": 2}), "expected an object with a single key (the type sigil)")]
+    #[case(json!({"X": "1"}), "unknown type sigil \"X\"")]
+    #[case(json!({"Z": 1}), "expected a string")]
+    #[case(json!({"Z": "1.0"}), "invalid integer \"1.0\"")]
+    #[case(json!({"#": "ABC"}), "invalid hex bytes \"ABC\"")]
+    #[case(json!({"@": "SRID=1;POINT(1 2)"}), "invalid point \"SRID=1;POINT(1 2)\"")]
+    #[case(json!({"V": ["FLOAT16", []]}), "unknown vector type \"FLOAT16\"")]
+    #[case(json!({"{}": {"a": {"[]": [{"?": "yes"}]}}}), "at `a[0]`: invalid boolean \"yes\"")]
+    #[case(json!({"V": ["INTEGER8", ["1", "300"]]}), "at `[1]`: invalid vector element \"300\"")]
+    #[case(json!(18446744073709551615u64), "integer 18446744073709551615 out of range")]
+    fn test_decode_invalid(#[case] jolt: Json, #[case] error: &str) {
+        assert_eq!(
+            ValueReceive::from_jolt(&jolt).unwrap_err().to_string(),
+            error
+        );
+    }
+
+    #[test]
+    fn test_value_send() {
+        let value = ValueSend::List(vec![
+            ValueSend::Integer(1),
+            ValueSend::Date(Date::from_ordinal(1).unwrap()),
+        ]);
+        let jolt = value.to_jolt().unwrap();
+        assert_eq!(jolt, json!({"[]": [{"Z": "1"}, {"T": "1970-01-02"}]}));
+        assert_eq!(ValueSend::from_jolt(&jolt).unwrap(), value);
+    }
+
+    #[rstest]
+    #[case(json!({"()": ["n", [], {}]}), "nodes")]
+    #[case(json!({"->": ["r", "n1", "T", "n2", {}]}), "relationships")]
+    #[case(json!({"..": [{"()": ["n", [], {}]}]}), "paths")]
+    #[case(json!({"!": {"name": "Foo", "minimum_protocol_version": "6.0"}}), "unsupported types")]
+    fn test_value_send_rejects_unsendable(#[case] jolt: Json, #[case] type_name: &str) {
+        let jolt = json!({ "[]": [jolt] });
+        assert_eq!(
+            ValueSend::from_jolt(&jolt).unwrap_err().to_string(),
+            format!("at `[0]`: {type_name} cannot be decoded into ValueSend")
+        );
+    }
+
+    #[test]
+    fn test_record_ndjson_line() {
+        let keys = ["z", "a"].map(|key| Arc::new(String::from(key)));
+        let record = Record::from_entries([
+            (Arc::clone(&keys[0]), ValueReceive::Integer(1)),
+            (
+                Arc::clone(&keys[1]),
+                ValueReceive::String(String::from("\n")),
+            ),
+        ]);
+        let mut out = String::new();
+
+        write_ndjson_line(&record, &mut out).unwrap();
+
+        assert_eq!(out, "{\"z\":{\"Z\":\"1\"},\"a\":{\"U\":\"\\n\"}}\n");
+    }
+
+    #[test]
+    fn test_eager_result_ndjson() {
+        let key = Arc::new(String::from("x"));
+        let broken = ValueReceive::BrokenValue(BrokenValue {
+            inner: BrokenValueInner::Reason(String::from("oh no")),
+        });
+        let records = vec![
+            Record::from_entries([(Arc::clone(&key), ValueReceive::Integer(1))]),
+            Record::from_entries([(Arc::clone(&key), ValueReceive::Integer(2))]),
+        ];
+        let mut result =
+            EagerResult::new(vec![Arc::clone(&key)], records, Summary::builder().build());
+
+        assert_eq!(
+            result.to_ndjson().unwrap(),
+            "{\"x\":{\"Z\":\"1\"}}\n{\"x\":{\"Z\":\"2\"}}\n"
+        );
+
+        result.records.push(Record::from_entries([(key, broken)]));
+        assert_eq!(
+            result.to_ndjson().unwrap_err().to_string(),
+            "at `[2].x`: cannot encode broken value: oh no"
+        );
+    }
+}
@@ -1 +1,847 @@
-// todo: serde fun
+// Copyright Rouven Bauer
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::result::Result as StdResult;
+
+use serde::ser::{
+    self, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+    SerializeTuple, SerializeTupleStruct, SerializeTupleVariant, Serializer,
+};
+use thiserror::Error;
+
+use super::ValueSend;
+use super::spatial::{Cartesian2D, Cartesian3D, WGS84_2D, WGS84_3D};
+use super::time::{Date, DateTime, DateTimeFixed, Duration, LocalDateTime, LocalTime, Time};
+use super::vector::Vector;
+
+type Result<T> = StdResult<T, SerializeError>;
+
+/// Error returned by [`to_value()`] and [`to_parameters()`] if a value cannot be represented as
+/// [`ValueSend`].
+#[derive(Debug, Clone, Error)]
+#[error("{message}")]
+pub struct SerializeError {
+    message: String,
+}
+
+impl ser::Error for SerializeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self {
+            message: msg.to_string(),
+        }
+    }
+}
+
+/// Serialize any type implementing [`serde::Serialize`] into a [`ValueSend`].
+///
+/// Requires the `serde_1` feature.
+///
+/// The mapping follows serde's data model:
+///  * `bool`, integers, floats, `char`s and strings, and bytes map to the corresponding scalar
+///    types. Unsigned integers larger than [`i64::MAX`] cannot be represented and cause an error.
+///  * `None`, `()`, and unit structs map to [`ValueSend::Null`].
+///  * Sequences, tuples, and tuple structs map to [`ValueSend::List`].
+///  * Maps and structs map to [`ValueSend::Map`]. Map keys must serialize to strings.
+///  * Unit variants map to a [`ValueSend::String`] of the variant's name.
+///    All other enum variants map to a [`ValueSend::Map`] with the variant's name as the only key.
+///  * The driver's spatial ([`super::spatial`]), temporal ([`super::time`]), and
+///    [`Vector`] types as well as [`ValueSend`] itself map to the corresponding Bolt
+///    types.
+///
+/// # Example
+/// ```
+/// use std::collections::HashMap;
+///
+/// use neo4j::value::time::Date;
+/// use neo4j::value::to_value;
+/// use neo4j::ValueSend;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Person {
+///     name: String,
+///     born: Date,
+///     nick: Option<String>,
+/// }
+///
+/// let born = Date::from_ordinal(0).unwrap();
+/// let person = Person {
+///     name: "Alice".into(),
+///     born,
+///     nick: None,
+/// };
+/// assert_eq!(
+///     to_value(&person).unwrap(),
+///     ValueSend::Map(HashMap::from([
+///         ("name".into(), ValueSend::String("Alice".into())),
+///         ("born".into(), ValueSend::Date(born)),
+///         ("nick".into(), ValueSend::Null),
+///     ]))
+/// );
+/// ```
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<ValueSend> {
+    value.serialize(ValueSerializer)
+}
+
+/// Serialize any type implementing [`serde::Serialize`] into query parameters.
+///
+/// Requires the `serde_1` feature.
+///
+/// This works like [`to_value()`], but the value must serialize to a map (e.g., a struct).
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+///
+/// use neo4j::value::to_parameters;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Person {
+///     name: String,
+///     age: u8,
+/// }
+///
+/// let person = Person {
+///     name: "Alice".into(),
+///     age: 42,
+/// };
+///
+/// # let driver = doc_test_utils::get_driver();
+/// let result = driver
+///     .execute_query("RETURN $name AS name, $age AS age")
+///     .with_database(Arc::new(String::from("neo4j")))
+///     .with_parameters(to_parameters(&person).unwrap())
+///     .run()
+///     .unwrap();
+/// # assert_eq!(result.records.len(), 1);
+/// ```
+pub fn to_parameters<T: Serialize + ?Sized>(value: &T) -> Result<HashMap<String, ValueSend>> {
+    match to_value(value)? {
+        ValueSend::Map(map) => Ok(map),
+        _ => Err(ser::Error::custom("parameters must serialize to a map")),
+    }
+}
+
+/// Names of newtype structs used to smuggle the driver's types through serde's data model.
+/// [`ValueSerializer`] turns them back into the corresponding [`ValueSend`] variant, all other
+/// serializers see the plain inner values.
+mod token {
+    pub(super) const PREFIX: &str = "$neo4j::private::";
+    pub(super) const CARTESIAN_2D: &str = "$neo4j::private::Cartesian2D";
+    pub(super) const CARTESIAN_3D: &str = "$neo4j::private::Cartesian3D";
+    pub(super) const WGS84_2D: &str = "$neo4j::private::WGS84_2D";
+    pub(super) const WGS84_3D: &str = "$neo4j::private::WGS84_3D";
+    pub(super) const DURATION: &str = "$neo4j::private::Duration";
+    pub(super) const LOCAL_TIME: &str = "$neo4j::private::LocalTime";
+    pub(super) const TIME: &str = "$neo4j::private::Time";
+    pub(super) const DATE: &str = "$neo4j::private::Date";
+    pub(super) const LOCAL_DATE_TIME: &str = "$neo4j::private::LocalDateTime";
+    pub(super) const DATE_TIME: &str = "$neo4j::private::DateTime";
+    pub(super) const DATE_TIME_FIXED: &str = "$neo4j::private::DateTimeFixed";
+    pub(super) const VECTOR_F64: &str = "$neo4j::private::VectorF64";
+    pub(super) const VECTOR_F32: &str = "$neo4j::private::VectorF32";
+    pub(super) const VECTOR_I64: &str = "$neo4j::private::VectorI64";
+    pub(super) const VECTOR_I32: &str = "$neo4j::private::VectorI32";
+    pub(super) const VECTOR_I16: &str = "$neo4j::private::VectorI16";
+    pub(super) const VECTOR_I8: &str = "$neo4j::private::VectorI8";
+}
+
+impl Serialize for Cartesian2D {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(token::CARTESIAN_2D, &(self.x(), self.y()))
+    }
+}
+
+impl Serialize for Cartesian3D {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(token::CARTESIAN_3D, &(self.x(), self.y(), self.z()))
+    }
+}
+
+impl Serialize for WGS84_2D {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(token::WGS84_2D, &(self.longitude(), self.latitude()))
+    }
+}
+
+impl Serialize for WGS84_3D {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(
+            token::WGS84_3D,
+            &(self.longitude(), self.latitude(), self.altitude()),
+        )
+    }
+}
+
+impl Serialize for Duration {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(
+            token::DURATION,
+            &(self.months, self.days, self.secs, self.nanos),
+        )
+    }
+}
+
+impl Serialize for LocalTime {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(token::LOCAL_TIME, &self.nanos_since_midnight())
+    }
+}
+
+impl Serialize for Time {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(
+            token::TIME,
+            &(self.nanos_since_midnight(), self.utc_offset()),
+        )
+    }
+}
+
+impl Serialize for Date {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(token::DATE, &self.ordinal())
+    }
+}
+
+impl Serialize for LocalDateTime {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(token::LOCAL_DATE_TIME, &self.timestamp())
+    }
+}
+
+impl Serialize for DateTime {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        let (secs, nanos) = self.utc_timestamp();
+        serializer.serialize_newtype_struct(token::DATE_TIME, &(secs, nanos, self.timezone_name()))
+    }
+}
+
+impl Serialize for DateTimeFixed {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        let (secs, nanos) = self.utc_timestamp();
+        serializer
+            .serialize_newtype_struct(token::DATE_TIME_FIXED, &(secs, nanos, self.utc_offset()))
+    }
+}
+
+impl Serialize for Vector {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        match self {
+            Vector::F64(v) => serializer.serialize_newtype_struct(token::VECTOR_F64, v),
+            Vector::F32(v) => serializer.serialize_newtype_struct(token::VECTOR_F32, v),
+            Vector::I64(v) => serializer.serialize_newtype_struct(token::VECTOR_I64, v),
+            Vector::I32(v) => serializer.serialize_newtype_struct(token::VECTOR_I32, v),
+            Vector::I16(v) => serializer.serialize_newtype_struct(token::VECTOR_I16, v),
+            Vector::I8(v) => serializer.serialize_newtype_struct(token::VECTOR_I8, v),
+        }
+    }
+}
+
+impl Serialize for ValueSend {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        match self {
+            ValueSend::Null => serializer.serialize_unit(),
+            ValueSend::Boolean(v) => serializer.serialize_bool(*v),
+            ValueSend::Integer(v) => serializer.serialize_i64(*v),
+            ValueSend::Float(v) => serializer.serialize_f64(*v),
+            ValueSend::Bytes(v) => serializer.serialize_bytes(v),
+            ValueSend::String(v) => serializer.serialize_str(v),
+            ValueSend::List(v) => v.serialize(serializer),
+            ValueSend::Map(v) => v.serialize(serializer),
+            ValueSend::Cartesian2D(v) => v.serialize(serializer),
+            ValueSend::Cartesian3D(v) => v.serialize(serializer),
+            ValueSend::WGS84_2D(v) => v.serialize(serializer),
+            ValueSend::WGS84_3D(v) => v.serialize(serializer),
+            ValueSend::Duration(v) => v.serialize(serializer),
+            ValueSend::LocalTime(v) => v.serialize(serializer),
+            ValueSend::Time(v) => v.serialize(serializer),
+            ValueSend::Date(v) => v.serialize(serializer),
+            ValueSend::LocalDateTime(v) => v.serialize(serializer),
+            ValueSend::DateTime(v) => v.serialize(serializer),
+            ValueSend::DateTimeFixed(v) => v.serialize(serializer),
+            ValueSend::Vector(v) => v.serialize(serializer),
+        }
+    }
+}
+
+/// Turn the serialized inner value of one of the [`token`] newtype structs back into the
+/// driver type.
+fn from_token(name: &'static str, value: ValueSend) -> Result<ValueSend> {
+    fn fields<const N: usize>(value: ValueSend) -> Option<[ValueSend; N]> {
+        match value {
+            ValueSend::List(list) => list.try_into().ok(),
+            _ => None,
+        }
+    }
+
+    fn vector<T>(value: ValueSend, convert: impl Fn(ValueSend) -> Option<T>) -> Option<Vec<T>> {
+        match value {
+            ValueSend::List(list) => list.into_iter().map(convert).collect(),
+            _ => None,
+        }
+    }
+
+    fn float(value: ValueSend) -> Option<f64> {
+        match value {
+            ValueSend::Float(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn int<T: TryFrom<i64>>(value: ValueSend) -> Option<T> {
+        match value {
+            ValueSend::Integer(v) => v.try_into().ok(),
+            _ => None,
+        }
+    }
+
+    let value = match name {
+        token::CARTESIAN_2D => fields(value).and_then(|[x, y]| {
+            Some(ValueSend::Cartesian2D(Cartesian2D::new(
+                float(x)?,
+                float(y)?,
+            )))
+        }),
+        token::CARTESIAN_3D => fields(value).and_then(|[x, y, z]| {
+            Some(ValueSend::Cartesian3D(Cartesian3D::new(
+                float(x)?,
+                float(y)?,
+                float(z)?,
+            )))
+        }),
+        token::WGS84_2D => fields(value).and_then(|[longitude, latitude]| {
+            Some(ValueSend::WGS84_2D(WGS84_2D::new(
+                float(longitude)?,
+                float(latitude)?,
+            )))
+        }),
+        token::WGS84_3D => fields(value).and_then(|[longitude, latitude, altitude]| {
+            Some(ValueSend::WGS84_3D(WGS84_3D::new(
+                float(longitude)?,
+                float(latitude)?,
+                float(altitude)?,
+            )))
+        }),
+        token::DURATION => fields(value).and_then(|[months, days, secs, nanos]| {
+            Duration::new(int(months)?, int(days)?, int(secs)?, int(nanos)?)
+                .map(ValueSend::Duration)
+        }),
+        token::LOCAL_TIME => int(value)
+            .and_then(LocalTime::from_nanos_since_midnight)
+            .map(ValueSend::LocalTime),
+        token::TIME => fields(value).and_then(|[nanos, utc_offset]| {
+            Time::from_nanos_since_midnight(int(nanos)?, int(utc_offset)?).map(ValueSend::Time)
+        }),
+        token::DATE => int(value).and_then(Date::from_ordinal).map(ValueSend::Date),
+        token::LOCAL_DATE_TIME => fields(value).and_then(|[secs, nanos]| {
+            LocalDateTime::from_timestamp(int(secs)?, int(nanos)?).map(ValueSend::LocalDateTime)
+        }),
+        token::DATE_TIME => fields(value).and_then(|[secs, nanos, tz_name]| {
+            let ValueSend::String(tz_name) = tz_name else {
+                return None;
+            };
+            DateTime::from_utc_timestamp(int(secs)?, int(nanos)?, tz_name).map(ValueSend::DateTime)
+        }),
+        token::DATE_TIME_FIXED => fields(value).and_then(|[secs, nanos, utc_offset]| {
+            DateTimeFixed::from_utc_timestamp(int(secs)?, int(nanos)?, int(utc_offset)?)
+                .map(ValueSend::DateTimeFixed)
+        }),
+        token::VECTOR_F64 => vector(value, float).map(|v| ValueSend::Vector(Vector::F64(v))),
+        token::VECTOR_F32 => {
+            vector(value, |v| float(v).map(|v| v as f32)).map(|v| ValueSend::Vector(Vector::F32(v)))
+        }
+        token::VECTOR_I64 => vector(value, int).map(|v| ValueSend::Vector(Vector::I64(v))),
+        token::VECTOR_I32 => vector(value, int).map(|v| ValueSend::Vector(Vector::I32(v))),
+        token::VECTOR_I16 => vector(value, int).map(|v| ValueSend::Vector(Vector::I16(v))),
+        token::VECTOR_I8 => vector(value, int).map(|v| ValueSend::Vector(Vector::I8(v))),
+        _ => return Ok(value),
+    };
+    value.ok_or_else(|| {
+        ser::Error::custom(format_args!(
+            "invalid serialized representation of {}",
+            &name[token::PREFIX.len()..]
+        ))
+    })
+}
+
+struct ValueSerializer;
+
+impl Serializer for ValueSerializer {
+    type Ok = ValueSend;
+    type Error = SerializeError;
+    type SerializeSeq = ListSerializer;
+    type SerializeTuple = ListSerializer;
+    type SerializeTupleStruct = ListSerializer;
+    type SerializeTupleVariant = VariantSerializer<ListSerializer>;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = VariantSerializer<MapSerializer>;
+
+    fn serialize_bool(self, v: bool) -> Result<ValueSend> {
+        Ok(ValueSend::Boolean(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<ValueSend> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<ValueSend> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<ValueSend> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<ValueSend> {
+        Ok(ValueSend::Integer(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<ValueSend> {
+        match i64::try_from(v) {
+            Ok(v) => self.serialize_i64(v),
+            Err(_) => Err(ser::Error::custom(format_args!(
+                "integer {v} out of range for a 64-bit signed integer"
+            ))),
+        }
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<ValueSend> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<ValueSend> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<ValueSend> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<ValueSend> {
+        self.serialize_i128(v.into())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<ValueSend> {
+        match i64::try_from(v) {
+            Ok(v) => self.serialize_i64(v),
+            Err(_) => Err(ser::Error::custom(format_args!(
+                "integer {v} out of range for a 64-bit signed integer"
+            ))),
+        }
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<ValueSend> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<ValueSend> {
+        Ok(ValueSend::Float(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<ValueSend> {
+        Ok(ValueSend::String(v.into()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<ValueSend> {
+        Ok(ValueSend::String(v.into()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<ValueSend> {
+        Ok(ValueSend::Bytes(v.into()))
+    }
+
+    fn serialize_none(self) -> Result<ValueSend> {
+        Ok(ValueSend::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<ValueSend> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<ValueSend> {
+        Ok(ValueSend::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<ValueSend> {
+        Ok(ValueSend::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<ValueSend> {
+        Ok(ValueSend::String(variant.into()))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<ValueSend> {
+        let value = value.serialize(self)?;
+        match name.starts_with(token::PREFIX) {
+            true => from_token(name, value),
+            false => Ok(value),
+        }
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<ValueSend> {
+        Ok(ValueSend::Map(HashMap::from([(
+            variant.into(),
+            value.serialize(self)?,
+        )])))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<ListSerializer> {
+        Ok(ListSerializer(Vec::with_capacity(len.unwrap_or_default())))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<ListSerializer> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<ListSerializer> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<VariantSerializer<ListSerializer>> {
+        Ok(VariantSerializer {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<MapSerializer> {
+        Ok(MapSerializer {
+            map: HashMap::with_capacity(len.unwrap_or_default()),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<MapSerializer> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<VariantSerializer<MapSerializer>> {
+        Ok(VariantSerializer {
+            variant,
+            inner: self.serialize_map(Some(len))?,
+        })
+    }
+}
+
+struct ListSerializer(Vec<ValueSend>);
+
+impl SerializeSeq for ListSerializer {
+    type Ok = ValueSend;
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.0.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<ValueSend> {
+        Ok(ValueSend::List(self.0))
+    }
+}
+
+impl SerializeTuple for ListSerializer {
+    type Ok = ValueSend;
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<ValueSend> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl SerializeTupleStruct for ListSerializer {
+    type Ok = ValueSend;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<ValueSend> {
+        SerializeSeq::end(self)
+    }
+}
+
+struct MapSerializer {
+    map: HashMap<String, ValueSend>,
+    key: Option<String>,
+}
+
+impl SerializeMap for MapSerializer {
+    type Ok = ValueSend;
+    type Error = SerializeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        match key.serialize(ValueSerializer)? {
+            ValueSend::String(key) => {
+                self.key = Some(key);
+                Ok(())
+            }
+            _ => Err(ser::Error::custom("map keys must be strings")),
+        }
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = self
+            .key
+            .take()
+            .expect("serialize_value called before serialize_key");
+        self.map.insert(key, value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<ValueSend> {
+        Ok(ValueSend::Map(self.map))
+    }
+}
+
+impl SerializeStruct for MapSerializer {
+    type Ok = ValueSend;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.map
+            .insert(key.into(), value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<ValueSend> {
+        SerializeMap::end(self)
+    }
+}
+
+struct VariantSerializer<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl<S> VariantSerializer<S> {
+    fn wrap(variant: &'static str, value: ValueSend) -> ValueSend {
+        ValueSend::Map(HashMap::from([(variant.into(), value)]))
+    }
+}
+
+impl SerializeTupleVariant for VariantSerializer<ListSerializer> {
+    type Ok = ValueSend;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    fn end(self) -> Result<ValueSend> {
+        Ok(Self::wrap(self.variant, SerializeSeq::end(self.inner)?))
+    }
+}
+
+impl SerializeStructVariant for VariantSerializer<MapSerializer> {
+    type Ok = ValueSend;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<ValueSend> {
+        Ok(Self::wrap(self.variant, SerializeMap::end(self.inner)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rstest::*;
+    use serde::Serialize;
+
+    use super::*;
+
+    fn map<const N: usize>(entries: [(&str, ValueSend); N]) -> ValueSend {
+        ValueSend::Map(entries.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+
+    #[test]
+    fn test_struct() {
+        #[derive(Serialize)]
+        struct Person {
+            name: &'static str,
+            age: u8,
+            nick: Option<String>,
+            tags: Vec<&'static str>,
+            data: Vec<u8>,
+        }
+
+        let person = Person {
+            name: "Alice",
+            age: 42,
+            nick: None,
+            tags: vec!["a", "b"],
+            data: vec![1, 2],
+        };
+        assert_eq!(
+            to_value(&person).unwrap(),
+            map([
+                ("name", ValueSend::String("Alice".into())),
+                ("age", ValueSend::Integer(42)),
+                ("nick", ValueSend::Null),
+                (
+                    "tags",
+                    ValueSend::List(vec![
+                        ValueSend::String("a".into()),
+                        ValueSend::String("b".into()),
+                    ])
+                ),
+                (
+                    "data",
+                    ValueSend::List(vec![ValueSend::Integer(1), ValueSend::Integer(2)])
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_enum() {
+        #[derive(Serialize)]
+        enum Shape {
+            Empty,
+            Circle(f64),
+            Line(i64, i64),
+            Rect { w: i64, h: i64 },
+        }
+
+        assert_eq!(
+            to_value(&Shape::Empty).unwrap(),
+            ValueSend::String("Empty".into())
+        );
+        assert_eq!(
+            to_value(&Shape::Circle(1.5)).unwrap(),
+            map([("Circle", ValueSend::Float(1.5))])
+        );
+        assert_eq!(
+            to_value(&Shape::Line(1, 2)).unwrap(),
+            map([(
+                "Line",
+                ValueSend::List(vec![ValueSend::Integer(1), ValueSend::Integer(2)])
+            )])
+        );
+        assert_eq!(
+            to_value(&Shape::Rect { w: 1, h: 2 }).unwrap(),
+            map([(
+                "Rect",
+                map([("w", ValueSend::Integer(1)), ("h", ValueSend::Integer(2))])
+            )])
+        );
+    }
+
+    #[rstest]
+    #[case(ValueSend::Cartesian2D(Cartesian2D::new(1.0, 2.0)))]
+    #[case(ValueSend::Cartesian3D(Cartesian3D::new(1.0, 2.0, 3.0)))]
+    #[case(ValueSend::WGS84_2D(WGS84_2D::new(1.0, 2.0)))]
+    #[case(ValueSend::WGS84_3D(WGS84_3D::new(1.0, 2.0, 3.0)))]
+    #[case(ValueSend::Duration(Duration::new(1, -2, 3, 4).unwrap()))]
+    #[case(ValueSend::LocalTime(LocalTime::from_nanos_since_midnight(1234).unwrap()))]
+    #[case(ValueSend::Time(Time::from_nanos_since_midnight(1234, -3600).unwrap()))]
+    #[case(ValueSend::Date(Date::from_ordinal(-42).unwrap()))]
+    #[case(ValueSend::LocalDateTime(LocalDateTime::from_timestamp(-1, 2).unwrap()))]
+    #[case(ValueSend::DateTime(DateTime::from_utc_timestamp(1, 2, "Europe/Paris").unwrap()))]
+    #[case(ValueSend::DateTimeFixed(DateTimeFixed::from_utc_timestamp(1, 2, 3600).unwrap()))]
+    #[case(ValueSend::Vector(Vector::F64(vec![1.5, -2.0])))]
+    #[case(ValueSend::Vector(Vector::F32(vec![1.5, -2.0])))]
+    #[case(ValueSend::Vector(Vector::I64(vec![i64::MIN, i64::MAX])))]
+    #[case(ValueSend::Vector(Vector::I32(vec![i32::MIN, i32::MAX])))]
+    #[case(ValueSend::Vector(Vector::I16(vec![i16::MIN, i16::MAX])))]
+    #[case(ValueSend::Vector(Vector::I8(vec![i8::MIN, i8::MAX])))]
+    #[case(ValueSend::Bytes(vec![1, 2, 3]))]
+    #[case(ValueSend::List(vec![ValueSend::Null, ValueSend::Boolean(true)]))]
+    fn test_driver_types_round_trip(#[case] value: ValueSend) {
+        assert_eq!(to_value(&value).unwrap(), value);
+        let nested = map([("value", value.clone())]);
+        assert_eq!(to_value(&nested).unwrap(), nested);
+    }
+
+    #[test]
+    fn test_to_parameters() {
+        let params = BTreeMap::from([("a", 1), ("b", 2)]);
+        assert_eq!(
+            to_parameters(&params).unwrap(),
+            HashMap::from([
+                ("a".into(), ValueSend::Integer(1)),
+                ("b".into(), ValueSend::Integer(2)),
+            ])
+        );
+        let err = to_parameters(&[1, 2]).unwrap_err();
+        assert_eq!(err.to_string(), "parameters must serialize to a map");
+    }
+
+    #[test]
+    fn test_integer_out_of_range() {
+        assert_eq!(
+            to_value(&(i64::MAX as u64)).unwrap(),
+            ValueSend::Integer(i64::MAX)
+        );
+        let err = to_value(&(i64::MAX as u64 + 1)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "integer 9223372036854775808 out of range for a 64-bit signed integer"
+        );
+    }
+
+    #[test]
+    fn test_non_string_map_key() {
+        let err = to_value(&BTreeMap::from([(1, 2)])).unwrap_err();
+        assert_eq!(err.to_string(), "map keys must be strings");
+    }
+}
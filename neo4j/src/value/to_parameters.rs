@@ -0,0 +1,114 @@
+// Copyright Rouven Bauer
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use super::ValueSend;
+
+// imports for docs
+#[allow(unused)]
+use crate::driver::ExecuteQueryBuilder;
+
+/// Conversion of a Rust type into query parameters.
+///
+/// The returned map can be passed to, e.g., [`ExecuteQueryBuilder::with_parameters()`].
+///
+/// With the `derive` feature enabled, this trait can be derived for structs with named fields
+/// (see `neo4j_derive::ToParameters`).
+///
+/// # Example
+/// ```
+/// use std::collections::HashMap;
+///
+/// use neo4j::value::ToParameters;
+/// use neo4j::ValueSend;
+///
+/// struct NewPerson {
+///     name: String,
+/// }
+///
+/// impl ToParameters for NewPerson {
+///     fn to_parameters(&self) -> HashMap<String, ValueSend> {
+///         HashMap::from([(String::from("name"), self.name.clone().into())])
+///     }
+/// }
+///
+/// let parameters = NewPerson {
+///     name: String::from("Alice"),
+/// }
+/// .to_parameters();
+/// assert_eq!(parameters["name"], ValueSend::from("Alice"));
+/// ```
+pub trait ToParameters {
+    /// Convert `self` into a map of parameter names to values.
+    fn to_parameters(&self) -> HashMap<String, ValueSend>;
+}
+
+impl<T: Clone + Into<ValueSend>> ToParameters for HashMap<String, T> {
+    fn to_parameters(&self) -> HashMap<String, ValueSend> {
+        self.iter()
+            .map(|(key, value)| (key.clone(), value.clone().into()))
+            .collect()
+    }
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod tests {
+    use super::*;
+    use crate::value::ToParameters;
+
+    #[derive(Clone, ToParameters)]
+    struct NewPerson {
+        name: String,
+        #[neo4j(rename = "yearsOld")]
+        age: u8,
+        nick: Option<String>,
+    }
+
+    #[test]
+    fn test_derive() {
+        let parameters = NewPerson {
+            name: String::from("Alice"),
+            age: 42,
+            nick: None,
+        }
+        .to_parameters();
+        assert_eq!(
+            parameters,
+            HashMap::from([
+                (String::from("name"), ValueSend::from("Alice")),
+                (String::from("yearsOld"), ValueSend::Integer(42)),
+                (String::from("nick"), ValueSend::Null),
+            ])
+        );
+    }
+
+    #[derive(Clone, ToParameters)]
+    #[neo4j(crate = "crate")]
+    struct Tag {
+        name: String,
+    }
+
+    #[test]
+    fn test_derive_with_crate_path() {
+        let parameters = Tag {
+            name: String::from("rust"),
+        }
+        .to_parameters();
+        assert_eq!(
+            parameters,
+            HashMap::from([(String::from("name"), ValueSend::from("rust"))])
+        );
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright Rouven Bauer
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{Display, Formatter};
+
+/// A single step in the path to a nested value.
+///
+/// Value conversion errors (e.g., [`FromValueReceiveError`](super::FromValueReceiveError)) carry
+/// a list of these to point at the value that failed to convert.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ValuePathElement {
+    /// A key of a map, a column of a record, or a property of a node or relationship.
+    Key(String),
+    /// An index into a list, a path, or a list of records.
+    Index(usize),
+}
+
+/// The reason a value conversion failed together with the path to the offending value.
+///
+/// Displayed as ``at `a[1].b`: <message>`` (or just the message if the path is empty).
+#[derive(Debug, Clone)]
+pub(crate) struct ValuePathError {
+    path: Vec<ValuePathElement>,
+    message: String,
+}
+
+impl ValuePathError {
+    pub(crate) fn new(message: impl Into<String>) -> Self {
+        Self {
+            path: Vec::new(),
+            message: message.into(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn path(&self) -> &[ValuePathElement] {
+        &self.path
+    }
+
+    #[inline]
+    pub(crate) fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub(crate) fn prepend_path(mut self, element: ValuePathElement) -> Self {
+        self.path.insert(0, element);
+        self
+    }
+}
+
+impl Display for ValuePathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.path.is_empty() {
+            f.write_str("at `")?;
+            for (i, element) in self.path.iter().enumerate() {
+                match element {
+                    ValuePathElement::Key(key) if i == 0 => write!(f, "{key}")?,
+                    ValuePathElement::Key(key) => write!(f, ".{key}")?,
+                    ValuePathElement::Index(index) => write!(f, "[{index}]")?,
+                }
+            }
+            f.write_str("`: ")?;
+        }
+        f.write_str(&self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(vec![], "oops")]
+    #[case(vec![ValuePathElement::Key("a".into())], "at `a`: oops")]
+    #[case(vec![ValuePathElement::Index(2)], "at `[2]`: oops")]
+    #[case(
+        vec![
+            ValuePathElement::Key("a".into()),
+            ValuePathElement::Index(1),
+            ValuePathElement::Key("b".into()),
+        ],
+        "at `a[1].b`: oops"
+    )]
+    fn test_display(#[case] path: Vec<ValuePathElement>, #[case] expected: &str) {
+        let err = path
+            .iter()
+            .rev()
+            .cloned()
+            .fold(ValuePathError::new("oops"), ValuePathError::prepend_path);
+        assert_eq!(err.path(), path);
+        assert_eq!(err.message(), "oops");
+        assert_eq!(err.to_string(), expected);
+    }
+}
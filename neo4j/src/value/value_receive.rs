@@ -16,6 +16,8 @@ use std::collections::{HashMap, VecDeque};
 
 use itertools::Itertools;
 
+#[cfg(feature = "serde_1")]
+use super::DeserializeError;
 use super::spatial;
 use super::time;
 use super::value_send::ValueSend;
@@ -63,6 +65,37 @@ impl ValueReceive {
     pub fn is_null(&self) -> bool {
         matches!(self, ValueReceive::Null)
     }
+
+    /// Human-readable name of the value's type for error messages.
+    pub(crate) fn type_name(&self) -> &'static str {
+        match self {
+            ValueReceive::Null => "null",
+            ValueReceive::Boolean(_) => "boolean",
+            ValueReceive::Integer(_) => "integer",
+            ValueReceive::Float(_) => "float",
+            ValueReceive::Bytes(_) => "bytes",
+            ValueReceive::String(_) => "string",
+            ValueReceive::List(_) => "list",
+            ValueReceive::Map(_) => "map",
+            ValueReceive::Node(_) => "node",
+            ValueReceive::Relationship(_) => "relationship",
+            ValueReceive::Path(_) => "path",
+            ValueReceive::Cartesian2D(_) => "cartesian 2D point",
+            ValueReceive::Cartesian3D(_) => "cartesian 3D point",
+            ValueReceive::WGS84_2D(_) => "WGS-84 2D point",
+            ValueReceive::WGS84_3D(_) => "WGS-84 3D point",
+            ValueReceive::Duration(_) => "duration",
+            ValueReceive::LocalTime(_) => "local time",
+            ValueReceive::Time(_) => "time",
+            ValueReceive::Date(_) => "date",
+            ValueReceive::LocalDateTime(_) => "local datetime",
+            ValueReceive::DateTime(_) => "datetime",
+            ValueReceive::DateTimeFixed(_) => "datetime with fixed offset",
+            ValueReceive::Vector(_) => "vector",
+            ValueReceive::UnsupportedType(_) => "unsupported type",
+            ValueReceive::BrokenValue(_) => "broken value",
+        }
+    }
 }
 
 impl TryFrom<ValueReceive> for bool {
@@ -1129,6 +1162,52 @@ impl ValueReceive {
     }
 }
 
+#[cfg(feature = "serde_1")]
+impl ValueReceive {
+    /// Deserialize the value into any type implementing [`serde::Deserialize`].
+    ///
+    /// Lists (and vectors) map to sequences, maps to maps, nodes and relationships to maps of
+    /// their properties, and points to maps of their srid and coordinates
+    /// (`x`, `y`, `z` or `longitude`, `latitude`, `altitude`).
+    /// Enums can be deserialized from strings (unit variants) or maps with a single key (the
+    /// variant's name).
+    /// Paths, temporal values, and unsupported or broken values cannot be deserialized.
+    ///
+    /// Requires the `serde_1` feature.
+    ///
+    /// # Errors
+    /// A [`DeserializeError`] carrying the path to the offending value.
+    ///
+    /// # Example
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use neo4j::ValueReceive;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize, PartialEq)]
+    /// struct Person {
+    ///     name: String,
+    ///     age: u8,
+    /// }
+    ///
+    /// let value = ValueReceive::Map(HashMap::from([
+    ///     ("name".into(), ValueReceive::String("Alice".into())),
+    ///     ("age".into(), ValueReceive::Integer(42)),
+    /// ]));
+    /// assert_eq!(
+    ///     value.to::<Person>().unwrap(),
+    ///     Person {
+    ///         name: "Alice".into(),
+    ///         age: 42
+    ///     }
+    /// );
+    /// ```
+    pub fn to<'de, T: serde::Deserialize<'de>>(&'de self) -> Result<T, DeserializeError> {
+        super::from_value(self)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BrokenValue {
     pub(crate) inner: BrokenValueInner,
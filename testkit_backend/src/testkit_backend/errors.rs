@@ -142,6 +142,11 @@ impl From<Neo4jError> for TestKitError {
                 }
                 .into()
             }
+            Neo4jError::InvalidUsage { message, .. } => TestKitError::driver_error_client_only(
+                String::from("UsageError"),
+                message,
+                retryable,
+            ),
             Neo4jError::Timeout { message, .. } => {
                 TestKitError::driver_error_client_only(String::from("Timeout"), message, retryable)
             }
@@ -378,6 +383,11 @@ impl TestKitError {
                 retryable,
             }
             .into(),
+            Neo4jError::InvalidUsage { message, .. } => TestKitError::driver_error_client_only(
+                String::from("UsageError"),
+                message.clone(),
+                retryable,
+            ),
             Neo4jError::Timeout { message, .. } => TestKitError::driver_error_client_only(
                 String::from("Timeout"),
                 message.clone(),
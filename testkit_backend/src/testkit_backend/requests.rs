@@ -894,8 +894,27 @@ impl Request {
                 Duration::from_millis(connection_acquisition_timeout_ms),
             );
         }
-        if client_certificate.is_some() || client_certificate_provider_id.is_some() {
-            return Err(TestKitError::backend_err("mTLS not (yet) supported"));
+        if client_certificate_provider_id.is_some() {
+            return Err(TestKitError::backend_err(
+                "client certificate providers not (yet) supported",
+            ));
+        }
+        if let Some(ClientCertificate::ClientCertificate {
+            certfile,
+            keyfile,
+            password,
+        }) = client_certificate
+        {
+            if password.is_some() {
+                return Err(TestKitError::backend_err(
+                    "password protected client keys not supported",
+                ));
+            }
+            let certificate = neo4j::driver::client_certificate::ClientCertificate::from_pem_files(
+                certfile, keyfile,
+            )
+            .map_err(|e| TestKitError::backend_err(e.to_string()))?;
+            connection_config = connection_config.with_client_certificate(certificate);
         }
         if let Some(filter) = load_notification_filter(
             notifications_min_severity,
@@ -1498,7 +1517,7 @@ impl Request {
             .get_connection_pool_metrics(GetConnectionPoolMetrics { address })
             .result?;
         backend.send(&Response::ConnectionPoolMetrics {
-            in_use: metrics.in_use,
+            in_use: metrics.in_use + metrics.creating,
             idle: metrics.idle,
         })
     }